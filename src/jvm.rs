@@ -1,2 +1,8 @@
 pub mod data;
+pub mod array;
+pub mod string;
 pub mod exec;
+pub mod npe;
+pub mod inflate;
+pub mod zip;
+pub mod classpath;
@@ -0,0 +1,164 @@
+use super::exec::{RuntimeValue, JVMError};
+
+// https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-6.html#jvms-6.5.newarray
+pub const T_BOOLEAN:u8 = 4;
+pub const T_CHAR:u8 = 5;
+pub const T_FLOAT:u8 = 6;
+pub const T_DOUBLE:u8 = 7;
+pub const T_BYTE:u8 = 8;
+pub const T_SHORT:u8 = 9;
+pub const T_INT:u8 = 10;
+pub const T_LONG:u8 = 11;
+
+/* primitive arrays are stored unboxed, so an int[] takes 4 bytes per element */
+#[derive(Clone)]
+pub enum ArrayElements {
+    Boolean(Vec<u8>),
+    Byte(Vec<i8>),
+    Char(Vec<u16>),
+    Short(Vec<i16>),
+    Int(Vec<i32>),
+    Long(Vec<i64>),
+    Float(Vec<f32>),
+    Double(Vec<f64>),
+    Reference(Vec<RuntimeValue>),
+}
+
+#[derive(Clone)]
+pub struct JVMArray {
+    // the class name of the array, which is its descriptor, such as [I or [[Ljava/lang/String;
+    pub class: String,
+    pub elements: ArrayElements,
+}
+
+impl JVMArray {
+    /* create an array of the given class filled with the default value of the component type */
+    pub fn new(class: &str, length: i32) -> Result<JVMArray, JVMError> {
+        if length < 0 {
+            return Err(JVMError::exception("java/lang/NegativeArraySizeException", Some(length.to_string())));
+        }
+
+        let length = length as usize;
+        let elements = match class.strip_prefix('[') {
+            Some("Z") => ArrayElements::Boolean(vec![0; length]),
+            Some("B") => ArrayElements::Byte(vec![0; length]),
+            Some("C") => ArrayElements::Char(vec![0; length]),
+            Some("S") => ArrayElements::Short(vec![0; length]),
+            Some("I") => ArrayElements::Int(vec![0; length]),
+            Some("J") => ArrayElements::Long(vec![0; length]),
+            Some("F") => ArrayElements::Float(vec![0.0; length]),
+            Some("D") => ArrayElements::Double(vec![0.0; length]),
+            Some(_) => ArrayElements::Reference(vec![RuntimeValue::Null; length]),
+            None => {
+                return Err(format!("not an array class: {}", class).into());
+            }
+        };
+
+        return Ok(JVMArray{
+            class: class.to_string(),
+            elements: elements,
+        });
+    }
+
+    pub fn length(self: &JVMArray) -> usize {
+        match &self.elements {
+            ArrayElements::Boolean(elements) => elements.len(),
+            ArrayElements::Byte(elements) => elements.len(),
+            ArrayElements::Char(elements) => elements.len(),
+            ArrayElements::Short(elements) => elements.len(),
+            ArrayElements::Int(elements) => elements.len(),
+            ArrayElements::Long(elements) => elements.len(),
+            ArrayElements::Float(elements) => elements.len(),
+            ArrayElements::Double(elements) => elements.len(),
+            ArrayElements::Reference(elements) => elements.len(),
+        }
+    }
+
+    fn check_index(self: &JVMArray, index: i32) -> Result<usize, JVMError> {
+        if index < 0 || index as usize >= self.length() {
+            let message = format!("Index {} out of bounds for length {}", index, self.length());
+            return Err(JVMError::exception("java/lang/ArrayIndexOutOfBoundsException", Some(message)));
+        }
+
+        return Ok(index as usize);
+    }
+
+    pub fn load(self: &JVMArray, index: i32) -> Result<RuntimeValue, JVMError> {
+        let index = self.check_index(index)?;
+        let value = match &self.elements {
+            ArrayElements::Boolean(elements) => RuntimeValue::Int(elements[index] as i32),
+            ArrayElements::Byte(elements) => RuntimeValue::Int(elements[index] as i32),
+            ArrayElements::Char(elements) => RuntimeValue::Int(elements[index] as i32),
+            ArrayElements::Short(elements) => RuntimeValue::Int(elements[index] as i32),
+            ArrayElements::Int(elements) => RuntimeValue::Int(elements[index]),
+            ArrayElements::Long(elements) => RuntimeValue::Long(elements[index]),
+            ArrayElements::Float(elements) => RuntimeValue::Float(elements[index]),
+            ArrayElements::Double(elements) => RuntimeValue::Double(elements[index]),
+            ArrayElements::Reference(elements) => elements[index].clone(),
+        };
+
+        return Ok(value);
+    }
+
+    /* store a value, truncating ints for the narrower primitive arrays the same way
+     * bastore, castore and sastore do
+     */
+    pub fn store(self: &mut JVMArray, index: i32, value: RuntimeValue) -> Result<(), JVMError> {
+        let index = self.check_index(index)?;
+        match (&mut self.elements, value) {
+            (ArrayElements::Boolean(elements), RuntimeValue::Int(value)) => elements[index] = (value & 1) as u8,
+            (ArrayElements::Byte(elements), RuntimeValue::Int(value)) => elements[index] = value as i8,
+            (ArrayElements::Char(elements), RuntimeValue::Int(value)) => elements[index] = value as u16,
+            (ArrayElements::Short(elements), RuntimeValue::Int(value)) => elements[index] = value as i16,
+            (ArrayElements::Int(elements), RuntimeValue::Int(value)) => elements[index] = value,
+            (ArrayElements::Long(elements), RuntimeValue::Long(value)) => elements[index] = value,
+            (ArrayElements::Float(elements), RuntimeValue::Float(value)) => elements[index] = value,
+            (ArrayElements::Double(elements), RuntimeValue::Double(value)) => elements[index] = value,
+            (ArrayElements::Reference(elements), value) => elements[index] = value,
+            (_, value) => {
+                return Err(format!("cannot store {:?} in array of class {}", value, self.class).into());
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+/* the array class for newarray's atype operand */
+pub fn primitive_array_class(atype: u8) -> Result<&'static str, JVMError> {
+    match atype {
+        T_BOOLEAN => Ok("[Z"),
+        T_CHAR => Ok("[C"),
+        T_FLOAT => Ok("[F"),
+        T_DOUBLE => Ok("[D"),
+        T_BYTE => Ok("[B"),
+        T_SHORT => Ok("[S"),
+        T_INT => Ok("[I"),
+        T_LONG => Ok("[J"),
+        _ => Err(format!("invalid newarray type {}", atype).into()),
+    }
+}
+
+/* the class of an array whose components are of the given class. arrays of arrays
+ * are named by the component's descriptor, so String gives [Ljava/lang/String; and
+ * [I gives [[I
+ */
+pub fn array_class_of(component: &str) -> String {
+    if component.starts_with('[') {
+        return format!("[{}", component);
+    }
+
+    return format!("[L{};", component);
+}
+
+/* the class name of the components of an array class, the inverse of array_class_of.
+ * primitive components are returned as their descriptor, such as I
+ */
+pub fn component_class_of(array_class: &str) -> Option<&str> {
+    let component = array_class.strip_prefix('[')?;
+    if let Some(name) = component.strip_prefix('L') {
+        return name.strip_suffix(';');
+    }
+
+    return Some(component);
+}
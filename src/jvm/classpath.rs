@@ -0,0 +1,174 @@
+/* where classes are loaded from: directories and jar files, searched in order the way the
+ * -classpath option describes
+ * https://docs.oracle.com/en/java/javase/17/docs/specs/man/java.html
+ * https://docs.oracle.com/en/java/javase/17/docs/specs/jar/jar.html
+ */
+
+use std::collections::HashMap;
+use std::io;
+use std::path;
+
+use crate::jvm::zip::ZipArchive;
+
+/* the java feature version this jvm implements. multi-release jars provide classes for it from
+ * META-INF/versions/N, with the highest N that is not newer winning
+ */
+pub const JAVA_FEATURE_VERSION: u32 = 17;
+
+// versioned entries are only looked at from java 9, when multi-release jars were introduced
+const FIRST_MULTI_RELEASE_VERSION: u32 = 9;
+
+const MANIFEST_NAME: &str = "META-INF/MANIFEST.MF";
+
+/* the attributes of the main section of a manifest. lines longer than 72 bytes are continued on
+ * the next line after a single space, and attribute names are not case sensitive
+ */
+fn parse_manifest(contents: &[u8]) -> HashMap<String, String> {
+    let text = String::from_utf8_lossy(contents);
+    let mut attributes: HashMap<String, String> = HashMap::new();
+    let mut last: Option<String> = None;
+    for line in text.lines() {
+        // a blank line ends the main section, the sections after it are about single entries
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some(continuation) = line.strip_prefix(' ') {
+            if let Some(value) = last.as_ref().and_then(|name| attributes.get_mut(name)) {
+                value.push_str(continuation);
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            let name = name.trim().to_ascii_lowercase();
+            attributes.insert(name.clone(), value.strip_prefix(' ').unwrap_or(value).to_string());
+            last = Some(name);
+        }
+    }
+
+    return attributes;
+}
+
+pub struct Jar {
+    path: path::PathBuf,
+    archive: ZipArchive,
+    manifest: HashMap<String, String>,
+}
+
+impl Jar {
+    pub fn open(path: &path::Path) -> Result<Jar, io::Error> {
+        let archive = ZipArchive::open(path)?;
+        let manifest = match archive.read(MANIFEST_NAME)? {
+            Some(contents) => parse_manifest(&contents),
+            None => HashMap::new(),
+        };
+
+        return Ok(Jar{
+            path: path.to_path_buf(),
+            archive: archive,
+            manifest: manifest,
+        });
+    }
+
+    fn attribute(self: &Jar, name: &str) -> Option<&str> {
+        return self.manifest.get(&name.to_ascii_lowercase()).map(|value| value.trim());
+    }
+
+    // the class `java -jar` runs, as a binary name such as com/acme/Main
+    pub fn main_class(self: &Jar) -> Option<String> {
+        return self.attribute("Main-Class").filter(|name| !name.is_empty()).map(|name| name.replace('.', "/"));
+    }
+
+    /* the jars and directories the manifest adds to the class path. they are relative urls,
+     * resolved against the directory of the jar
+     */
+    fn class_path(self: &Jar) -> Vec<path::PathBuf> {
+        let directory = self.path.parent().unwrap_or(path::Path::new(""));
+        return match self.attribute("Class-Path") {
+            Some(paths) => paths.split_whitespace().map(|url| directory.join(url.replace("%20", " "))).collect(),
+            None => Vec::new(),
+        };
+    }
+
+    fn multi_release(self: &Jar) -> bool {
+        return self.attribute("Multi-Release").map(|value| value.eq_ignore_ascii_case("true")).unwrap_or(false);
+    }
+
+    fn read_class(self: &Jar, file_name: &str) -> Result<Option<Vec<u8>>, io::Error> {
+        if self.multi_release() {
+            for version in (FIRST_MULTI_RELEASE_VERSION..=JAVA_FEATURE_VERSION).rev() {
+                let versioned = format!("META-INF/versions/{}/{}", version, file_name);
+                if self.archive.contains(&versioned) {
+                    return self.archive.read(&versioned);
+                }
+            }
+        }
+
+        return self.archive.read(file_name);
+    }
+}
+
+enum ClassPathEntry {
+    Directory(path::PathBuf),
+    Jar(Jar),
+}
+
+pub struct ClassPath {
+    entries: Vec<ClassPathEntry>,
+}
+
+impl ClassPath {
+    /* open the entries of a class path. a jar brings in the entries of its manifest's Class-Path
+     * right after itself. like java, entries that do not exist or cannot be read are left out
+     */
+    pub fn new(paths: &[path::PathBuf]) -> ClassPath {
+        let mut class_path = ClassPath{
+            entries: Vec::new(),
+        };
+        let mut seen = Vec::new();
+        for path in paths {
+            class_path.add(path, &mut seen);
+        }
+        return class_path;
+    }
+
+    fn add(self: &mut ClassPath, path: &path::Path, seen: &mut Vec<path::PathBuf>) {
+        // jars may name each other in their Class-Path, each is only opened once
+        if seen.iter().any(|other| other == path) {
+            return;
+        }
+        seen.push(path.to_path_buf());
+
+        if path.is_dir() {
+            self.entries.push(ClassPathEntry::Directory(path.to_path_buf()));
+        } else if let Ok(jar) = Jar::open(path) {
+            let dependencies = jar.class_path();
+            self.entries.push(ClassPathEntry::Jar(jar));
+            for dependency in dependencies.iter() {
+                self.add(dependency, seen);
+            }
+        }
+    }
+
+    /* the contents of the class file for a class with a binary name such as com/acme/Foo, from the
+     * first entry that has one
+     */
+    pub fn read_class(self: &ClassPath, class_name: &str) -> Result<Option<Vec<u8>>, io::Error> {
+        let file_name = format!("{}.class", class_name);
+        for entry in self.entries.iter() {
+            match entry {
+                ClassPathEntry::Directory(directory) => {
+                    let path = directory.join(&file_name);
+                    if path.is_file() {
+                        return std::fs::read(path).map(Some);
+                    }
+                },
+                ClassPathEntry::Jar(jar) => {
+                    if let Some(contents) = jar.read_class(&file_name)? {
+                        return Ok(Some(contents));
+                    }
+                }
+            }
+        }
+
+        return Ok(None);
+    }
+}
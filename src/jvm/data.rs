@@ -1,6 +1,7 @@
 use std::io::{Read};
 
 use crate::debug;
+use super::string::JVMString;
 
 pub struct ConstantPoolInfo {
     tag: u8,
@@ -8,16 +9,26 @@ pub struct ConstantPoolInfo {
 }
 
 pub struct FieldInfo {
-    access_flags: u16,
-    name_index: u16,
-    descriptor_index: u16,
-    attributes: Vec<AttributeKind>,
+    pub access_flags: u16,
+    pub name_index: u16,
+    pub descriptor_index: u16,
+    pub attributes: Vec<AttributeKind>,
 }
 
+pub const ACC_PUBLIC:u16 = 0x0001;
+pub const ACC_PRIVATE:u16 = 0x0002;
+pub const ACC_PROTECTED:u16 = 0x0004;
+pub const ACC_STATIC:u16 = 0x0008;
+pub const ACC_FINAL:u16 = 0x0010;
+pub const ACC_NATIVE:u16 = 0x0100;
+pub const ACC_INTERFACE:u16 = 0x0200;
+pub const ACC_ABSTRACT:u16 = 0x0400;
+pub const ACC_SYNTHETIC:u16 = 0x1000;
+
 pub struct MethodInfo {
-    access_flags: u16,
+    pub access_flags: u16,
     pub name_index: u16,
-    descriptor_index: u16,
+    pub descriptor_index: u16,
     pub attributes: Vec<AttributeKind>,
 }
 
@@ -71,10 +82,25 @@ struct AttributeInfo {
 pub enum ConstantPoolEntry {
     Classref(u16),
     Methodref(u16, u16),
+    InterfaceMethodref(u16, u16),
     NameAndType{name_index:u16, descriptor_index:u16},
     Utf8(String),
+    // a Utf8 entry a rust string cannot hold, which is a string literal with a lone surrogate in it
+    Utf16(Vec<u16>),
     Fieldref{class_index:u16, name_and_type_index:u16},
     Stringref(u16),
+    // a method handle constant, such as the bootstrap method of an invokedynamic
+    MethodHandle{reference_kind: u8, reference_index: u16},
+    MethodType{descriptor_index: u16},
+    InvokeDynamic{bootstrap_method_attr_index: u16, name_and_type_index: u16},
+    // a constant computed by a bootstrap method the first time it is loaded
+    Dynamic{bootstrap_method_attr_index: u16, name_and_type_index: u16},
+    Integer(i32),
+    Float(f32),
+    Long(i64),
+    Double(f64),
+    // the slot following a Long or Double entry
+    Unusable,
 }
 
 impl ConstantPoolEntry {
@@ -82,10 +108,20 @@ impl ConstantPoolEntry {
         return match self {
             ConstantPoolEntry::Classref(_) => "Classref",
             ConstantPoolEntry::Methodref(_, _) => "Methodref",
+            ConstantPoolEntry::InterfaceMethodref(_, _) => "InterfaceMethodref",
             ConstantPoolEntry::NameAndType{..} => "NameAndType",
-            ConstantPoolEntry::Utf8(_) => "Utf8",
+            ConstantPoolEntry::Utf8(_) | ConstantPoolEntry::Utf16(_) => "Utf8",
             ConstantPoolEntry::Fieldref{..} => "Fieldref",
             ConstantPoolEntry::Stringref(_) => "Stringref",
+            ConstantPoolEntry::MethodHandle{..} => "MethodHandle",
+            ConstantPoolEntry::MethodType{..} => "MethodType",
+            ConstantPoolEntry::InvokeDynamic{..} => "InvokeDynamic",
+            ConstantPoolEntry::Dynamic{..} => "Dynamic",
+            ConstantPoolEntry::Integer(_) => "Integer",
+            ConstantPoolEntry::Float(_) => "Float",
+            ConstantPoolEntry::Long(_) => "Long",
+            ConstantPoolEntry::Double(_) => "Double",
+            ConstantPoolEntry::Unusable => "Unusable",
         }
     }
 }
@@ -95,23 +131,39 @@ pub type ConstantPool = Vec<ConstantPoolEntry>;
 pub struct JVMClassFile {
     magic: u32,
     minor_version: u16,
-    major_version: u16,
+    pub major_version: u16,
     pub constant_pool: ConstantPool,
-    access_flags: u16,
+    pub access_flags: u16,
     pub this_class: u16,
-    super_class: u16,
-    interfaces: Vec<u16>,
-    fields: Vec<FieldInfo>,
+    pub super_class: u16,
+    pub interfaces: Vec<u16>,
+    pub fields: Vec<FieldInfo>,
     pub methods: Vec<MethodInfo>,
     pub attributes: Vec<AttributeKind>,
 }
 
+impl JVMClassFile {
+    // the binary name of the class, such as com/acme/Foo
+    pub fn class_name(&self) -> Option<&str> {
+        match constant_pool_lookup(&self.constant_pool, self.this_class as usize) {
+            Some(ConstantPoolEntry::Classref(name_index)) => lookup_utf8_constant(&self.constant_pool, *name_index as usize),
+            _ => None,
+        }
+    }
+}
+
 fn read_u32_bigendian(file: &mut dyn std::io::Read) -> u32 {
     let mut buf = [0; 4];
     file.read_exact(&mut buf).unwrap();
     u32::from_be_bytes(buf)
 }
 
+fn read_u64_bigendian(file: &mut dyn std::io::Read) -> u64 {
+    let mut buf = [0; 8];
+    file.read_exact(&mut buf).unwrap();
+    u64::from_be_bytes(buf)
+}
+
 fn read_u16_bigendian(file: &mut dyn std::io::Read) -> u16 {
     let mut buf = [0; 2];
     file.read_exact(&mut buf).unwrap();
@@ -124,23 +176,46 @@ fn read_u8(file: &mut dyn std::io::Read) -> u8 {
     u8::from_be_bytes(buf)
 }
 
+const CONSTANT_INTEGER:u8 = 3;
+const CONSTANT_FLOAT:u8 = 4;
+const CONSTANT_LONG:u8 = 5;
+const CONSTANT_DOUBLE:u8 = 6;
 const CONSTANT_CLASSREF:u8 = 7;
 const CONSTANT_METHODREF:u8 = 10;
+const CONSTANT_INTERFACEMETHODREF:u8 = 11;
 const CONSTANT_NAMEANDTYPE:u8 = 12;
 const CONSTANT_UTF8:u8 = 1;
 const CONSTANT_FIELDREF:u8 = 9;
 const CONSTANT_STRING:u8 = 8;
+const CONSTANT_METHODHANDLE:u8 = 15;
+const CONSTANT_METHODTYPE:u8 = 16;
+const CONSTANT_DYNAMIC:u8 = 17;
+const CONSTANT_INVOKEDYNAMIC:u8 = 18;
 
 pub struct ExceptionTableEntry {
-    start_pc: u16,
-    end_pc: u16,
-    handler_pc: u16,
-    catch_type: u16,
+    pub start_pc: u16,
+    pub end_pc: u16,
+    pub handler_pc: u16,
+    pub catch_type: u16,
+}
+
+// a bootstrap method of the BootstrapMethods attribute, a MethodHandle constant and its arguments
+pub struct BootstrapMethod {
+    pub bootstrap_method_ref: u16,
+    pub bootstrap_arguments: Vec<u16>,
 }
 
 pub struct LineNumberTableEntry {
-    start_pc: u16,
-    line_number: u16,
+    pub start_pc: u16,
+    pub line_number: u16,
+}
+
+pub struct LocalVariableTableEntry {
+    pub start_pc: u16,
+    pub length: u16,
+    pub name_index: u16,
+    pub descriptor_index: u16,
+    pub index: u16,
 }
 
 // FIXME: change this to an enum
@@ -159,11 +234,30 @@ pub enum AttributeKind {
     LineNumberTable{
         line_number_table: Vec<LineNumberTableEntry>,
     },
+    LocalVariableTable{
+        local_variable_table: Vec<LocalVariableTableEntry>,
+    },
     SourceFile{
+        sourcefile_index: u16,
+    },
+    // the value of a static final field whose initializer is a constant
+    ConstantValue{
+        constantvalue_index: u16,
+    },
+    Exceptions{
+        exception_index_table: Vec<u16>,
     },
     StackMapFrame{
         entries: Vec<StackMapFrameEntry>,
     },
+    // the bootstrap methods invokedynamic instructions of the class refer to
+    BootstrapMethods{
+        bootstrap_methods: Vec<BootstrapMethod>,
+    },
+    /* an attribute the jvm has no use for, such as InnerClasses or Signature. the jvm is required
+     * to ignore attributes it does not recognize
+     */
+    Ignored,
 }
 
 fn read_verification_type_info(file: &mut dyn std::io::Read) -> Result<(), std::io::Error> {
@@ -337,10 +431,42 @@ fn read_attribute(file: &mut dyn std::io::Read, constant_pool: &ConstantPool) ->
                 line_number_table: line_numbers,
             });
         },
+        Some("LocalVariableTable") => {
+            let mut local_variables:Vec<LocalVariableTableEntry> = Vec::new();
+            let length = read_u16_bigendian(&mut result);
+            for _i in 0..length {
+                local_variables.push(LocalVariableTableEntry{
+                    start_pc: read_u16_bigendian(&mut result),
+                    length: read_u16_bigendian(&mut result),
+                    name_index: read_u16_bigendian(&mut result),
+                    descriptor_index: read_u16_bigendian(&mut result),
+                    index: read_u16_bigendian(&mut result),
+                });
+            }
+            return Ok(AttributeKind::LocalVariableTable{
+                local_variable_table: local_variables,
+            });
+        },
+        Some("Exceptions") => {
+            let mut exceptions = Vec::new();
+            let length = read_u16_bigendian(&mut result);
+            for _i in 0..length {
+                exceptions.push(read_u16_bigendian(&mut result));
+            }
+            return Ok(AttributeKind::Exceptions{
+                exception_index_table: exceptions,
+            });
+        },
         Some("SourceFile") => {
-            // result.bytes().collect::<Vec<_>>();
-            result.bytes().for_each(drop);
+            let sourcefile_index = read_u16_bigendian(&mut result);
             return Ok(AttributeKind::SourceFile{
+                sourcefile_index: sourcefile_index,
+            });
+        },
+        Some("ConstantValue") => {
+            let constantvalue_index = read_u16_bigendian(&mut result);
+            return Ok(AttributeKind::ConstantValue{
+                constantvalue_index: constantvalue_index,
             });
         },
         Some("StackMapTable") => {
@@ -354,10 +480,29 @@ fn read_attribute(file: &mut dyn std::io::Read, constant_pool: &ConstantPool) ->
                 entries: entries,
             });
         },
+        Some("BootstrapMethods") => {
+            let mut bootstrap_methods = Vec::new();
+            let length = read_u16_bigendian(&mut result);
+            for _i in 0..length {
+                let bootstrap_method_ref = read_u16_bigendian(&mut result);
+                let argument_count = read_u16_bigendian(&mut result);
+                let mut bootstrap_arguments = Vec::new();
+                for _j in 0..argument_count {
+                    bootstrap_arguments.push(read_u16_bigendian(&mut result));
+                }
+                bootstrap_methods.push(BootstrapMethod{
+                    bootstrap_method_ref: bootstrap_method_ref,
+                    bootstrap_arguments: bootstrap_arguments,
+                });
+            }
+            return Ok(AttributeKind::BootstrapMethods{
+                bootstrap_methods: bootstrap_methods,
+            });
+        },
         Some(something) => {
-            // result.bytes().collect::<Vec<_>>();
+            debug!("Ignoring attribute {}", something);
             result.bytes().for_each(drop);
-            return Err(std::io::Error::new(std::io::ErrorKind::Other, format!("unknown attribute '{}'", something)));
+            return Ok(AttributeKind::Ignored);
         },
         _ => {
             return Err(std::io::Error::new(std::io::ErrorKind::Other, format!("unhandled name index {}", name_index)));
@@ -382,7 +527,7 @@ fn read_attributes(reader: &mut dyn std::io::Read, constant_pool: &ConstantPool,
     return Ok(result);
 }
 
-fn read_field(file: &mut std::fs::File, constant_pool: &ConstantPool) -> Result<FieldInfo, std::io::Error> {
+fn read_field(file: &mut dyn std::io::Read, constant_pool: &ConstantPool) -> Result<FieldInfo, std::io::Error> {
     let access_flags = read_u16_bigendian(file);
     let name_index = read_u16_bigendian(file);
     let descriptor_index = read_u16_bigendian(file);
@@ -411,218 +556,298 @@ pub fn parse_class_file(filename: &str) -> Result<JVMClassFile, std::io::Error>
     match std::fs::File::open(filename) {
         Ok(file) => {
             let mut file = file;
-            let mut jvm_class_file = JVMClassFile {
-                magic: 0,
-                minor_version: 0,
-                major_version: 0,
-                constant_pool: Vec::new(),
-                access_flags: 0,
-                this_class: 0,
-                super_class: 0,
-                interfaces: Vec::new(),
-                fields: Vec::new(),
-                methods: Vec::new(),
-                attributes: Vec::new(),
-            };
-            jvm_class_file.magic = read_u32_bigendian(&mut file);
-
-            if jvm_class_file.magic != 0xcafebabe {
-                return Err(std::io::Error::new(std::io::ErrorKind::Other, "Not a class file"));
-            }
+            return read_class_file(&mut file);
+        },
+        Err(error) => {
+            return Err(error);
+        }
+    }
+}
 
-            jvm_class_file.minor_version = read_u16_bigendian(&mut file);
-            jvm_class_file.major_version = read_u16_bigendian(&mut file);
-            let constant_pool_count = read_u16_bigendian(&mut file);
+// parse a class file from wherever its bytes come from, such as a file or an entry in a jar
+pub fn read_class_file(file: &mut dyn std::io::Read) -> Result<JVMClassFile, std::io::Error> {
+    let mut jvm_class_file = JVMClassFile {
+        magic: 0,
+        minor_version: 0,
+        major_version: 0,
+        constant_pool: Vec::new(),
+        access_flags: 0,
+        this_class: 0,
+        super_class: 0,
+        interfaces: Vec::new(),
+        fields: Vec::new(),
+        methods: Vec::new(),
+        attributes: Vec::new(),
+    };
+    jvm_class_file.magic = read_u32_bigendian(file);
+
+    if jvm_class_file.magic != 0xcafebabe {
+        return Err(std::io::Error::new(std::io::ErrorKind::Other, "Not a class file"));
+    }
 
-            debug!("Reading constants {0}", constant_pool_count);
-            for _i in 0..constant_pool_count - 1 {
+    jvm_class_file.minor_version = read_u16_bigendian(file);
+    jvm_class_file.major_version = read_u16_bigendian(file);
+    let constant_pool_count = read_u16_bigendian(file);
+
+    debug!("Reading constants {0}", constant_pool_count);
+    while jvm_class_file.constant_pool.len() < (constant_pool_count - 1) as usize {
+        /*
+        let mut constant_pool_info = ConstantPoolInfo {
+            tag: 0,
+            info: Vec::new(),
+        };
+        */
+        let tag = read_u8(file);
+        // println!("Read constant tag {0}", tag);
+        match tag {
+            CONSTANT_CLASSREF => {
+                // name index
+                let name = read_u16_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::Classref(name));
+            },
+            CONSTANT_METHODREF => {
+                // class index
+                let class = read_u16_bigendian(file);
+                let name_and_type = read_u16_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::Methodref(class, name_and_type));
+            },
+            CONSTANT_METHODHANDLE => {
+                let reference_kind = read_u8(file);
+                let reference_index = read_u16_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::MethodHandle{reference_kind: reference_kind, reference_index: reference_index});
+            },
+            CONSTANT_METHODTYPE => {
+                let descriptor_index = read_u16_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::MethodType{descriptor_index: descriptor_index});
+            },
+            CONSTANT_DYNAMIC => {
+                let bootstrap_method_attr_index = read_u16_bigendian(file);
+                let name_and_type_index = read_u16_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::Dynamic{bootstrap_method_attr_index: bootstrap_method_attr_index, name_and_type_index: name_and_type_index});
+            },
+            CONSTANT_INVOKEDYNAMIC => {
+                let bootstrap_method_attr_index = read_u16_bigendian(file);
+                let name_and_type_index = read_u16_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::InvokeDynamic{bootstrap_method_attr_index: bootstrap_method_attr_index, name_and_type_index: name_and_type_index});
+            },
+            CONSTANT_INTERFACEMETHODREF => {
+                let class = read_u16_bigendian(file);
+                let name_and_type = read_u16_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::InterfaceMethodref(class, name_and_type));
+            },
+
+            CONSTANT_UTF8 => {
+                // length
+                // constant_pool_info.info.push(read_u8(file));
+                // constant_pool_info.info.push(read_u8(file));
+                let length = read_u16_bigendian(file);
+                // bytes
+                // let length = (constant_pool_info.info[0] as u16) << 8 | constant_pool_info.info[1] as u16;
                 /*
-                let mut constant_pool_info = ConstantPoolInfo {
-                    tag: 0,
-                    info: Vec::new(),
-                };
+                for _i in 0..length {
+                    constant_pool_info.info.push(read_u8(file));
+                }
                 */
-                let tag = read_u8(&mut file);
-                // println!("Read constant tag {0}", tag);
-                match tag {
-                    CONSTANT_CLASSREF => {
-                        // name index
-                        let name = read_u16_bigendian(&mut file);
-                        jvm_class_file.constant_pool.push(ConstantPoolEntry::Classref(name));
-                    },
-                    CONSTANT_METHODREF => {
-                        // class index
-                        let class = read_u16_bigendian(&mut file);
-                        let name_and_type = read_u16_bigendian(&mut file);
-                        jvm_class_file.constant_pool.push(ConstantPoolEntry::Methodref(class, name_and_type));
-                    },
 
-                    CONSTANT_UTF8 => {
-                        // length
-                        // constant_pool_info.info.push(read_u8(&mut file));
-                        // constant_pool_info.info.push(read_u8(&mut file));
-                        let length = read_u16_bigendian(&mut file);
-                        // bytes
-                        // let length = (constant_pool_info.info[0] as u16) << 8 | constant_pool_info.info[1] as u16;
-                        /*
-                        for _i in 0..length {
-                            constant_pool_info.info.push(read_u8(&mut file));
-                        }
-                        */
-
-                        // read length bytes from file
-                        let result = String::from_utf8((&file).bytes().take(length as usize).map(|r| r.unwrap()).collect::<Vec<_>>());
-                        match result {
-                            Ok(s) => {
-                                // println!("Read utf8 string '{}'", s);
-                                jvm_class_file.constant_pool.push(ConstantPoolEntry::Utf8(s));
-                            }
-                            Err(err) => return Err(std::io::Error::new(std::io::ErrorKind::Other, err))
+                // read length bytes from file
+                let result = decode_modified_utf8(&(&mut *file).bytes().take(length as usize).map(|r| r.unwrap()).collect::<Vec<_>>());
+                match result {
+                    Ok(chars) => {
+                        match String::from_utf16(&chars) {
+                            Ok(s) => jvm_class_file.constant_pool.push(ConstantPoolEntry::Utf8(s)),
+                            Err(_) => jvm_class_file.constant_pool.push(ConstantPoolEntry::Utf16(chars)),
                         }
+                    }
+                    Err(err) => return Err(std::io::Error::new(std::io::ErrorKind::Other, err))
+                }
 
-                        /*
-                        match
-                            Ok(s) => {
-                            }
-                            Err(std::string::FromUtf8Error) => {
-                                println!("ERROR: could not read utf8 string: {}", err);
-                                return Err(err)
-                            }
-                        }
-                        */
-
-                        /*
-                        let bytes = file.bytes().take(length as usize);
-                        let reader: Box<dyn Read> = Box::new(bytes);
-                        let bufreader = BufReader::new(reader);
-                        let mut buffer = String::new();
-                        bufreader.read_to_string(&mut buffer);
-                        */
-
-                        /*
-                        match bytes.collect::<Vec<_>>() {
-                            Ok(data) => {
-                                jvm_class_file.constant_pool.push(ConstantPool::Utf8(String::from_utf8(data).unwrap()));
-                            },
-                            Error => {
-                                println!("ERROR: reading utf8 string");
-                            }
-                        }
-                        */
-                    },
-                    CONSTANT_STRING => {
-                        // string index
-                        let index = read_u16_bigendian(&mut file);
-                        jvm_class_file.constant_pool.push(ConstantPoolEntry::Stringref(index));
-                    },
-                    CONSTANT_FIELDREF => {
-                        // class index
-                        let class = read_u16_bigendian(&mut file);
-                        // name and type index
-                        let name_and_type = read_u16_bigendian(&mut file);
-                        jvm_class_file.constant_pool.push(ConstantPoolEntry::Fieldref{class_index:class, name_and_type_index:name_and_type});
-                    },
-                    CONSTANT_NAMEANDTYPE => {
-                        // name index
-                        let name = read_u16_bigendian(&mut file);
-                        // descriptor index
-                        let descriptor = read_u16_bigendian(&mut file);
-                        jvm_class_file.constant_pool.push(ConstantPoolEntry::NameAndType{name_index:name, descriptor_index:descriptor});
+                /*
+                match
+                    Ok(s) => {
+                    }
+                    Err(std::string::FromUtf8Error) => {
+                        println!("ERROR: could not read utf8 string: {}", err);
+                        return Err(err)
+                    }
+                }
+                */
+
+                /*
+                let bytes = file.bytes().take(length as usize);
+                let reader: Box<dyn Read> = Box::new(bytes);
+                let bufreader = BufReader::new(reader);
+                let mut buffer = String::new();
+                bufreader.read_to_string(&mut buffer);
+                */
+
+                /*
+                match bytes.collect::<Vec<_>>() {
+                    Ok(data) => {
+                        jvm_class_file.constant_pool.push(ConstantPool::Utf8(String::from_utf8(data).unwrap()));
                     },
-                    _ => {
-                        return Err(std::io::Error::new(std::io::ErrorKind::Other, "Unhandled constant tag"));
+                    Error => {
+                        println!("ERROR: reading utf8 string");
                     }
                 }
-                // constant_pool_info.info = read_u8(&mut file);
-                // jvm_class_file.constant_pool.push(constant_pool_info);
+                */
+            },
+            CONSTANT_STRING => {
+                // string index
+                let index = read_u16_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::Stringref(index));
+            },
+            CONSTANT_FIELDREF => {
+                // class index
+                let class = read_u16_bigendian(file);
+                // name and type index
+                let name_and_type = read_u16_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::Fieldref{class_index:class, name_and_type_index:name_and_type});
+            },
+            CONSTANT_INTEGER => {
+                let value = read_u32_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::Integer(value as i32));
+            },
+            CONSTANT_FLOAT => {
+                let value = read_u32_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::Float(f32::from_bits(value)));
+            },
+            // 8 byte constants take up two entries in the constant pool
+            CONSTANT_LONG => {
+                let value = read_u64_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::Long(value as i64));
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::Unusable);
+            },
+            CONSTANT_DOUBLE => {
+                let value = read_u64_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::Double(f64::from_bits(value)));
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::Unusable);
+            },
+            CONSTANT_NAMEANDTYPE => {
+                // name index
+                let name = read_u16_bigendian(file);
+                // descriptor index
+                let descriptor = read_u16_bigendian(file);
+                jvm_class_file.constant_pool.push(ConstantPoolEntry::NameAndType{name_index:name, descriptor_index:descriptor});
+            },
+            _ => {
+                return Err(std::io::Error::new(std::io::ErrorKind::Other, "Unhandled constant tag"));
             }
+        }
+        // constant_pool_info.info = read_u8(file);
+        // jvm_class_file.constant_pool.push(constant_pool_info);
+    }
 
-            jvm_class_file.access_flags = read_u16_bigendian(&mut file);
-            jvm_class_file.this_class = read_u16_bigendian(&mut file);
-            jvm_class_file.super_class = read_u16_bigendian(&mut file);
-            let interfaces_count = read_u16_bigendian(&mut file);
+    jvm_class_file.access_flags = read_u16_bigendian(file);
+    jvm_class_file.this_class = read_u16_bigendian(file);
+    jvm_class_file.super_class = read_u16_bigendian(file);
+    let interfaces_count = read_u16_bigendian(file);
 
-            // read interfaces_count number of u16 and put them the interfaces vec
-            for _i in 0..interfaces_count {
-                jvm_class_file.interfaces.push(read_u16_bigendian(&mut file));
-            }
+    // read interfaces_count number of u16 and put them the interfaces vec
+    for _i in 0..interfaces_count {
+        jvm_class_file.interfaces.push(read_u16_bigendian(file));
+    }
 
-            let fields_count = read_u16_bigendian(&mut file);
+    let fields_count = read_u16_bigendian(file);
 
-            for _i in 0..fields_count {
-                jvm_class_file.fields.push(read_field(&mut file, &jvm_class_file.constant_pool)?);
-            }
+    for _i in 0..fields_count {
+        jvm_class_file.fields.push(read_field(file, &jvm_class_file.constant_pool)?);
+    }
 
-            let methods_count = read_u16_bigendian(&mut file);
+    let methods_count = read_u16_bigendian(file);
 
-            for _i in 0..methods_count {
-                let access_flags = read_u16_bigendian(&mut file);
-                let name_index = read_u16_bigendian(&mut file);
-                let descriptor_index = read_u16_bigendian(&mut file);
-                let attributes_count = read_u16_bigendian(&mut file);
+    for _i in 0..methods_count {
+        let access_flags = read_u16_bigendian(file);
+        let name_index = read_u16_bigendian(file);
+        let descriptor_index = read_u16_bigendian(file);
+        let attributes_count = read_u16_bigendian(file);
 
-                debug!("Method access flags 0x{:x}", access_flags);
+        debug!("Method access flags 0x{:x}", access_flags);
 
-                let attributes = read_attributes(&mut file, &jvm_class_file.constant_pool, attributes_count)?;
+        let attributes = read_attributes(file, &jvm_class_file.constant_pool, attributes_count)?;
 
-                let method = MethodInfo {
-                    access_flags: access_flags,
-                    name_index: name_index,
-                    descriptor_index: descriptor_index,
-                    attributes: attributes,
-                };
+        let method = MethodInfo {
+            access_flags: access_flags,
+            name_index: name_index,
+            descriptor_index: descriptor_index,
+            attributes: attributes,
+        };
 
-                jvm_class_file.methods.push(method);
-            }
+        jvm_class_file.methods.push(method);
+    }
 
-            let attributes_count = read_u16_bigendian(&mut file);
+    let attributes_count = read_u16_bigendian(file);
 
-            /*
-            for _i in 0..jvm_class_file.attributes_count {
-                println!("Reading class attribute");
-                jvm_class_file.attributes.push(read_attribute(&mut file, &jvm_class_file.constant_pool)?);
+    /*
+    for _i in 0..jvm_class_file.attributes_count {
+        println!("Reading class attribute");
+        jvm_class_file.attributes.push(read_attribute(file, &jvm_class_file.constant_pool)?);
+    }
+    */
+
+    debug!("Reading class attributes");
+    jvm_class_file.attributes = read_attributes(file, &jvm_class_file.constant_pool, attributes_count)?;
+
+    debug!("Magic: 0x{0:x}", jvm_class_file.magic);
+    debug!("Version: {0}.{1}", jvm_class_file.major_version, jvm_class_file.minor_version);
+    debug!("Constant pool: {0}", constant_pool_count);
+    debug!("Access flags: 0x{0:X}", jvm_class_file.access_flags);
+    debug!("Interfaces: {0}", interfaces_count);
+    debug!("Fields: {0}", fields_count);
+    debug!("Methods: {0}", methods_count);
+
+    for i in 0..fields_count {
+        debug!("Field {}", i);
+        let name = lookup_utf8_constant(&jvm_class_file.constant_pool, jvm_class_file.fields[i as usize].name_index as usize);
+        match name {
+            Some(name) => {
+                debug!("  name={}", name);
+            },
+            None => {
+                debug!("  name=unknown");
             }
-            */
-
-            debug!("Reading class attributes");
-            jvm_class_file.attributes = read_attributes(&mut file, &jvm_class_file.constant_pool, attributes_count)?;
-
-            debug!("Magic: 0x{0:x}", jvm_class_file.magic);
-            debug!("Version: {0}.{1}", jvm_class_file.major_version, jvm_class_file.minor_version);
-            debug!("Constant pool: {0}", constant_pool_count);
-            debug!("Access flags: 0x{0:X}", jvm_class_file.access_flags);
-            debug!("Interfaces: {0}", interfaces_count);
-            debug!("Fields: {0}", fields_count);
-            debug!("Methods: {0}", methods_count);
-
-            for i in 0..fields_count {
-                debug!("Field {}", i);
-                let name = lookup_utf8_constant(&jvm_class_file.constant_pool, jvm_class_file.fields[i as usize].name_index as usize);
-                match name {
-                    Some(name) => {
-                        debug!("  name={}", name);
-                    },
-                    None => {
-                        debug!("  name=unknown");
-                    }
-                }
-                let descriptor = lookup_utf8_constant(&jvm_class_file.constant_pool, jvm_class_file.fields[i as usize].descriptor_index as usize);
-                match descriptor {
-                    Some(descriptor) => {
-                        debug!("  descriptor={}", descriptor);
-                    },
-                    None => {
-                        debug!("  descriptor=unknown");
-                    }
-                }
+        }
+        let descriptor = lookup_utf8_constant(&jvm_class_file.constant_pool, jvm_class_file.fields[i as usize].descriptor_index as usize);
+        match descriptor {
+            Some(descriptor) => {
+                debug!("  descriptor={}", descriptor);
+            },
+            None => {
+                debug!("  descriptor=unknown");
             }
+        }
+    }
 
-            return Ok(jvm_class_file)
-        },
-        Err(error) => {
-            return Err(error);
+    return Ok(jvm_class_file)
+}
+
+/* class files encode strings in modified UTF-8, which writes the null char as two bytes and a
+ * supplementary character as the two three byte encodings of its surrogates. the chars are
+ * decoded as they are, lone surrogates included
+ * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-4.html#jvms-4.4.7
+ */
+fn decode_modified_utf8(bytes: &[u8]) -> Result<Vec<u16>, std::io::Error> {
+    let invalid = || std::io::Error::new(std::io::ErrorKind::Other, "invalid modified utf-8 string");
+    let mut chars: Vec<u16> = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let x = bytes[i] as u16;
+        if x & 0x80 == 0 {
+            chars.push(x);
+            i += 1;
+        } else if x & 0xe0 == 0xc0 {
+            let y = *bytes.get(i + 1).ok_or_else(invalid)? as u16;
+            chars.push(((x & 0x1f) << 6) | (y & 0x3f));
+            i += 2;
+        } else if x & 0xf0 == 0xe0 {
+            let y = *bytes.get(i + 1).ok_or_else(invalid)? as u16;
+            let z = *bytes.get(i + 2).ok_or_else(invalid)? as u16;
+            chars.push(((x & 0xf) << 12) | ((y & 0x3f) << 6) | (z & 0x3f));
+            i += 3;
+        } else {
+            return Err(invalid());
         }
     }
+    return Ok(chars);
 }
 
 pub fn lookup_utf8_constant(constant_pool: &ConstantPool, constant_index: usize) -> Option<&str> {
@@ -636,6 +861,15 @@ pub fn lookup_utf8_constant(constant_pool: &ConstantPool, constant_index: usize)
     }
 }
 
+// the contents of the string a Stringref entry refers to
+pub fn lookup_string_constant(constant_pool: &ConstantPool, constant_index: usize) -> Option<JVMString> {
+    match constant_pool_lookup(constant_pool, constant_index) {
+        Some(ConstantPoolEntry::Utf8(string)) => Some(JVMString::from(string.as_str())),
+        Some(ConstantPoolEntry::Utf16(chars)) => Some(JVMString::new(chars.clone())),
+        _ => None,
+    }
+}
+
 pub fn constant_pool_lookup(constant_pool: &ConstantPool, constant_index: usize) -> Option<&ConstantPoolEntry> {
     if constant_index >= 1 && constant_index <= constant_pool.len() {
         return Some(&constant_pool[constant_index-1]);
@@ -730,6 +964,15 @@ fn parse_field_descriptor(descriptor: &mut std::iter::Peekable<std::str::Chars>)
     return Err(format!("cannot parse field descriptor: {}", make_string_from(descriptor)));
 }
 
+pub fn parse_type_descriptor(descriptor: &str) -> Result<Descriptor, String> {
+    let mut chars = descriptor.chars().peekable();
+    let result = parse_field_descriptor(&mut chars)?;
+    if chars.peek().is_some() {
+        return Err(format!("trailing characters in field descriptor: {}", descriptor));
+    }
+    return Ok(result);
+}
+
 pub fn parse_method_descriptor(descriptor: &str) -> Result<MethodDescriptor, String> {
     // println!("parse method descriptor: {}", descriptor);
 
@@ -5,58 +5,233 @@ use std::fmt;
 
 use crate::debug;
 use super::data::*;
+use super::array::*;
+use super::string::JVMString;
+use super::npe;
+use super::classpath::ClassPath;
 
 // https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-6.html#jvms-6.5
 pub mod opcodes {
+    pub const ACONSTNULL:u8 = 0x1; // aconst_null
+    pub const ICONSTM1:u8 = 0x2; // iconst_m1
     pub const ICONST0:u8 = 0x3; // iconst_0
     pub const ICONST1:u8 = 0x4; // iconst_1
     pub const ICONST2:u8 = 0x5; // iconst_2
     pub const ICONST3:u8 = 0x6; // iconst_3
     pub const ICONST4:u8 = 0x7; // iconst_4
     pub const ICONST5:u8 = 0x8; // iconst_5
+    pub const LCONST0:u8 = 0x9; // lconst_0
+    pub const LCONST1:u8 = 0xa; // lconst_1
+    pub const FCONST0:u8 = 0xb; // fconst_0
+    pub const FCONST1:u8 = 0xc; // fconst_1
+    pub const FCONST2:u8 = 0xd; // fconst_2
+    pub const DCONST0:u8 = 0xe; // dconst_0
+    pub const DCONST1:u8 = 0xf; // dconst_1
     pub const PUSHBYTE:u8 = 0x10; // bipush
+    pub const PUSHSHORT:u8 = 0x11; // sipush
     pub const PUSHRUNTIMECONSTANT:u8 = 0x12; // ldc
+    pub const PUSHRUNTIMECONSTANTWIDE:u8 = 0x13; // ldc_w
+    pub const PUSHRUNTIMECONSTANT2WIDE:u8 = 0x14; // ldc2_w
     pub const ILOAD:u8 = 0x15; // iload
+    pub const LLOAD:u8 = 0x16; // lload
+    pub const FLOAD:u8 = 0x17; // fload
+    pub const DLOAD:u8 = 0x18; // dload
+    pub const ALOAD:u8 = 0x19; // aload
     pub const ILOAD0:u8 = 0x1a; // iload_0
     pub const ILOAD1:u8 = 0x1b; // iload_1
     pub const ILOAD2:u8 = 0x1c; // iload_2
     pub const ILOAD3:u8 = 0x1d; // iload_3
+    pub const LLOAD0:u8 = 0x1e; // lload_0
+    pub const LLOAD1:u8 = 0x1f; // lload_1
+    pub const LLOAD2:u8 = 0x20; // lload_2
+    pub const LLOAD3:u8 = 0x21; // lload_3
+    pub const FLOAD0:u8 = 0x22; // fload_0
+    pub const FLOAD1:u8 = 0x23; // fload_1
+    pub const FLOAD2:u8 = 0x24; // fload_2
+    pub const FLOAD3:u8 = 0x25; // fload_3
+    pub const DLOAD0:u8 = 0x26; // dload_0
+    pub const DLOAD1:u8 = 0x27; // dload_1
+    pub const DLOAD2:u8 = 0x28; // dload_2
+    pub const DLOAD3:u8 = 0x29; // dload_3
     pub const ALOAD0:u8 = 0x2a; // aload_0
     pub const ALOAD1:u8 = 0x2b; // aload_1
+    pub const ALOAD2:u8 = 0x2c; // aload_2
+    pub const ALOAD3:u8 = 0x2d; // aload_3
+    pub const IALOAD:u8 = 0x2e; // iaload
+    pub const LALOAD:u8 = 0x2f; // laload
+    pub const FALOAD:u8 = 0x30; // faload
+    pub const DALOAD:u8 = 0x31; // daload
+    pub const AALOAD:u8 = 0x32; // aaload
+    pub const BALOAD:u8 = 0x33; // baload
+    pub const CALOAD:u8 = 0x34; // caload
+    pub const SALOAD:u8 = 0x35; // saload
     pub const ISTORE:u8 = 0x36; // istore
+    pub const LSTORE:u8 = 0x37; // lstore
+    pub const FSTORE:u8 = 0x38; // fstore
+    pub const DSTORE:u8 = 0x39; // dstore
+    pub const ASTORE:u8 = 0x3a; // astore
     pub const ISTORE0:u8 = 0x3b; // istore_0
     pub const ISTORE1:u8 = 0x3c; // istore_1
     pub const ISTORE2:u8 = 0x3d; // istore_2
     pub const ISTORE3:u8 = 0x3e; // istore_3
+    pub const LSTORE0:u8 = 0x3f; // lstore_0
+    pub const LSTORE1:u8 = 0x40; // lstore_1
+    pub const LSTORE2:u8 = 0x41; // lstore_2
+    pub const LSTORE3:u8 = 0x42; // lstore_3
+    pub const FSTORE0:u8 = 0x43; // fstore_0
+    pub const FSTORE1:u8 = 0x44; // fstore_1
+    pub const FSTORE2:u8 = 0x45; // fstore_2
+    pub const FSTORE3:u8 = 0x46; // fstore_3
+    pub const DSTORE0:u8 = 0x47; // dstore_0
+    pub const DSTORE1:u8 = 0x48; // dstore_1
+    pub const DSTORE2:u8 = 0x49; // dstore_2
+    pub const DSTORE3:u8 = 0x4a; // dstore_3
+    pub const ASTORE0:u8 = 0x4b; // astore_0
     pub const ASTORE1:u8 = 0x4c; // astore_1
+    pub const ASTORE2:u8 = 0x4d; // astore_2
+    pub const ASTORE3:u8 = 0x4e; // astore_3
+    pub const IASTORE:u8 = 0x4f; // iastore
+    pub const LASTORE:u8 = 0x50; // lastore
+    pub const FASTORE:u8 = 0x51; // fastore
+    pub const DASTORE:u8 = 0x52; // dastore
+    pub const AASTORE:u8 = 0x53; // aastore
+    pub const BASTORE:u8 = 0x54; // bastore
+    pub const CASTORE:u8 = 0x55; // castore
+    pub const SASTORE:u8 = 0x56; // sastore
+    pub const POP:u8 = 0x57; // pop
+    pub const POP2:u8 = 0x58; // pop2
     pub const DUP:u8 = 0x59; // dup
+    pub const DUPX1:u8 = 0x5a; // dup_x1
+    pub const DUPX2:u8 = 0x5b; // dup_x2
+    pub const DUP2:u8 = 0x5c; // dup2
+    pub const DUP2X1:u8 = 0x5d; // dup2_x1
+    pub const DUP2X2:u8 = 0x5e; // dup2_x2
+    pub const SWAP:u8 = 0x5f; // swap
     pub const IADD:u8 = 0x60; // iadd
+    pub const LADD:u8 = 0x61; // ladd
+    pub const FADD:u8 = 0x62; // fadd
+    pub const DADD:u8 = 0x63; // dadd
+    pub const ISUB:u8 = 0x64; // isub
+    pub const LSUB:u8 = 0x65; // lsub
+    pub const FSUB:u8 = 0x66; // fsub
+    pub const DSUB:u8 = 0x67; // dsub
     pub const IMUL:u8 = 0x68; // imul
+    pub const LMUL:u8 = 0x69; // lmul
+    pub const FMUL:u8 = 0x6a; // fmul
+    pub const DMUL:u8 = 0x6b; // dmul
     pub const IDIV:u8 = 0x6c; // idiv
+    pub const LDIV:u8 = 0x6d; // ldiv
+    pub const FDIV:u8 = 0x6e; // fdiv
+    pub const DDIV:u8 = 0x6f; // ddiv
+    pub const IREM:u8 = 0x70; // irem
+    pub const LREM:u8 = 0x71; // lrem
+    pub const FREM:u8 = 0x72; // frem
+    pub const DREM:u8 = 0x73; // drem
+    pub const INEG:u8 = 0x74; // ineg
+    pub const LNEG:u8 = 0x75; // lneg
+    pub const FNEG:u8 = 0x76; // fneg
+    pub const DNEG:u8 = 0x77; // dneg
+    pub const ISHL:u8 = 0x78; // ishl
+    pub const LSHL:u8 = 0x79; // lshl
+    pub const ISHR:u8 = 0x7a; // ishr
+    pub const LSHR:u8 = 0x7b; // lshr
+    pub const IUSHR:u8 = 0x7c; // iushr
+    pub const LUSHR:u8 = 0x7d; // lushr
+    pub const IAND:u8 = 0x7e; // iand
+    pub const LAND:u8 = 0x7f; // land
+    pub const IOR:u8 = 0x80; // ior
+    pub const LOR:u8 = 0x81; // lor
+    pub const IXOR:u8 = 0x82; // ixor
+    pub const LXOR:u8 = 0x83; // lxor
     pub const IINC:u8 = 0x84; // iinc
-    pub const TABLESWITCH:u8 = 0xaa; // tableswitch
+    pub const I2L:u8 = 0x85; // i2l
+    pub const I2F:u8 = 0x86; // i2f
+    pub const I2D:u8 = 0x87; // i2d
+    pub const L2I:u8 = 0x88; // l2i
+    pub const L2F:u8 = 0x89; // l2f
+    pub const L2D:u8 = 0x8a; // l2d
+    pub const F2I:u8 = 0x8b; // f2i
+    pub const F2L:u8 = 0x8c; // f2l
+    pub const F2D:u8 = 0x8d; // f2d
+    pub const D2I:u8 = 0x8e; // d2i
+    pub const D2L:u8 = 0x8f; // d2l
+    pub const D2F:u8 = 0x90; // d2f
+    pub const I2B:u8 = 0x91; // i2b
+    pub const I2C:u8 = 0x92; // i2c
+    pub const I2S:u8 = 0x93; // i2s
+    pub const LCMP:u8 = 0x94; // lcmp
+    pub const FCMPL:u8 = 0x95; // fcmpl
+    pub const FCMPG:u8 = 0x96; // fcmpg
+    pub const DCMPL:u8 = 0x97; // dcmpl
+    pub const DCMPG:u8 = 0x98; // dcmpg
+    pub const IFEQUAL:u8 = 0x99; // ifeq
+    pub const IFNOTEQUAL:u8 = 0x9a; // ifne
+    pub const IFLESS:u8 = 0x9b; // iflt
+    pub const IFGREATEREQUAL:u8 = 0x9c; // ifge
+    pub const IFGREATER:u8 = 0x9d; // ifgt
+    pub const IFLESSEQUAL:u8 = 0x9e; // ifle
+    pub const IFICOMPAREEQUAL:u8 = 0x9f; // if_icmpeq
+    pub const IFICOMPARENOTEQUAL:u8 = 0xa0; // if_icmpne
+    pub const IFICOMPARELESS:u8 = 0xa1; // if_icmplt
     pub const IFICOMPAREGREATEREQUAL:u8 = 0xa2; // if_icmpge
+    pub const IFICOMPAREGREATER:u8 = 0xa3; // if_icmpgt
+    pub const IFICOMPARELESSEQUAL:u8 = 0xa4; // if_icmple
+    pub const IFACOMPAREEQUAL:u8 = 0xa5; // if_acmpeq
+    pub const IFACOMPARENOTEQUAL:u8 = 0xa6; // if_acmpne
     pub const GOTO:u8 = 0xa7; // goto
+    pub const JSR:u8 = 0xa8; // jsr
+    pub const RET:u8 = 0xa9; // ret
+    pub const TABLESWITCH:u8 = 0xaa; // tableswitch
+    pub const LOOKUPSWITCH:u8 = 0xab; // lookupswitch
     pub const IRETURN:u8 = 0xac; // ireturn
+    pub const LRETURN:u8 = 0xad; // lreturn
+    pub const FRETURN:u8 = 0xae; // freturn
+    pub const DRETURN:u8 = 0xaf; // dreturn
+    pub const ARETURN:u8 = 0xb0; // areturn
     pub const RETURN:u8 = 0xb1; // return
     pub const GETSTATIC:u8 = 0xb2; // getstatic
+    pub const PUTSTATIC:u8 = 0xb3; // putstatic
     pub const GETFIELD:u8 = 0xb4; // getfield
     pub const PUTFIELD:u8 = 0xb5; // putfield
     pub const INVOKEVIRTUAL:u8 = 0xb6; // invokevirtual
     pub const INVOKESPECIAL:u8 = 0xb7; // invokespecial
     pub const INVOKESTATIC:u8 = 0xb8; // invokestatic
+    pub const INVOKEINTERFACE:u8 = 0xb9; // invokeinterface
+    pub const INVOKEDYNAMIC:u8 = 0xba; // invokedynamic
     pub const NEW:u8 = 0xbb; // new
+    pub const NEWARRAY:u8 = 0xbc; // newarray
+    pub const ANEWARRAY:u8 = 0xbd; // anewarray
+    pub const ARRAYLENGTH:u8 = 0xbe; // arraylength
+    pub const ATHROW:u8 = 0xbf; // athrow
+    pub const CHECKCAST:u8 = 0xc0; // checkcast
+    pub const INSTANCEOF:u8 = 0xc1; // instanceof
+    pub const MONITORENTER:u8 = 0xc2; // monitorenter
+    pub const MONITOREXIT:u8 = 0xc3; // monitorexit
+    pub const WIDE:u8 = 0xc4; // wide
+    pub const MULTIANEWARRAY:u8 = 0xc5; // multianewarray
+    pub const IFNULL:u8 = 0xc6; // ifnull
+    pub const IFNONNULL:u8 = 0xc7; // ifnonnull
+    pub const GOTOW:u8 = 0xc8; // goto_w
+    pub const JSRW:u8 = 0xc9; // jsr_w
 }
 
+// jsr, jsr_w and ret may not appear in class files of version 51.0 or above
+// https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-4.html#jvms-4.9.1
+const MAX_SUBROUTINE_CLASS_VERSION:u16 = 50;
+
 #[derive(Clone)]
 pub enum RuntimeValue{
-    Int(i64),
+    Int(i32),
     Long(i64),
     Float(f32),
     Double(f64),
     Void,
-    String(String),
+    String(rc::Rc<cell::RefCell<JVMString>>),
     Object(rc::Rc<cell::RefCell<JVMObject>>),
+    Array(rc::Rc<cell::RefCell<JVMArray>>),
+    Null,
+    // pushed by jsr/jsr_w, the pc of the instruction following the jsr
+    ReturnAddress(usize),
 }
 
 /*
@@ -68,13 +243,297 @@ impl Clone for RuntimeValue {
             RuntimeValue::Float(i) => RuntimeValue::Float(*i),
             RuntimeValue::Double(i) => RuntimeValue::Double(*i),
             RuntimeValue::Void => RuntimeValue::Void,
-            RuntimeValue::String(s) => RuntimeValue::String(s.clone()),
+            RuntimeValue::String(s) => new_string(s),
             RuntimeValue::Object(object) => RuntimeValue::Object(object.clone()),
+            RuntimeValue::Array(array) => RuntimeValue::Array(array.clone()),
+            RuntimeValue::Null => RuntimeValue::Null,
+            RuntimeValue::ReturnAddress(pc) => RuntimeValue::ReturnAddress(*pc),
         }
     }
 }
 */
 
+impl RuntimeValue {
+    // longs and doubles take up two words on the operand stack and two local variable slots
+    // https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-2.html#jvms-2.11.1
+    fn is_category2(self: &RuntimeValue) -> bool {
+        match self {
+            RuntimeValue::Long(_) | RuntimeValue::Double(_) => true,
+            _ => false,
+        }
+    }
+
+    // if_acmpeq/if_acmpne compare references by identity
+    fn same_reference(self: &RuntimeValue, other: &RuntimeValue) -> Result<bool, JVMError> {
+        match (self, other) {
+            (RuntimeValue::Null, RuntimeValue::Null) => Ok(true),
+            (RuntimeValue::Object(object1), RuntimeValue::Object(object2)) => Ok(rc::Rc::ptr_eq(object1, object2)),
+            (RuntimeValue::Array(array1), RuntimeValue::Array(array2)) => Ok(rc::Rc::ptr_eq(array1, array2)),
+            (RuntimeValue::String(string1), RuntimeValue::String(string2)) => Ok(rc::Rc::ptr_eq(string1, string2)),
+            (RuntimeValue::Null | RuntimeValue::Object(_) | RuntimeValue::Array(_) | RuntimeValue::String(_),
+             RuntimeValue::Null | RuntimeValue::Object(_) | RuntimeValue::Array(_) | RuntimeValue::String(_)) => Ok(false),
+            _ => Err(format!("cannot compare non-references {:?} and {:?}", self, other).into()),
+        }
+    }
+
+    // the contents of a string, or None for anything else
+    fn to_rust_string(self: &RuntimeValue) -> Option<String> {
+        match self {
+            RuntimeValue::String(string) => Some(string.borrow().to_string()),
+            _ => None,
+        }
+    }
+
+    // the class of a reference, or None for null and non-references
+    fn class_name(self: &RuntimeValue) -> Option<String> {
+        match self {
+            RuntimeValue::Object(object) => Some(object.borrow().class.clone()),
+            RuntimeValue::Array(array) => Some(array.borrow().class.clone()),
+            RuntimeValue::String(_) => Some("java/lang/String".to_string()),
+            _ => None,
+        }
+    }
+
+    /* the class whose methods a reference has. arrays have the methods of java/lang/Object
+     * https://docs.oracle.com/javase/specs/jls/se17/html/jls-10.html#jls-10.7
+     */
+    fn method_class(self: &RuntimeValue) -> Option<String> {
+        match self {
+            RuntimeValue::Array(_) => Some("java/lang/Object".to_string()),
+            _ => self.class_name(),
+        }
+    }
+
+    /* the hash code Object.hashCode gives a reference, which stays the same for as long as the
+     * reference lives since what it refers to is never moved
+     */
+    fn identity_hash_code(self: &RuntimeValue) -> i32 {
+        let address = match self {
+            RuntimeValue::Object(object) => rc::Rc::as_ptr(object) as usize,
+            RuntimeValue::Array(array) => rc::Rc::as_ptr(array) as usize,
+            RuntimeValue::String(string) => rc::Rc::as_ptr(string) as usize,
+            _ => return 0,
+        };
+        // allocations are aligned, so the lowest bits are always the same
+        return ((address >> 3) as u32 & 0x7fffffff) as i32;
+    }
+}
+
+/* how far resolving a dynamically computed constant got. a resolution that failed with a
+ * LinkageError fails again with an error of the same class, message and cause class from then on,
+ * without calling the bootstrap method again
+ * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-5.html#jvms-5.4.3
+ */
+enum DynamicConstant {
+    Resolving,
+    Resolved(RuntimeValue),
+    Failed{class: String, message: RuntimeValue, cause: Option<(String, RuntimeValue)>},
+}
+
+/* a method either completes abruptly by throwing a java exception, which callers may catch, or
+ * fails because of a problem in the jvm itself
+ */
+pub enum JVMError {
+    Exception(rc::Rc<cell::RefCell<JVMObject>>),
+    Internal(String),
+}
+
+impl JVMError {
+    /* a new instance of one of the throwable classes provided by the jvm, for the errors the jvm
+     * itself raises such as a division by zero or a missing method
+     */
+    pub fn exception(class: &str, message: Option<String>) -> JVMError {
+        let mut fields = HashMap::new();
+        fields.insert("detailMessage".to_string(), match message {
+            Some(message) => new_string(&message),
+            None => RuntimeValue::Null,
+        });
+        fields.insert("cause".to_string(), RuntimeValue::Null);
+
+        return JVMError::Exception(rc::Rc::new(cell::RefCell::new(JVMObject{
+            class: class.to_string(),
+            fields: fields,
+        })));
+    }
+
+    /* a NullPointerException raised by an instruction using a null reference. its message is filled
+     * in once the method it was thrown in is known, see npe::null_pointer_message
+     */
+    pub fn null_pointer() -> JVMError {
+        let exception = JVMError::exception("java/lang/NullPointerException", None);
+        if let JVMError::Exception(object) = &exception {
+            object.borrow_mut().fields.insert("extendedMessageState".to_string(), RuntimeValue::Int(1));
+        }
+        return exception;
+    }
+
+    /* what the jvm prints when an exception escapes main: the Throwable.printStackTrace output */
+    pub fn stack_trace(self: &JVMError) -> String {
+        match self {
+            JVMError::Exception(exception) => format_stack_trace(exception),
+            JVMError::Internal(message) => format!("{}\n", message),
+        }
+    }
+}
+
+impl From<String> for JVMError {
+    fn from(message: String) -> JVMError {
+        return JVMError::Internal(message);
+    }
+}
+
+impl fmt::Display for JVMError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JVMError::Exception(exception) => write!(f, "{}", throwable_to_string(&exception.borrow())),
+            JVMError::Internal(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+// the value a field or local variable of the given type starts out with
+fn default_value(descriptor: &Descriptor) -> RuntimeValue {
+    match descriptor {
+        Descriptor::Byte | Descriptor::Char | Descriptor::Short | Descriptor::Boolean | Descriptor::Int => RuntimeValue::Int(0),
+        Descriptor::Long => RuntimeValue::Long(0),
+        Descriptor::Float => RuntimeValue::Float(0.0),
+        Descriptor::Double => RuntimeValue::Double(0.0),
+        Descriptor::Object(_) | Descriptor::Array(_) => RuntimeValue::Null,
+        Descriptor::Void => RuntimeValue::Void,
+    }
+}
+
+// java/lang/String becomes java.lang.String, the way class names are shown to java code
+fn external_class_name(class_name: &str) -> String {
+    return class_name.replace('/', ".");
+}
+
+// a type the way it is written in java source, such as int or java.lang.String[]
+fn descriptor_external_name(descriptor: &Descriptor) -> String {
+    match descriptor {
+        Descriptor::Byte => "byte".to_string(),
+        Descriptor::Char => "char".to_string(),
+        Descriptor::Double => "double".to_string(),
+        Descriptor::Float => "float".to_string(),
+        Descriptor::Int => "int".to_string(),
+        Descriptor::Long => "long".to_string(),
+        Descriptor::Short => "short".to_string(),
+        Descriptor::Boolean => "boolean".to_string(),
+        Descriptor::Void => "void".to_string(),
+        Descriptor::Object(class_name) => external_class_name(class_name),
+        Descriptor::Array(component) => format!("{}[]", descriptor_external_name(component)),
+    }
+}
+
+/* a method as named in linkage error messages, such as void Main.run(int, java.lang.String).
+ * the class is left out when there is none
+ */
+fn method_external_name(class_name: Option<&str>, name: &str, descriptor: &MethodDescriptor) -> String {
+    let parameters: Vec<String> = descriptor.parameters.iter().map(descriptor_external_name).collect();
+    let return_type = descriptor_external_name(&descriptor.return_type);
+    match class_name {
+        Some(class_name) => format!("{} {}.{}({})", return_type, external_class_name(class_name), name, parameters.join(", ")),
+        None => format!("{} {}({})", return_type, name, parameters.join(", ")),
+    }
+}
+
+/* the module and class loader a class belongs to, as described in a ClassCastException message.
+ * everything provided by this jvm itself is considered part of java.base
+ */
+fn describe_class_location(class_name: &str) -> &'static str {
+    let element = class_name.trim_start_matches('[');
+    let is_primitive_array = class_name.starts_with('[') && element.len() == 1;
+    let element = element.strip_prefix('L').and_then(|name| name.strip_suffix(';')).unwrap_or(element);
+
+    if is_primitive_array || ["java/", "javax/", "jdk/", "sun/"].iter().any(|prefix| element.starts_with(prefix)) {
+        return "module java.base of loader 'bootstrap'";
+    }
+
+    return "unnamed module of loader 'app'";
+}
+
+// the message hotspot uses for a ClassCastException
+fn class_cast_message(from: &str, to: &str) -> String {
+    let from_name = external_class_name(from);
+    let to_name = external_class_name(to);
+    let from_location = describe_class_location(from);
+    let to_location = describe_class_location(to);
+
+    if from_location == to_location {
+        return format!("class {} cannot be cast to class {} ({} and {} are in {})", from_name, to_name, from_name, to_name, from_location);
+    }
+
+    return format!("class {} cannot be cast to class {} ({} is in {}; {} is in {})", from_name, to_name, from_name, from_location, to_name, to_location);
+}
+
+pub fn make_string_array(strings: &[String]) -> RuntimeValue {
+    let elements = strings.iter().map(|string| new_string(string)).collect::<Vec<_>>();
+    return RuntimeValue::Array(rc::Rc::new(cell::RefCell::new(JVMArray{
+        class: "[Ljava/lang/String;".to_string(),
+        elements: ArrayElements::Reference(elements),
+    })));
+}
+
+/* a new java/lang/String, a different object from any other string even if it has the same
+ * contents. string literals are interned instead, see RuntimeConst::intern
+ */
+pub fn new_string(value: &str) -> RuntimeValue {
+    return RuntimeValue::String(rc::Rc::new(cell::RefCell::new(JVMString::from(value))));
+}
+
+fn new_string_of_chars(chars: Vec<u16>) -> RuntimeValue {
+    return RuntimeValue::String(rc::Rc::new(cell::RefCell::new(JVMString::new(chars))));
+}
+
+fn new_array(class: &str, length: i32) -> Result<RuntimeValue, JVMError> {
+    return Ok(RuntimeValue::Array(rc::Rc::new(cell::RefCell::new(JVMArray::new(class, length)?))));
+}
+
+/* multianewarray creates the outer `lengths.len()` dimensions, the components
+ * of the innermost created arrays are left as their default value
+ */
+fn new_multi_array(class: &str, lengths: &[i32]) -> Result<RuntimeValue, JVMError> {
+    // all the lengths are checked before anything is allocated
+    for length in lengths {
+        if *length < 0 {
+            return Err(JVMError::exception("java/lang/NegativeArraySizeException", Some(length.to_string())));
+        }
+    }
+
+    let array = new_array(class, lengths[0])?;
+    if lengths.len() > 1 {
+        if let RuntimeValue::Array(array) = &array {
+            let component = class[1..].to_string();
+            let mut array = array.borrow_mut();
+            for index in 0..lengths[0] {
+                array.store(index, new_multi_array(&component, &lengths[1..])?)?;
+            }
+        }
+    }
+
+    return Ok(array);
+}
+
+fn lookup_class_name(constant_pool: &ConstantPool, index: usize) -> Result<&str, JVMError> {
+    match constant_pool_lookup(constant_pool, index) {
+        Some(ConstantPoolEntry::Classref(name_index)) => {
+            match lookup_utf8_constant(constant_pool, *name_index as usize) {
+                Some(name) => Ok(name),
+                None => Err(format!("invalid class name index {}", name_index).into()),
+            }
+        },
+        _ => Err(format!("index {} was not a classref", index).into()),
+    }
+}
+
+fn pop_array(frame: &mut Frame) -> Result<rc::Rc<cell::RefCell<JVMArray>>, JVMError> {
+    match frame.pop_value_force()? {
+        RuntimeValue::Array(array) => Ok(array),
+        RuntimeValue::Null => Err(JVMError::null_pointer()),
+        value => Err(format!("expected an array on the stack but found {:?}", value).into()),
+    }
+}
+
 impl fmt::Debug for RuntimeValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -94,54 +553,265 @@ impl fmt::Debug for RuntimeValue {
                 write!(f, "Void")
             },
             RuntimeValue::String(value) => {
-                write!(f, "String({})", value)
+                write!(f, "String({})", value.borrow())
             },
             RuntimeValue::Object(value) => {
                 write!(f, "Object({:?})", value.borrow().class)
             },
+            RuntimeValue::Array(value) => {
+                write!(f, "Array({:?})", value.borrow().class)
+            },
+            RuntimeValue::Null => {
+                write!(f, "Null")
+            },
+            RuntimeValue::ReturnAddress(pc) => {
+                write!(f, "ReturnAddress({})", pc)
+            },
         }
     }
 }
 
-enum JVMMethod<'a>{
-    Native(fn(&[RuntimeValue]) -> RuntimeValue),
-    Bytecode(&'a MethodInfo),
+// a method the jvm implements itself, given the receiver if any followed by the arguments
+type NativeFunction = fn(&RuntimeConst, &[RuntimeValue]) -> Result<RuntimeValue, JVMError>;
+
+type JVMObjectRef = rc::Rc<cell::RefCell<JVMObject>>;
+
+#[derive(Clone)]
+enum JVMMethod {
+    Native(NativeFunction),
+    /* a native that may hand back the frame of a bytecode method to run in its place, such as the
+     * method of a lambda class invoking the implementation, so that it runs on the frame stack
+     */
+    Invoker(fn(&RuntimeConst, &[RuntimeValue]) -> Result<Invocation, JVMError>),
+    Bytecode(rc::Rc<MethodInfo>),
 }
 
-fn create_jvm_class(jvmclass: &JVMClassFile) -> Result<JVMClass, String> {
+/* the runtime form of a class file. the class takes over the constant pool and methods of the
+ * class file, so it no longer has to be kept around
+ */
+fn create_jvm_class(jvm: &RuntimeConst, jvmclass: JVMClassFile) -> Result<JVMClass, JVMError> {
     match constant_pool_lookup(&jvmclass.constant_pool, jvmclass.this_class as usize) {
         Some(ConstantPoolEntry::Classref(class_index)) => {
             match constant_pool_lookup(&jvmclass.constant_pool, *class_index as usize) {
                 Some(ConstantPoolEntry::Utf8(class_name)) => {
                     let mut methods = HashMap::new();
 
-                    for method in jvmclass.methods.iter() {
+                    for method in jvmclass.methods.into_iter() {
                         let method_name = lookup_method_name(&jvmclass.constant_pool, method.name_index as usize)?;
-                        methods.insert(method_name.to_string(), JVMMethod::Bytecode(method));
+                        let descriptor = match lookup_utf8_constant(&jvmclass.constant_pool, method.descriptor_index as usize) {
+                            Some(descriptor) => descriptor,
+                            None => return Err(format!("invalid descriptor for method {} in class {}", method_name, class_name).into()),
+                        };
+                        methods.insert(method_key(&method_name, descriptor), JVMMethod::Bytecode(rc::Rc::new(method)));
+                    }
+
+                    // only java/lang/Object has no superclass
+                    let super_class = if jvmclass.super_class == 0 {
+                        None
+                    } else {
+                        Some(lookup_class_name(&jvmclass.constant_pool, jvmclass.super_class as usize)?.to_string())
+                    };
+
+                    let mut interfaces = Vec::new();
+                    for interface in jvmclass.interfaces.iter() {
+                        interfaces.push(lookup_class_name(&jvmclass.constant_pool, *interface as usize)?.to_string());
+                    }
+
+                    let mut source_file = None;
+                    let mut bootstrap_methods = Vec::new();
+                    for attribute in jvmclass.attributes.into_iter() {
+                        match attribute {
+                            AttributeKind::SourceFile{sourcefile_index} => {
+                                source_file = lookup_utf8_constant(&jvmclass.constant_pool, sourcefile_index as usize).map(|name| name.to_string());
+                            },
+                            AttributeKind::BootstrapMethods{bootstrap_methods: methods} => {
+                                bootstrap_methods = methods;
+                            },
+                            _ => {
+                            }
+                        }
+                    }
+
+                    /* static fields start out with their default value as well, unless they are constants
+                     * with a ConstantValue attribute
+                     */
+                    let mut fields = HashMap::new();
+                    let mut instance_fields = HashMap::new();
+                    for field in jvmclass.fields.iter() {
+                        match (lookup_utf8_constant(&jvmclass.constant_pool, field.name_index as usize), lookup_utf8_constant(&jvmclass.constant_pool, field.descriptor_index as usize)) {
+                            (Some(name), Some(descriptor)) => {
+                                let value = default_value(&parse_type_descriptor(descriptor)?);
+                                if field.access_flags & ACC_STATIC != 0 {
+                                    let value = match constant_value(jvm, &jvmclass.constant_pool, field)? {
+                                        Some(constant) => constant,
+                                        None => value,
+                                    };
+                                    fields.insert(name.to_string(), value);
+                                } else {
+                                    instance_fields.insert(name.to_string(), value);
+                                }
+                            },
+                            _ => {
+                                return Err(format!("invalid field in class {}", class_name).into());
+                            }
+                        }
                     }
 
+                    let class_name = class_name.to_string();
                     return Ok(JVMClass{
-                        class: class_name.to_string(),
+                        class: class_name,
+                        access_flags: jvmclass.access_flags,
+                        major_version: jvmclass.major_version,
+                        super_class: super_class,
+                        interfaces: interfaces,
+                        source_file: source_file,
+                        constant_pool: rc::Rc::new(jvmclass.constant_pool),
                         methods: methods,
-                        fields: HashMap::new(),
+                        fields: cell::RefCell::new(fields),
+                        instance_fields: instance_fields,
+                        object_fields: HashMap::new(),
+                        field_owners: HashMap::new(),
+                        bootstrap_methods: bootstrap_methods,
+                        call_sites: cell::RefCell::new(HashMap::new()),
+                        dynamic_constants: cell::RefCell::new(HashMap::new()),
+                        subtype_cache: cell::RefCell::new(HashMap::new()),
+                        initialization: cell::RefCell::new(Initialization::NotStarted),
                     })
                 },
                 _ => {
-                    return Err("Invalid name reference".to_string());
+                    return Err("Invalid name reference".to_string().into());
                 }
             }
         },
         _ => {
-            return Err("Invalid class reference".to_string());
+            return Err("Invalid class reference".to_string().into());
+        }
+    }
+
+}
+
+/* the key a bytecode method is known by in its class, its name followed by its descriptor such as
+ * add(II)I, since methods may be overloaded. native methods are keyed by name alone and take care
+ * of all the overloads themselves
+ */
+fn method_key(name: &str, descriptor: &str) -> String {
+    return format!("{}{}", name, descriptor);
+}
+
+// the access flags of a method, the methods the jvm provides itself are all public
+fn method_access_flags(method: &JVMMethod) -> u16 {
+    match method {
+        JVMMethod::Native(_) | JVMMethod::Invoker(_) => ACC_PUBLIC,
+        JVMMethod::Bytecode(info) => info.access_flags,
+    }
+}
+
+// the package of a class, such as com/acme for com/acme/Foo
+fn package_name(class_name: &str) -> &str {
+    return match class_name.rfind('/') {
+        Some(index) => &class_name[..index],
+        None => "",
+    };
+}
+
+/* the initial value of a static field from its ConstantValue attribute
+ * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-4.html#jvms-4.7.2
+ */
+fn constant_value(jvm: &RuntimeConst, constant_pool: &ConstantPool, field: &FieldInfo) -> Result<Option<RuntimeValue>, JVMError> {
+    for attribute in field.attributes.iter() {
+        if let AttributeKind::ConstantValue{constantvalue_index} = attribute {
+            let value = match constant_pool_lookup(constant_pool, *constantvalue_index as usize) {
+                Some(ConstantPoolEntry::Integer(value)) => RuntimeValue::Int(*value),
+                Some(ConstantPoolEntry::Long(value)) => RuntimeValue::Long(*value),
+                Some(ConstantPoolEntry::Float(value)) => RuntimeValue::Float(*value),
+                Some(ConstantPoolEntry::Double(value)) => RuntimeValue::Double(*value),
+                Some(ConstantPoolEntry::Stringref(string_index)) => {
+                    match lookup_string_constant(constant_pool, *string_index as usize) {
+                        Some(string) => jvm.intern_contents(string),
+                        None => return Err(format!("invalid string constant {}", string_index).into()),
+                    }
+                },
+                _ => return Err(format!("invalid constant value index {}", constantvalue_index).into()),
+            };
+            return Ok(Some(value));
+        }
+    }
+
+    return Ok(None);
+}
+
+/* the source line of the instruction at `pc` in a method, or -1 if the method has no
+ * LineNumberTable. the line is that of the closest entry starting at or before `pc`
+ */
+fn line_number(method: &MethodInfo, pc: usize) -> i32 {
+    if let Some(AttributeKind::Code{attributes, ..}) = lookup_code_attribute(method) {
+        let mut closest: Option<&LineNumberTableEntry> = None;
+        for attribute in attributes {
+            if let AttributeKind::LineNumberTable{line_number_table} = attribute {
+                for entry in line_number_table {
+                    if entry.start_pc as usize <= pc && closest.map_or(true, |closest| entry.start_pc >= closest.start_pc) {
+                        closest = Some(entry);
+                    }
+                }
+            }
+        }
+
+        if let Some(entry) = closest {
+            return entry.line_number as i32;
         }
     }
 
+    return -1;
+}
+
+/* how far a class is through initialization. a class whose initializer failed records the
+ * ExceptionInInitializerError that becomes the cause of the NoClassDefFoundError thrown by every
+ * later attempt to use it
+ * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-5.html#jvms-5.5
+ */
+#[derive(Clone)]
+enum Initialization {
+    NotStarted,
+    InProgress,
+    Done,
+    Failed(rc::Rc<cell::RefCell<JVMObject>>),
 }
 
-struct JVMClass<'a>{
+struct JVMClass {
     class: String,
-    methods: HashMap<String, JVMMethod<'a>>,
-    fields: HashMap<String, RuntimeValue>,
+    access_flags: u16,
+    major_version: u16,
+    super_class: Option<String>,
+    interfaces: Vec<String>,
+    // the SourceFile attribute, such as Main.java, if the class was compiled with one
+    source_file: Option<String>,
+    /* the constant pool of the class file, which the bytecode of its methods refers to. the
+     * classes the jvm provides itself only have native methods, so theirs is empty
+     */
+    constant_pool: rc::Rc<ConstantPool>,
+    methods: HashMap<String, JVMMethod>,
+    // the static fields, which getstatic and putstatic use once the class is initialized
+    fields: cell::RefCell<HashMap<String, RuntimeValue>>,
+    // the initial value of every instance field the class declares itself
+    instance_fields: HashMap<String, RuntimeValue>,
+    /* the initial value of every field of an object of the class, its own and the ones it inherits,
+     * copied into each new object. a superclass field hidden by a field of the same name further
+     * down is kept under the name of the class that declares it, as in com/acme/Base.count
+     */
+    object_fields: HashMap<String, RuntimeValue>,
+    // the class that declares each of object_fields
+    field_owners: HashMap<String, String>,
+    bootstrap_methods: Vec<BootstrapMethod>,
+    /* the targets of the invokedynamic instructions linked so far, keyed by the name and descriptor
+     * indices of the method and the pc of the instruction, since every instruction is a call site
+     * of its own
+     */
+    call_sites: cell::RefCell<HashMap<(u16, u16, usize), RuntimeValue>>,
+    // the dynamically computed constants, keyed by their constant pool index
+    dynamic_constants: cell::RefCell<HashMap<usize, DynamicConstant>>,
+    // the results of is_subclass_of, keyed by the name of the would-be superclass or interface
+    subtype_cache: cell::RefCell<HashMap<String, bool>>,
+    initialization: cell::RefCell<Initialization>,
 }
 
 pub struct JVMObject{
@@ -158,149 +828,818 @@ impl Clone for JVMObject {
     }
 }
 
-impl <'a>JVMClass<'a>{
-    fn create_object(self: &JVMClass<'a>) -> JVMObject {
+impl JVMClass {
+    fn create_object(self: &JVMClass) -> JVMObject {
         return JVMObject{
             class: self.class.clone(),
-            fields: HashMap::new(),
+            fields: self.object_fields.clone(),
+        }
+    }
+
+    // the method a class declares itself with a name and descriptor
+    fn declared_method(self: &JVMClass, name: &str, descriptor: &str) -> Option<&JVMMethod> {
+        return self.methods.get(&method_key(name, descriptor)).or_else(|| self.methods.get(name));
+    }
+
+    /* lay out the fields of objects of this class: those of its superclass followed by its own. a
+     * field of the superclass with the same name as one of ours moves to its qualified name
+     */
+    fn inherit_fields(self: &mut JVMClass, super_class: &JVMClass) {
+        let mut object_fields = super_class.object_fields.clone();
+        let mut field_owners = super_class.field_owners.clone();
+        for (name, value) in self.instance_fields.iter() {
+            if let (Some(hidden), Some(owner)) = (object_fields.remove(name), field_owners.remove(name)) {
+                let key = format!("{}.{}", owner, name);
+                object_fields.insert(key.clone(), hidden);
+                field_owners.insert(key, owner);
+            }
+            object_fields.insert(name.clone(), value.clone());
+            field_owners.insert(name.clone(), self.class.clone());
+        }
+
+        self.object_fields = object_fields;
+        self.field_owners = field_owners;
+    }
+
+    // the key objects of this class store a field declared by `declaring_class` under
+    fn field_key(self: &JVMClass, declaring_class: &str, name: &str) -> String {
+        if self.field_owners.get(name).map(|owner| owner.as_str()) == Some(declaring_class) {
+            return name.to_string();
+        }
+        return format!("{}.{}", declaring_class, name);
+    }
+
+    /* whether this class is `target` or has it as a superclass or superinterface. the
+     * supertypes of classes that are not loaded are not known, so they are not searched
+     */
+    fn is_subclass_of(self: &JVMClass, jvm: &RuntimeConst, target: &str) -> bool {
+        if self.class == target {
+            return true;
+        }
+
+        if let Some(result) = self.subtype_cache.borrow().get(target) {
+            return *result;
+        }
+
+        let mut result = false;
+        for parent in self.super_class.iter().chain(self.interfaces.iter()) {
+            if parent == target {
+                result = true;
+                break;
+            }
+
+            if let Some(parent_class) = jvm.lookup_class(parent) {
+                if parent_class.is_subclass_of(jvm, target) {
+                    result = true;
+                    break;
+                }
+            }
         }
+
+        self.subtype_cache.borrow_mut().insert(target.to_string(), result);
+        return result;
     }
 }
 
+/* a method being executed. frames live on an explicit stack owned by run_method rather than on
+ * the rust stack, so deep java recursion ends in a StackOverflowError instead of crashing the jvm
+ */
 struct Frame {
+    class: rc::Rc<JVMClass>,
+    method: rc::Rc<MethodInfo>,
+    // where to continue once a method invoked by this one returns or an exception is caught
+    pc: usize,
     stack: Vec<RuntimeValue>,
     locals: Vec<RuntimeValue>,
 }
 
+// what executing a frame stops for
+enum Step {
+    // the method returned, with Void for a void method
+    Return(RuntimeValue),
+    // the method invoked a bytecode method, which runs in this new frame
+    Call(Frame),
+}
+
+// what an invoke instruction leads to
+enum Invocation {
+    // a native method ran and produced its result
+    Completed(RuntimeValue),
+    // a bytecode method is ready to run in this new frame
+    Call(Frame),
+}
+
+// a method being executed, as far as stack traces are concerned
+struct CallFrame {
+    class: String,
+    method: String,
+    // the method itself, for the line numbers of stack traces
+    method_info: rc::Rc<MethodInfo>,
+    // the instruction being executed
+    pc: usize,
+    // the part of the stack size this frame takes up
+    size: usize,
+}
+
+/* the thread stack size, like -Xss, when none is given. each frame is charged a word for each of
+ * its local variables and operand stack slots plus a fixed overhead, roughly what an interpreted
+ * frame takes in hotspot
+ */
+pub const DEFAULT_STACK_SIZE: usize = 1024 * 1024;
+const FRAME_OVERHEAD: usize = 96;
+/* how many runs of the interpreter may be nested on the native stack, see run_method, and the
+ * native stack the interpreter needs for that many. a nested run takes about 45k in a debug build
+ */
+const MAX_NESTED_RUNS: usize = 1000;
+pub const NATIVE_STACK_SIZE: usize = 256 * 1024 * 1024;
+const WORD_SIZE: usize = 8;
+
+/* how the jvm is set up from the command line */
+pub struct JVMOptions {
+    // the directories and jars classes are loaded from, searched in order, like -classpath
+    pub class_path: ClassPath,
+    // the most stack the frames of java methods may take up, like -Xss
+    pub stack_size: usize,
+}
+
 struct RuntimeConst<'a> {
-    classes: HashMap<String, JVMClass<'a>>,
+    // every class loaded so far, shared with the frames running their methods
+    classes: cell::RefCell<HashMap<String, rc::Rc<JVMClass>>>,
+    class_path: &'a ClassPath,
+    // the methods being executed, the innermost one last
+    call_stack: cell::RefCell<Vec<CallFrame>>,
+    // the most stack the frames on the call stack may take up, and how much they take now
+    stack_size: usize,
+    stack_used: cell::Cell<usize>,
+    /* how many runs of the interpreter are nested in one another on the native stack, one for
+     * each time a native such as String.valueOf calls back into java code
+     */
+    nested_runs: cell::Cell<usize>,
+    // the java/lang/Class object of each class that has been asked for one
+    class_objects: cell::RefCell<HashMap<String, rc::Rc<cell::RefCell<JVMObject>>>>,
+    // how many lambda classes have been made, to give each its own name
+    lambda_count: cell::Cell<usize>,
+    // the interned strings, which string literals and String.intern share, by their contents
+    strings: cell::RefCell<HashMap<JVMString, rc::Rc<cell::RefCell<JVMString>>>>,
+    // the objects valueOf boxes small values into, by wrapper class and value
+    boxes: cell::RefCell<HashMap<(String, i64), JVMObjectRef>>,
 }
 
-impl <'a, 'b: 'a>RuntimeConst<'a> {
-    fn lookup_class(self: &RuntimeConst<'a>, class_name: &str) -> Option<&JVMClass> {
-        return self.classes.get(class_name);
+impl <'a>RuntimeConst<'a> {
+    /* the one string with the given contents that all string literals and interned strings with
+     * those contents are
+     * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-5.html#jvms-5.1
+     */
+    fn intern(self: &RuntimeConst<'a>, string: &rc::Rc<cell::RefCell<JVMString>>) -> RuntimeValue {
+        let contents = string.borrow().clone();
+        let interned = self.strings.borrow_mut().entry(contents).or_insert_with(|| string.clone()).clone();
+        return RuntimeValue::String(interned);
     }
 
-    fn add_class(self: &mut RuntimeConst<'a>, jvm_class: JVMClass<'b>){
-        self.classes.insert(jvm_class.class.clone(), jvm_class);
+    fn intern_str(self: &RuntimeConst<'a>, value: &str) -> RuntimeValue {
+        return self.intern_contents(JVMString::from(value));
     }
-}
 
-impl Frame {
-    fn as_ref(self: &mut Frame) -> &mut Frame {
-        return self
+    fn intern_contents(self: &RuntimeConst<'a>, contents: JVMString) -> RuntimeValue {
+        if let Some(interned) = self.strings.borrow().get(&contents) {
+            return RuntimeValue::String(interned.clone());
+        }
+        let string = rc::Rc::new(cell::RefCell::new(contents.clone()));
+        self.strings.borrow_mut().insert(contents, string.clone());
+        return RuntimeValue::String(string);
     }
 
-    fn push_value(self: &mut Frame, value: RuntimeValue) {
-        self.stack.push(value);
+    fn lookup_class(self: &RuntimeConst<'a>, class_name: &str) -> Option<rc::Rc<JVMClass>> {
+        return self.load_class(class_name).ok();
     }
 
-    fn pop_value(self: &mut Frame) -> Option<RuntimeValue> {
-        return self.stack.pop();
-    }
+    /* find a class by its binary name, such as com/acme/Foo, loading it from the class path the
+     * first time it is asked for. loading a class loads its superclass and interfaces as well
+     * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-5.html#jvms-5.3
+     */
+    fn load_class(self: &RuntimeConst<'a>, class_name: &str) -> Result<rc::Rc<JVMClass>, JVMError> {
+        if let Some(class) = self.classes.borrow().get(class_name) {
+            return Ok(class.clone());
+        }
 
-    fn pop_value_force(self: &mut Frame) -> Result<RuntimeValue, String> {
-        match self.stack.pop() {
-            Some(value) => {
-                return Ok(value);
-            },
-            None => {
-                return Err("Stack underflow".to_string());
+        // array classes are created by the jvm rather than loaded
+        if !class_name.starts_with('[') {
+            match self.class_path.read_class(class_name) {
+                Ok(Some(contents)) => {
+                    return self.define_class(class_name, &contents);
+                },
+                Ok(None) => {
+                },
+                Err(err) => {
+                    debug!("could not read class {}: {}", class_name, err);
+                }
             }
         }
+
+        return Err(JVMError::exception("java/lang/NoClassDefFoundError", Some(class_name.to_string())));
     }
-}
 
-fn invoke_static(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &RuntimeConst, method_index: usize) -> Result<RuntimeValue, String> {
-    match constant_pool_lookup(constant_pool, method_index) {
-        Some(ConstantPoolEntry::Methodref(class_index, name_and_type_index)) => {
-            match constant_pool_lookup(constant_pool, *class_index as usize) {
-                Some(ConstantPoolEntry::Classref(class_index)) => {
-                    match constant_pool_lookup(constant_pool, *class_index as usize) {
-                        Some(ConstantPoolEntry::Utf8(class_name)) => {
-                            debug!("Invoke method on class {}", class_name);
+    fn define_class(self: &RuntimeConst<'a>, class_name: &str, contents: &[u8]) -> Result<rc::Rc<JVMClass>, JVMError> {
+        debug!("loading class {}", class_name);
+        let class_file = match read_class_file(&mut &contents[..]) {
+            Ok(class_file) => class_file,
+            Err(err) => {
+                return Err(JVMError::exception("java/lang/ClassFormatError", Some(format!("{} in class file {}", err, class_name))));
+            }
+        };
 
-                            match constant_pool_lookup(constant_pool, *name_and_type_index as usize) {
-                                Some(ConstantPoolEntry::NameAndType{name_index, descriptor_index}) => {
-                                    match constant_pool_lookup(constant_pool, *name_index as usize) {
-                                        Some(ConstantPoolEntry::Utf8(method_name)) => {
-                                            debug!("invoke static method {} on class {}", method_name, class_name);
+        let class = create_jvm_class(self, class_file)?;
+        if class.class != class_name {
+            let message = format!("{} (wrong name: {})", class_name, class.class);
+            return Err(JVMError::exception("java/lang/NoClassDefFoundError", Some(message)));
+        }
 
-                                            if let Some(descriptor) = lookup_utf8_constant(constant_pool, *descriptor_index as usize) {
-                                                let method_descriptor = parse_method_descriptor(descriptor)?;
-                                                let mut locals = Vec::new();
-                                                for _i in 0..method_descriptor.parameters.len() {
-                                                    locals.push(frame.pop_value_force()?);
-                                                }
-                                                locals.reverse();
+        return self.link_class(class);
+    }
 
-                                                match jvm.lookup_class(class_name) {
-                                                    Some(class) => {
-                                                        match class.methods.get(method_name) {
-                                                            Some(method) => {
-                                                                match method {
-                                                                    JVMMethod::Native(f) => {
-                                                                        debug!("invoke native method");
-                                                                        return Ok(f(locals.as_slice()));
-                                                                    },
-                                                                    JVMMethod::Bytecode(info) => {
-                                                                        debug!("invoke bytecode method stack size {}", frame.stack.len());
+    // add a class once its superclass and interfaces are loaded, so it can inherit from them
+    fn link_class(self: &RuntimeConst<'a>, class: JVMClass) -> Result<rc::Rc<JVMClass>, JVMError> {
+        if let Some(super_class) = &class.super_class {
+            self.load_class(super_class)?;
+        }
+        for interface in class.interfaces.iter() {
+            self.load_class(interface)?;
+        }
 
-                                                                        if let Some(AttributeKind::Code { max_stack: _, max_locals, code: _, exception_table: _, attributes: _ }) = lookup_code_attribute(info) {
-                                                                            for _i in 0..((*max_locals as usize) - locals.len()) {
-                                                                                locals.push(RuntimeValue::Int(0));
-                                                                            }
-                                                                        }
+        return Ok(self.add_class(class));
+    }
 
-                                                                        let mut new_frame = create_frame(info)?;
-                                                                        new_frame.locals = locals;
-                                                                        return do_execute_method(&info, constant_pool, &mut new_frame, jvm);
-                                                                    }
-                                                                }
-                                                            },
-                                                            None => {
-                                                                debug!("  Unknown method {}", method_name);
-                                                            }
-                                                        }
-                                                    },
-                                                    _ => {
-                                                        debug!("  Unknown class {}", class_name);
-                                                    }
-                                                }
-                                            } else {
-                                                return Err(format!("could not find descriptor {}", *descriptor_index))
-                                            }
-                                        },
-                                        _ => {
-                                            return Err("Invalid name and type".to_string());
-                                        }
-                                    }
-                                },
-                                _ => {
-                                    return Err("Invalid name and type".to_string());
-                                }
-                            }
-                        },
-                        _ => {
-                            return Err("Invalid classref".to_string());
-                        }
-                    }
-                },
-                _ => {
-                    return Err("Invalid classref".to_string());
-                }
-            }
-        },
-        _ => {
-            return Err("Invalid methodref".to_string());
+    fn current_pc(self: &RuntimeConst<'a>) -> usize {
+        match self.call_stack.borrow().last() {
+            Some(call) => call.pc,
+            None => 0,
         }
     }
-    return Err("error invoking method".to_string());
-}
 
-fn invoke_special(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &RuntimeConst, method_index: usize) -> Result<(), String> {
+    fn set_current_pc(self: &RuntimeConst<'a>, pc: usize) {
+        if let Some(call) = self.call_stack.borrow_mut().last_mut() {
+            call.pc = pc;
+        }
+    }
+
+    /* record a frame on the call stack. a frame that does not fit in what is left of the stack size
+     * throws StackOverflowError in the caller instead
+     */
+    fn enter_method(self: &RuntimeConst<'a>, frame: &Frame) -> Result<(), JVMError> {
+        let max_stack = match lookup_code_attribute(&frame.method) {
+            Some(AttributeKind::Code{max_stack, ..}) => *max_stack as usize,
+            _ => 0,
+        };
+        let size = FRAME_OVERHEAD + (frame.locals.len() + max_stack) * WORD_SIZE;
+        if self.stack_used.get() + size > self.stack_size {
+            return Err(JVMError::exception("java/lang/StackOverflowError", None));
+        }
+
+        self.stack_used.set(self.stack_used.get() + size);
+        self.call_stack.borrow_mut().push(CallFrame{
+            class: frame.class.class.clone(),
+            method: lookup_utf8_constant(&frame.class.constant_pool, frame.method.name_index as usize).unwrap_or("<unknown>").to_string(),
+            method_info: frame.method.clone(),
+            pc: 0,
+            size: size,
+        });
+        return Ok(());
+    }
+
+    // the java/lang/Class object for a class, the same object every time
+    fn class_object(self: &RuntimeConst<'a>, class_name: &str) -> RuntimeValue {
+        let object = self.class_objects.borrow_mut().entry(class_name.to_string()).or_insert_with(|| {
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), new_string(class_name));
+            return rc::Rc::new(cell::RefCell::new(JVMObject{
+                class: "java/lang/Class".to_string(),
+                fields: fields,
+            }));
+        }).clone();
+        return RuntimeValue::Object(object);
+    }
+
+    fn leave_method(self: &RuntimeConst<'a>) {
+        if let Some(call) = self.call_stack.borrow_mut().pop() {
+            self.stack_used.set(self.stack_used.get() - call.size);
+        }
+    }
+
+    // System.out or System.err
+    fn system_stream(self: &RuntimeConst<'a>, name: &str) -> Result<RuntimeValue, JVMError> {
+        match self.lookup_class("java/lang/System").and_then(|system| system.fields.borrow().get(name).cloned()) {
+            Some(stream) => Ok(stream),
+            None => Err(format!("no stream System.{}", name).into()),
+        }
+    }
+
+    // the superclass of a class has to be added before it
+    fn add_class(self: &RuntimeConst<'a>, mut jvm_class: JVMClass) -> rc::Rc<JVMClass> {
+        let super_class = jvm_class.super_class.as_ref().and_then(|name| self.classes.borrow().get(name).cloned());
+        match super_class {
+            Some(super_class) => jvm_class.inherit_fields(&super_class),
+            None => {
+                jvm_class.object_fields = jvm_class.instance_fields.clone();
+                jvm_class.field_owners = jvm_class.instance_fields.keys().map(|name| (name.clone(), jvm_class.class.clone())).collect();
+            }
+        }
+
+        let jvm_class = rc::Rc::new(jvm_class);
+        self.classes.borrow_mut().insert(jvm_class.class.clone(), jvm_class.clone());
+        return jvm_class;
+    }
+
+    /* initialize a class on its first active use: its superclass first, then its static
+     * initializer. the initializer runs to completion in a nested run of the interpreter before
+     * the instruction that needed the class carries on
+     * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-5.html#jvms-5.5
+     */
+    fn initialize_class(self: &RuntimeConst<'a>, class: &rc::Rc<JVMClass>) -> Result<(), JVMError> {
+        let state = class.initialization.borrow().clone();
+        match state {
+            // there is only one thread, so a class being initialized is in use by its own initializer
+            Initialization::Done | Initialization::InProgress => {
+                return Ok(());
+            },
+            Initialization::Failed(cause) => {
+                let message = format!("Could not initialize class {}", external_class_name(&class.class));
+                let error = JVMError::exception("java/lang/NoClassDefFoundError", Some(message));
+                if let JVMError::Exception(object) = &error {
+                    object.borrow_mut().fields.insert("cause".to_string(), RuntimeValue::Object(cause));
+                }
+                return Err(error);
+            },
+            Initialization::NotStarted => {
+            }
+        }
+
+        *class.initialization.borrow_mut() = Initialization::InProgress;
+        match self.run_initializer(class) {
+            Ok(()) => {
+                *class.initialization.borrow_mut() = Initialization::Done;
+                return Ok(());
+            },
+            Err(JVMError::Exception(exception)) => {
+                *class.initialization.borrow_mut() = Initialization::Failed(initialization_failure(&exception));
+
+                // errors are thrown as they are, anything else is wrapped
+                let exception_class = exception.borrow().class.clone();
+                if self.is_assignable(&exception_class, "java/lang/Error") {
+                    return Err(JVMError::Exception(exception));
+                }
+                let error = JVMError::exception("java/lang/ExceptionInInitializerError", None);
+                if let JVMError::Exception(object) = &error {
+                    object.borrow_mut().fields.insert("cause".to_string(), RuntimeValue::Object(exception));
+                }
+                return Err(error);
+            },
+            Err(error) => {
+                return Err(error);
+            }
+        }
+    }
+
+    fn run_initializer(self: &RuntimeConst<'a>, class: &rc::Rc<JVMClass>) -> Result<(), JVMError> {
+        if let Some(super_class) = &class.super_class {
+            let super_class = self.load_class(super_class)?;
+            self.initialize_class(&super_class)?;
+        }
+
+        if let Some(JVMMethod::Bytecode(initializer)) = class.declared_method("<clinit>", "()V") {
+            debug!("initializing class {}", class.class);
+            run_method(self, create_frame(class.clone(), initializer.clone())?)?;
+        }
+        return Ok(());
+    }
+
+    /* find the class that declares a static field: the class named by the fieldref, one of its
+     * superinterfaces or one of its superclasses
+     * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-5.html#jvms-5.4.3.2
+     */
+    fn resolve_static_field(self: &RuntimeConst<'a>, class_name: &str, field_name: &str) -> Option<rc::Rc<JVMClass>> {
+        let class = self.lookup_class(class_name)?;
+        if class.fields.borrow().contains_key(field_name) {
+            return Some(class);
+        }
+
+        for interface in class.interfaces.iter() {
+            if let Some(declaring_class) = self.resolve_static_field(interface, field_name) {
+                return Some(declaring_class);
+            }
+        }
+
+        return match &class.super_class {
+            Some(super_class) => self.resolve_static_field(super_class, field_name),
+            None => None,
+        };
+    }
+
+    /* invokevirtual only invokes methods of classes and invokeinterface only methods of
+     * interfaces
+     */
+    fn expect_class_kind(self: &RuntimeConst<'a>, class_name: &str, interface: bool) -> Result<(), JVMError> {
+        let class = self.load_class(class_name)?;
+        let is_interface = class.access_flags & ACC_INTERFACE != 0;
+        if is_interface && !interface {
+            let message = format!("Found interface {}, but class was expected", external_class_name(class_name));
+            return Err(JVMError::exception("java/lang/IncompatibleClassChangeError", Some(message)));
+        }
+        if !is_interface && interface {
+            let message = format!("Found class {}, but interface was expected", external_class_name(class_name));
+            return Err(JVMError::exception("java/lang/IncompatibleClassChangeError", Some(message)));
+        }
+        return Ok(());
+    }
+
+    /* resolve the method a methodref, or an interface methodref when `interface_ref` is set, refers
+     * to. a methodref has to name a class and an interface methodref an interface
+     */
+    fn resolve_method_ref(self: &RuntimeConst<'a>, class_name: &str, method_name: &str, descriptor: &str, interface_ref: bool) -> Result<Option<(rc::Rc<JVMClass>, JVMMethod)>, JVMError> {
+        let class = self.load_class(class_name)?;
+        let is_interface = class.access_flags & ACC_INTERFACE != 0;
+        if is_interface != interface_ref {
+            let method = method_external_name(Some(class_name), method_name, &parse_method_descriptor(descriptor)?);
+            let message = format!("Method '{}' must be {} constant", method, if is_interface { "InterfaceMethodref" } else { "Methodref" });
+            return Err(JVMError::exception("java/lang/IncompatibleClassChangeError", Some(message)));
+        }
+
+        if is_interface {
+            return Ok(self.resolve_interface_method(class_name, method_name, descriptor));
+        }
+        return Ok(self.resolve_method(class_name, method_name, descriptor));
+    }
+
+    /* find a method in a class or the closest superclass that declares it, and failing that in
+     * the superinterfaces of the class
+     * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-5.html#jvms-5.4.3.3
+     */
+    fn resolve_method(self: &RuntimeConst<'a>, class_name: &str, method_name: &str, descriptor: &str) -> Option<(rc::Rc<JVMClass>, JVMMethod)> {
+        let mut current = self.lookup_class(class_name);
+        while let Some(class) = current {
+            if let Some(method) = class.declared_method(method_name, descriptor) {
+                return Some((class.clone(), method.clone()));
+            }
+
+            current = match &class.super_class {
+                Some(super_class) => self.lookup_class(super_class),
+                None => None,
+            };
+        }
+
+        return self.superinterface_method(class_name, method_name, descriptor);
+    }
+
+    /* find a method in an interface, in java/lang/Object or in the superinterfaces of the
+     * interface
+     * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-5.html#jvms-5.4.3.4
+     */
+    fn resolve_interface_method(self: &RuntimeConst<'a>, interface_name: &str, method_name: &str, descriptor: &str) -> Option<(rc::Rc<JVMClass>, JVMMethod)> {
+        let interface = self.lookup_class(interface_name)?;
+        if let Some(method) = interface.declared_method(method_name, descriptor) {
+            return Some((interface.clone(), method.clone()));
+        }
+
+        if let Some(object) = self.lookup_class("java/lang/Object") {
+            if let Some(method) = object.declared_method(method_name, descriptor) {
+                if method_access_flags(method) & ACC_PUBLIC != 0 && method_access_flags(method) & ACC_STATIC == 0 {
+                    return Some((object.clone(), method.clone()));
+                }
+            }
+        }
+
+        return self.superinterface_method(interface_name, method_name, descriptor);
+    }
+
+    /* the method a class inherits from its superinterfaces: the only maximally-specific one that
+     * is not abstract if there is one, otherwise any of them
+     */
+    fn superinterface_method(self: &RuntimeConst<'a>, class_name: &str, method_name: &str, descriptor: &str) -> Option<(rc::Rc<JVMClass>, JVMMethod)> {
+        let methods = self.maximally_specific_methods(class_name, method_name, descriptor);
+        let concrete: Vec<&(rc::Rc<JVMClass>, JVMMethod)> = methods.iter().filter(|(_, method)| method_access_flags(method) & ACC_ABSTRACT == 0).collect();
+        if concrete.len() == 1 {
+            return Some(concrete[0].clone());
+        }
+        return methods.into_iter().next();
+    }
+
+    /* the methods with a name and descriptor declared by the superinterfaces of a class that no
+     * other of them overrides, leaving out private and static methods, which are not inherited
+     * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-5.html#jvms-5.4.3.3
+     */
+    fn maximally_specific_methods(self: &RuntimeConst<'a>, class_name: &str, method_name: &str, descriptor: &str) -> Vec<(rc::Rc<JVMClass>, JVMMethod)> {
+        let mut methods = Vec::new();
+        self.collect_superinterface_methods(class_name, method_name, descriptor, &mut methods);
+        return methods.iter()
+            .filter(|(interface, _)| !methods.iter().any(|(other, _)| other.class != interface.class && other.is_subclass_of(self, &interface.class)))
+            .cloned()
+            .collect();
+    }
+
+    fn collect_superinterface_methods(self: &RuntimeConst<'a>, class_name: &str, method_name: &str, descriptor: &str, methods: &mut Vec<(rc::Rc<JVMClass>, JVMMethod)>) {
+        let class = match self.lookup_class(class_name) {
+            Some(class) => class,
+            None => return,
+        };
+
+        for interface_name in class.interfaces.iter() {
+            if let Some(interface) = self.lookup_class(interface_name) {
+                // an interface reached along more than one path is only counted once
+                let seen = methods.iter().any(|(other, _)| other.class == interface.class);
+                if let Some(method) = interface.declared_method(method_name, descriptor) {
+                    if !seen && method_access_flags(method) & (ACC_PRIVATE | ACC_STATIC) == 0 {
+                        methods.push((interface.clone(), method.clone()));
+                    }
+                }
+            }
+            self.collect_superinterface_methods(interface_name, method_name, descriptor, methods);
+        }
+
+        if let Some(super_class) = &class.super_class {
+            self.collect_superinterface_methods(super_class, method_name, descriptor, methods);
+        }
+    }
+
+    /* the method invokevirtual and invokeinterface run for a resolved method: the one the class of
+     * the receiver declares or inherits that overrides it, or else the default method it inherits
+     * from its interfaces. private methods are not overridden, and a method that is neither public
+     * nor protected is only overridden from within its own package. when there is no
+     * implementation, it is left to check_invoked_method to throw AbstractMethodError
+     * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-5.html#jvms-5.4.6
+     */
+    fn select_method(self: &RuntimeConst<'a>, receiver_class: &str, resolved_class: rc::Rc<JVMClass>, resolved: JVMMethod, name: &str, descriptor: &str) -> Result<(rc::Rc<JVMClass>, JVMMethod), JVMError> {
+        let resolved_flags = method_access_flags(&resolved);
+        if resolved_flags & ACC_PRIVATE != 0 {
+            return Ok((resolved_class, resolved));
+        }
+
+        let mut current = self.lookup_class(receiver_class);
+        while let Some(class) = current {
+            if let Some(method) = class.declared_method(name, descriptor) {
+                let flags = method_access_flags(method);
+                let overrides = flags & (ACC_PRIVATE | ACC_STATIC) == 0 &&
+                    (resolved_flags & (ACC_PUBLIC | ACC_PROTECTED) != 0 || package_name(&class.class) == package_name(&resolved_class.class));
+                if overrides {
+                    return Ok((class.clone(), method.clone()));
+                }
+            }
+
+            current = match &class.super_class {
+                Some(super_class) => self.lookup_class(super_class),
+                None => None,
+            };
+        }
+
+        let methods = self.maximally_specific_methods(receiver_class, name, descriptor);
+        let concrete: Vec<&(rc::Rc<JVMClass>, JVMMethod)> = methods.iter().filter(|(_, method)| method_access_flags(method) & ACC_ABSTRACT == 0).collect();
+        match concrete.len() {
+            0 => {
+                return Ok((resolved_class, resolved));
+            },
+            1 => {
+                return Ok(concrete[0].clone());
+            },
+            _ => {
+                let conflicting: Vec<String> = concrete.iter().map(|(interface, _)| format!("{}.{}", external_class_name(&interface.class), name)).collect();
+                let message = format!("Conflicting default methods: {}", conflicting.join(" "));
+                return Err(JVMError::exception("java/lang/IncompatibleClassChangeError", Some(message)));
+            }
+        }
+    }
+
+    /* the method invokespecial runs for a resolved method. a call to a method of a superclass
+     * other than a constructor, as in super.toString(), starts looking in the direct superclass
+     * of the current class rather than in the class the methodref names
+     * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-6.html#jvms-6.5.invokespecial
+     */
+    fn select_special_method(self: &RuntimeConst<'a>, current_class: &JVMClass, class_name: &str, resolved_class: rc::Rc<JVMClass>, resolved: JVMMethod, name: &str, descriptor: &str) -> (rc::Rc<JVMClass>, JVMMethod) {
+        let is_super_call = name != "<init>" && class_name != current_class.class &&
+            resolved_class.access_flags & ACC_INTERFACE == 0 && self.is_assignable(&current_class.class, class_name);
+        if !is_super_call {
+            return (resolved_class, resolved);
+        }
+
+        let mut current = current_class.super_class.as_ref().and_then(|super_class| self.lookup_class(super_class));
+        while let Some(class) = current {
+            if let Some(method) = class.declared_method(name, descriptor) {
+                return (class.clone(), method.clone());
+            }
+
+            current = match &class.super_class {
+                Some(super_class) => self.lookup_class(super_class),
+                None => None,
+            };
+        }
+
+        return (resolved_class, resolved);
+    }
+
+    /* find the class that declares an instance field: the class named by the fieldref or the
+     * closest superclass. a static field of the name found first makes the field unresolved
+     * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-5.html#jvms-5.4.3.2
+     */
+    fn resolve_instance_field(self: &RuntimeConst<'a>, class_name: &str, field_name: &str) -> Option<rc::Rc<JVMClass>> {
+        let mut current = self.lookup_class(class_name);
+        while let Some(class) = current {
+            if class.fields.borrow().contains_key(field_name) {
+                return None;
+            }
+            if class.instance_fields.contains_key(field_name) {
+                return Some(class);
+            }
+
+            current = match &class.super_class {
+                Some(super_class) => self.lookup_class(super_class),
+                None => None,
+            };
+        }
+
+        return None;
+    }
+
+    /* whether a reference of class `from` can be used where class `to` is expected, following the
+     * rules of checkcast https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-6.html#jvms-6.5.checkcast
+     */
+    fn is_assignable(self: &RuntimeConst<'a>, from: &str, to: &str) -> bool {
+        if from == to || to == "java/lang/Object" {
+            return true;
+        }
+
+        match (from.strip_prefix('['), to.strip_prefix('[')) {
+            (Some(from_component), Some(to_component)) => {
+                let is_reference = |component: &str| component.starts_with('L') || component.starts_with('[');
+                if is_reference(from_component) && is_reference(to_component) {
+                    match (component_class_of(from), component_class_of(to)) {
+                        (Some(from_component), Some(to_component)) => self.is_assignable(from_component, to_component),
+                        _ => false,
+                    }
+                } else {
+                    // arrays of primitives are only assignable to arrays of the same primitive
+                    from_component == to_component
+                }
+            },
+            // every array implements these two interfaces
+            (Some(_), None) => to == "java/lang/Cloneable" || to == "java/io/Serializable",
+            (None, Some(_)) => false,
+            (None, None) => {
+                match self.lookup_class(from) {
+                    Some(class) => class.is_subclass_of(self, to),
+                    None => false,
+                }
+            }
+        }
+    }
+}
+
+impl Frame {
+    fn as_ref(self: &mut Frame) -> &mut Frame {
+        return self
+    }
+
+    fn push_value(self: &mut Frame, value: RuntimeValue) {
+        self.stack.push(value);
+    }
+
+    fn pop_value(self: &mut Frame) -> Option<RuntimeValue> {
+        return self.stack.pop();
+    }
+
+    fn pop_value_force(self: &mut Frame) -> Result<RuntimeValue, JVMError> {
+        match self.stack.pop() {
+            Some(value) => {
+                return Ok(value);
+            },
+            None => {
+                return Err(JVMError::exception("java/lang/VerifyError", Some("Operand stack underflow".to_string())));
+            }
+        }
+    }
+
+    fn pop_int(self: &mut Frame) -> Result<i32, JVMError> {
+        match self.pop_value_force()? {
+            RuntimeValue::Int(value) => Ok(value),
+            value => Err(format!("expected an int on the stack but found {:?}", value).into()),
+        }
+    }
+
+    fn pop_long(self: &mut Frame) -> Result<i64, JVMError> {
+        match self.pop_value_force()? {
+            RuntimeValue::Long(value) => Ok(value),
+            value => Err(format!("expected a long on the stack but found {:?}", value).into()),
+        }
+    }
+
+    fn pop_float(self: &mut Frame) -> Result<f32, JVMError> {
+        match self.pop_value_force()? {
+            RuntimeValue::Float(value) => Ok(value),
+            value => Err(format!("expected a float on the stack but found {:?}", value).into()),
+        }
+    }
+
+    fn pop_double(self: &mut Frame) -> Result<f64, JVMError> {
+        match self.pop_value_force()? {
+            RuntimeValue::Double(value) => Ok(value),
+            value => Err(format!("expected a double on the stack but found {:?}", value).into()),
+        }
+    }
+
+    /* pop values totalling `words` words off the stack, where a long or double counts as two
+     * words. the values are returned in stack order, so the last element was the top of the stack
+     */
+    fn pop_words(self: &mut Frame, words: usize) -> Result<Vec<RuntimeValue>, JVMError> {
+        let mut values = Vec::new();
+        let mut count = 0;
+        while count < words {
+            let value = self.pop_value_force()?;
+            count += if value.is_category2() { 2 } else { 1 };
+            values.push(value);
+        }
+
+        if count != words {
+            return Err(format!("stack operation would split a category 2 value: {:?}", values.last()).into());
+        }
+
+        values.reverse();
+        return Ok(values);
+    }
+
+    fn push_values(self: &mut Frame, values: &[RuntimeValue]) {
+        for value in values {
+            self.push_value(value.clone());
+        }
+    }
+
+    /* the family of dup instructions copy the top `words` words of the stack and insert
+     * the copy below the `skip` words that are under them
+     */
+    fn dup_words(self: &mut Frame, words: usize, skip: usize) -> Result<(), JVMError> {
+        let top = self.pop_words(words)?;
+        let below = self.pop_words(skip)?;
+        self.push_values(&top);
+        self.push_values(&below);
+        self.push_values(&top);
+        return Ok(());
+    }
+
+    fn load_local(self: &Frame, index: usize) -> RuntimeValue {
+        return self.locals[index].clone();
+    }
+
+    fn store_local(self: &mut Frame, index: usize, value: RuntimeValue) {
+        if value.is_category2() {
+            // the second slot of a long or double cannot be used on its own
+            self.locals[index + 1] = RuntimeValue::Void;
+        }
+        self.locals[index] = value;
+    }
+}
+
+/* a method that could not be resolved, either because its class is missing or because
+ * neither the class nor its superclasses declare it
+ */
+fn unresolved_method(jvm: &RuntimeConst, class_name: &str, name: &str, descriptor: &MethodDescriptor) -> JVMError {
+    if let Err(error) = jvm.load_class(class_name) {
+        return error;
+    }
+
+    let message = format!("'{}'", method_external_name(Some(class_name), name, descriptor));
+    return JVMError::exception("java/lang/NoSuchMethodError", Some(message));
+}
+
+/* the errors for invoking a resolved method that cannot be run: invokestatic on an instance method
+ * or the other way around, abstract methods and native methods the jvm does not provide. `receiver`
+ * is the class of the object the method is invoked on, or None for invokestatic
+ * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-6.html#jvms-6.5.invokevirtual
+ */
+fn check_invoked_method(class: &JVMClass, method: &MethodInfo, name: &str, descriptor: &MethodDescriptor, receiver: Option<&str>) -> Result<(), JVMError> {
+    let method_name = method_external_name(Some(&class.class), name, descriptor);
+    let is_static = method.access_flags & ACC_STATIC != 0;
+    if receiver.is_none() && !is_static {
+        return Err(JVMError::exception("java/lang/IncompatibleClassChangeError", Some(format!("Expected static method '{}'", method_name))));
+    }
+
+    if receiver.is_some() && is_static {
+        return Err(JVMError::exception("java/lang/IncompatibleClassChangeError", Some(format!("Expecting non-static method '{}'", method_name))));
+    }
+
+    if method.access_flags & ACC_ABSTRACT != 0 {
+        let kind = if class.access_flags & ACC_INTERFACE != 0 { "interface" } else { "abstract class" };
+        let message = format!("Receiver class {} does not define or inherit an implementation of the resolved method 'abstract {}' of {} {}.",
+                              external_class_name(receiver.unwrap_or(&class.class)), method_external_name(None, name, descriptor), kind, external_class_name(&class.class));
+        return Err(JVMError::exception("java/lang/AbstractMethodError", Some(message)));
+    }
+
+    if method.access_flags & ACC_NATIVE != 0 {
+        return Err(JVMError::exception("java/lang/UnsatisfiedLinkError", Some(format!("'{}'", method_name))));
+    }
+
+    return Ok(());
+}
+
+fn invoke_static(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &RuntimeConst, method_index: usize) -> Result<Invocation, JVMError> {
+    // static methods of interfaces are invoked through an interface methodref
+    let interface_ref = matches!(constant_pool_lookup(constant_pool, method_index), Some(ConstantPoolEntry::InterfaceMethodref(..)));
     match constant_pool_lookup(constant_pool, method_index) {
-        Some(ConstantPoolEntry::Methodref(class_index, name_and_type_index)) => {
+        Some(ConstantPoolEntry::Methodref(class_index, name_and_type_index)) | Some(ConstantPoolEntry::InterfaceMethodref(class_index, name_and_type_index)) => {
             match constant_pool_lookup(constant_pool, *class_index as usize) {
                 Some(ConstantPoolEntry::Classref(class_index)) => {
                     match constant_pool_lookup(constant_pool, *class_index as usize) {
@@ -310,98 +1649,75 @@ fn invoke_special(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &Runtime
                             match constant_pool_lookup(constant_pool, *name_and_type_index as usize) {
                                 Some(ConstantPoolEntry::NameAndType{name_index, descriptor_index}) => {
                                     match constant_pool_lookup(constant_pool, *name_index as usize) {
-                                        Some(ConstantPoolEntry::Utf8(name)) => {
-                                            debug!("  method name={}", name);
+                                        Some(ConstantPoolEntry::Utf8(method_name)) => {
+                                            debug!("invoke static method {} on class {}", method_name, class_name);
 
-                                            debug!("  frame stack size {}", frame.stack.len());
                                             if let Some(descriptor) = lookup_utf8_constant(constant_pool, *descriptor_index as usize) {
-                                                debug!("  method descriptor={}", descriptor);
                                                 let method_descriptor = parse_method_descriptor(descriptor)?;
                                                 let mut locals = Vec::new();
-                                                for i in 0..method_descriptor.parameters.len() {
+                                                for _i in 0..method_descriptor.parameters.len() {
                                                     locals.push(frame.pop_value_force()?);
                                                 }
-                                                let object_arg = frame.pop_value_force()?;
+                                                locals.reverse();
 
-                                                match object_arg {
-                                                    RuntimeValue::Object(object) => {
-                                                        debug!("  popped object class '{}'", object.borrow().class);
-
-                                                        match jvm.lookup_class(class_name) {
-                                                            Some(class) => {
-                                                                match class.methods.get(name) {
-                                                                    Some(method) => {
-                                                                        locals.push(RuntimeValue::Object(object));
-                                                                        locals.reverse();
-
-                                                                        match method {
-                                                                            JVMMethod::Native(f) => {
-                                                                                debug!("invoke native method");
-                                                                                f(&locals.as_slice());
-                                                                                return Ok(());
-                                                                            },
-                                                                            JVMMethod::Bytecode(info) => {
-                                                                                debug!("invoke bytecode method '{}'", name);
-                                                                                let mut new_frame = create_frame(info)?;
-                                                                                new_frame.locals = locals;
-                                                                                do_execute_method(&info, constant_pool, &mut new_frame, jvm)?;
-                                                                                return Ok(());
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                    None => {
-                                                                        debug!("  Unknown method {}", name);
-                                                                    }
-                                                                }
+                                                match jvm.resolve_method_ref(class_name, method_name, descriptor, interface_ref)? {
+                                                    Some((class, method)) => {
+                                                        jvm.initialize_class(&class)?;
+                                                        match method {
+                                                            JVMMethod::Native(f) => {
+                                                                debug!("invoke native method");
+                                                                return Ok(Invocation::Completed(f(jvm, locals.as_slice())?));
                                                             },
-                                                            _ => {
-                                                                debug!("could not find class with name {}", class_name);
+                                                            JVMMethod::Invoker(f) => {
+                                                                return f(jvm, locals.as_slice());
+                                                            },
+                                                            JVMMethod::Bytecode(info) => {
+                                                                debug!("invoke bytecode method stack size {}", frame.stack.len());
+                                                                check_invoked_method(&class, &info, method_name, &method_descriptor, None)?;
+
+                                                                return Ok(Invocation::Call(create_frame_with_arguments(class, info, locals)?));
                                                             }
                                                         }
                                                     },
-                                                    value => {
-                                                        return Err(format!("wrong value type on stack: {:?}", value));
+                                                    None => {
+                                                        return Err(unresolved_method(jvm, class_name, method_name, &method_descriptor));
                                                     }
                                                 }
                                             } else {
-                                                return Err(format!("could not find method descriptor {}", *descriptor_index));
+                                                return Err(format!("could not find descriptor {}", *descriptor_index).into())
                                             }
                                         },
                                         _ => {
-                                            debug!("  Unknown name index {}", *name_index);
+                                            return Err("Invalid name and type".to_string().into());
                                         }
                                     }
                                 },
                                 _ => {
-                                    debug!("Unknown name and type index {}", *name_and_type_index);
+                                    return Err("Invalid name and type".to_string().into());
                                 }
                             }
-
                         },
                         _ => {
-                            debug!("Unknown class index {}", class_index);
+                            return Err("Invalid classref".to_string().into());
                         }
                     }
                 },
                 _ => {
-                    debug!("Unknown class index {}", class_index);
+                    return Err("Invalid classref".to_string().into());
                 }
             }
-        }
+        },
         _ => {
-            debug!("Unknown method index {}", method_index);
+            return Err("Invalid methodref".to_string().into());
         }
     }
-
-    return Err(format!("unable to find method index {}", method_index).to_string())
-
 }
 
-fn invoke_virtual(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &RuntimeConst, method_index: usize) -> Result<RuntimeValue, String> {
-    // FIXME: handle polymorphic methods: https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-2.html#jvms-2.9.3
-
+fn invoke_special(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &RuntimeConst, method_index: usize) -> Result<Invocation, JVMError> {
+    // private and superinterface methods of interfaces are invoked through an interface methodref
+    let interface_ref = matches!(constant_pool_lookup(constant_pool, method_index), Some(ConstantPoolEntry::InterfaceMethodref(..)));
     match constant_pool_lookup(constant_pool, method_index) {
-        Some(ConstantPoolEntry::Methodref(class_index, name_and_type_index)) => {
+        Some(ConstantPoolEntry::Methodref(class_index, name_and_type_index)) | Some(ConstantPoolEntry::InterfaceMethodref(class_index, name_and_type_index)) => {
             match constant_pool_lookup(constant_pool, *class_index as usize) {
                 Some(ConstantPoolEntry::Classref(class_index)) => {
                     match constant_pool_lookup(constant_pool, *class_index as usize) {
@@ -419,63 +1735,51 @@ fn invoke_virtual(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &Runtime
                                                 debug!("  method descriptor={}", descriptor);
                                                 let method_descriptor = parse_method_descriptor(descriptor)?;
                                                 let mut locals = Vec::new();
-                                                for i in 0..method_descriptor.parameters.len() {
+                                                for _i in 0..method_descriptor.parameters.len() {
                                                     locals.push(frame.pop_value_force()?);
                                                 }
+                                                let object_arg = frame.pop_value_force()?;
 
-                                                match frame.pop_value() {
-                                                    Some(RuntimeValue::Object(object)) => {
-                                                        debug!("  popped object class '{}'", object.borrow().class);
-
-                                                        match jvm.lookup_class(class_name) {
-                                                            Some(class) => {
+                                                match object_arg {
+                                                    receiver @ (RuntimeValue::Object(_) | RuntimeValue::String(_)) => {
+                                                        let receiver_class = receiver.class_name().unwrap_or_default();
+                                                        debug!("  popped object class '{}'", receiver_class);
 
-                                                                /* push `this' pointer */
-                                                                locals.push(RuntimeValue::Object(object));
+                                                        match jvm.resolve_method_ref(class_name, name, descriptor, interface_ref)? {
+                                                            Some((class, method)) => {
+                                                                let (class, method) = jvm.select_special_method(&frame.class, class_name, class, method, name, descriptor);
+                                                                locals.push(receiver);
                                                                 locals.reverse();
 
-                                                                match class.methods.get(name) {
-                                                                    Some(method) => {
-                                                                        match method {
-                                                                            JVMMethod::Native(f) => {
-                                                                                debug!("invoke native method");
-                                                                                return Ok(f(&locals.as_slice()));
-                                                                            },
-                                                                            JVMMethod::Bytecode(info) => {
-                                                                                debug!("invoke bytecode method '{}'", name);
-                                                                                let mut new_frame = create_frame(info)?;
-
-                                                                                // fill in the rest of the locals array with 0
-                                                                                if let Some(AttributeKind::Code { max_stack: _, max_locals, code: _, exception_table: _, attributes: _ }) = lookup_code_attribute(info) {
-                                                                                    for _i in 0..((*max_locals as usize) - locals.len()) {
-                                                                                        locals.push(RuntimeValue::Int(0));
-                                                                                    }
-                                                                                }
-
-                                                                                new_frame.locals = locals;
-                                                                                return do_execute_method(&info, constant_pool, &mut new_frame, jvm)
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                    None => {
-                                                                        debug!("  Unknown method {}", name);
+                                                                match method {
+                                                                    JVMMethod::Native(f) => {
+                                                                        debug!("invoke native method");
+                                                                        return Ok(Invocation::Completed(f(jvm, &locals.as_slice())?));
+                                                                    },
+                                                                    JVMMethod::Invoker(f) => {
+                                                                        return f(jvm, locals.as_slice());
+                                                                    },
+                                                                    JVMMethod::Bytecode(info) => {
+                                                                        debug!("invoke bytecode method '{}'", name);
+                                                                        check_invoked_method(&class, &info, name, &method_descriptor, Some(&receiver_class))?;
+                                                                        return Ok(Invocation::Call(create_frame_with_arguments(class, info, locals)?));
                                                                     }
                                                                 }
                                                             },
-                                                            _ => {
-                                                                debug!("could not find class with name {}", class_name);
+                                                            None => {
+                                                                return Err(unresolved_method(jvm, class_name, name, &method_descriptor));
                                                             }
                                                         }
                                                     },
-                                                    None => {
-                                                        return Err("no value on stack".to_string());
-                                                    }
-                                                    Some(value) => {
-                                                        return Err(format!("wrong value type on stack: {:?}", value));
+                                                    RuntimeValue::Null => {
+                                                        return Err(JVMError::null_pointer());
+                                                    },
+                                                    value => {
+                                                        return Err(format!("wrong value type on stack: {:?}", value).into());
                                                     }
                                                 }
                                             } else {
-                                                return Err(format!("could not find method descriptor {}", *descriptor_index));
+                                                return Err(format!("could not find method descriptor {}", *descriptor_index).into());
                                             }
                                         },
                                         _ => {
@@ -504,720 +1808,5665 @@ fn invoke_virtual(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &Runtime
         }
     }
 
-    return Err(format!("unable to find method index {}", method_index).to_string())
-}
+    return Err(format!("unable to find method index {}", method_index).to_string().into())
 
-fn op_getstatic(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &RuntimeConst, field_index: usize) -> Result<(), String> {
-    match constant_pool_lookup(constant_pool, field_index) {
-        Some(ConstantPoolEntry::Fieldref{class_index, name_and_type_index}) => {
-            debug!("  class={}", class_index);
-            debug!("  name_and_type={}", name_and_type_index);
+}
 
+fn invoke_virtual(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &RuntimeConst, method_index: usize) -> Result<Invocation, JVMError> {
+    match constant_pool_lookup(constant_pool, method_index) {
+        Some(ConstantPoolEntry::Methodref(class_index, name_and_type_index)) => {
             match constant_pool_lookup(constant_pool, *class_index as usize) {
-                Some(ConstantPoolEntry::Classref(index)) => {
-                    debug!("  classref={}", index);
-                    match lookup_utf8_constant(constant_pool, *index as usize) {
-                        Some(class_name) => {
-                            debug!("  class_name={}", class_name);
+                Some(ConstantPoolEntry::Classref(class_index)) => {
+                    match constant_pool_lookup(constant_pool, *class_index as usize) {
+                        Some(ConstantPoolEntry::Utf8(class_name)) => {
+                            debug!("Invoke method on class {}", class_name);
 
                             match constant_pool_lookup(constant_pool, *name_and_type_index as usize) {
                                 Some(ConstantPoolEntry::NameAndType{name_index, descriptor_index}) => {
-                                    debug!("  name_index={}", name_index);
-                                    debug!("  descriptor_index={}", descriptor_index);
-
-                                    match lookup_utf8_constant(constant_pool, *name_index as usize) {
-                                        Some(name) => {
-                                            debug!("  name={}", name);
-
-                                            match jvm.lookup_class(class_name) {
-                                                Some(class) => {
-                                                    match class.fields.get(name) {
-                                                        Some(value) => {
-                                                            // debug!(" pushing value");
-                                                            frame.push_value(value.clone());
-                                                            return Ok(());
-                                                        },
-                                                        None => {
-                                                            debug!("  Unknown field {}", name);
-                                                        }
-                                                    }
-                                                },
-                                                None => {
-                                                    debug!("  Unknown class {}", class_name);
-                                                }
-                                            }
-                                        },
-                                        None => {
-                                            debug!("  Unknown name index {}", *name_index);
-                                        }
-                                    }
+                                    match constant_pool_lookup(constant_pool, *name_index as usize) {
+                                        Some(ConstantPoolEntry::Utf8(name)) => {
+                                            debug!("  method name={}", name);
+
+                                            debug!("  frame stack size {}", frame.stack.len());
+                                            if let Some(descriptor) = lookup_utf8_constant(constant_pool, *descriptor_index as usize) {
+                                                debug!("  method descriptor={}", descriptor);
+                                                let method_descriptor = parse_method_descriptor(descriptor)?;
+                                                let mut locals = Vec::new();
+                                                for _i in 0..method_descriptor.parameters.len() {
+                                                    locals.push(frame.pop_value_force()?);
+                                                }
+
+                                                if is_signature_polymorphic(class_name, name) {
+                                                    locals.reverse();
+                                                    let handle = frame.pop_value_force()?;
+                                                    return invoke_polymorphic(jvm, handle, name, descriptor, locals);
+                                                }
 
-                                    match lookup_utf8_constant(constant_pool, *descriptor_index as usize) {
-                                        Some(descriptor) => {
-                                            debug!("  descriptor={}", descriptor);
+                                                match frame.pop_value() {
+                                                    Some(receiver @ (RuntimeValue::Object(_) | RuntimeValue::String(_) | RuntimeValue::Array(_))) => {
+                                                        let receiver_class = receiver.method_class().unwrap_or_default();
+                                                        debug!("  popped object class '{}'", receiver_class);
+
+                                                        // javac names the array type when it calls clone on an array
+                                                        let class_name = if class_name.starts_with('[') { "java/lang/Object" } else { class_name };
+                                                        jvm.expect_class_kind(class_name, false)?;
+                                                        match jvm.resolve_method_ref(class_name, name, descriptor, false)? {
+                                                            Some((class, method)) => {
+                                                                let (class, method) = jvm.select_method(&receiver_class, class, method, name, descriptor)?;
+
+                                                                /* push `this' pointer */
+                                                                locals.push(receiver);
+                                                                locals.reverse();
+
+                                                                match method {
+                                                                    JVMMethod::Native(f) => {
+                                                                        debug!("invoke native method");
+                                                                        return Ok(Invocation::Completed(f(jvm, &locals.as_slice())?));
+                                                                    },
+                                                                    JVMMethod::Invoker(f) => {
+                                                                        return f(jvm, locals.as_slice());
+                                                                    },
+                                                                    JVMMethod::Bytecode(info) => {
+                                                                        debug!("invoke bytecode method '{}'", name);
+                                                                        check_invoked_method(&class, &info, name, &method_descriptor, Some(&receiver_class))?;
+                                                                        return Ok(Invocation::Call(create_frame_with_arguments(class, info, locals)?));
+                                                                    }
+                                                                }
+                                                            },
+                                                            None => {
+                                                                return Err(unresolved_method(jvm, class_name, name, &method_descriptor));
+                                                            }
+                                                        }
+                                                    },
+                                                    None => {
+                                                        return Err("no value on stack".to_string().into());
+                                                    }
+                                                    Some(RuntimeValue::Null) => {
+                                                        return Err(JVMError::null_pointer());
+                                                    }
+                                                    Some(value) => {
+                                                        return Err(format!("wrong value type on stack: {:?}", value).into());
+                                                    }
+                                                }
+                                            } else {
+                                                return Err(format!("could not find method descriptor {}", *descriptor_index).into());
+                                            }
                                         },
-                                        None => {
-                                            debug!("  Unknown descriptor index {}", *descriptor_index);
+                                        _ => {
+                                            debug!("  Unknown name index {}", *name_index);
                                         }
                                     }
-
                                 },
                                 _ => {
-                                    debug!("  Unknown name and type index {}", *name_and_type_index);
+                                    debug!("Unknown name and type index {}", *name_and_type_index);
                                 }
                             }
 
                         },
                         _ => {
-                            debug!("  Unknown classref {}", *index);
+                            debug!("Unknown class index {}", class_index);
                         }
                     }
                 },
                 _ => {
-                    debug!("  Unknown classref {}", class_index);
+                    debug!("Unknown class index {}", class_index);
                 }
             }
-
-        },
-        Some(entry) => {
-            debug!("  {}", entry.name());
-        },
-        None => {
-            debug!("  Unknown constant pool entry {}", field_index);
+        }
+        _ => {
+            debug!("Unknown method index {}", method_index);
         }
     }
 
-    return Err(format!("error in getstatic with index {}", field_index).to_string());
+    return Err(format!("unable to find method index {}", method_index).to_string().into())
 }
 
-fn push_runtime_constant(constant_pool: &ConstantPool, frame: &mut Frame, index: usize) -> Result<(), String> {
-    if index > 0 && index < constant_pool.len() {
-        match constant_pool_lookup(constant_pool, index) {
-            Some(ConstantPoolEntry::Utf8(name)) => {
-                debug!("Pushing constant utf8 {}", name);
-            },
-            Some(ConstantPoolEntry::Stringref(string_index)) => {
-                debug!("Pushing constant string {}", string_index);
-                match constant_pool_lookup(constant_pool, *string_index as usize) {
-                    Some(ConstantPoolEntry::Utf8(name)) => {
-                        debug!("Pushing constant utf8 '{}'", name);
-                        frame.push_value(RuntimeValue::String(name.clone()));
-                        return Ok(());
-                    },
-                    None => {
-                        debug!("no such index {}", string_index);
-                    }
-                    _ => {
-                        debug!("constant pool index {} is invalid", string_index);
+/* invoke a method of an interface on an object, which has to implement the interface
+ * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-6.html#jvms-6.5.invokeinterface
+ */
+fn invoke_interface(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &RuntimeConst, method_index: usize) -> Result<Invocation, JVMError> {
+    let (class_name, name, descriptor) = match constant_pool_lookup(constant_pool, method_index) {
+        Some(ConstantPoolEntry::InterfaceMethodref(class_index, name_and_type_index)) => {
+            let class_name = lookup_class_name(constant_pool, *class_index as usize)?;
+            match constant_pool_lookup(constant_pool, *name_and_type_index as usize) {
+                Some(ConstantPoolEntry::NameAndType{name_index, descriptor_index}) => {
+                    match (lookup_utf8_constant(constant_pool, *name_index as usize), lookup_utf8_constant(constant_pool, *descriptor_index as usize)) {
+                        (Some(name), Some(descriptor)) => (class_name, name, descriptor),
+                        _ => return Err(format!("invalid name and type index {}", name_and_type_index).into()),
                     }
-                }
-            },
-            _ => {
-                debug!("ERROR: unhandled constant {}", &constant_pool[index-1].name());
+                },
+                _ => return Err(format!("unknown name and type index {}", name_and_type_index).into()),
             }
-        }
-    } else {
-        return Err(format!("constant index {} out of range", index).to_string());
+        },
+        _ => return Err(format!("unknown interface methodref {}", method_index).into()),
+    };
+    debug!("invoke interface method {}.{}{}", class_name, name, descriptor);
+
+    let method_descriptor = parse_method_descriptor(descriptor)?;
+    let mut locals = Vec::new();
+    for _i in 0..method_descriptor.parameters.len() {
+        locals.push(frame.pop_value_force()?);
+    }
+
+    jvm.expect_class_kind(class_name, true)?;
+    let (class, method) = match jvm.resolve_method_ref(class_name, name, descriptor, true)? {
+        Some(resolved) => resolved,
+        None => return Err(unresolved_method(jvm, class_name, name, &method_descriptor)),
+    };
+
+    let receiver = match frame.pop_value_force()? {
+        receiver @ (RuntimeValue::Object(_) | RuntimeValue::String(_)) => receiver,
+        RuntimeValue::Null => return Err(JVMError::null_pointer()),
+        value => return Err(format!("wrong value type on stack: {:?}", value).into()),
+    };
+    let receiver_class = receiver.class_name().unwrap_or_default();
+    if !jvm.is_assignable(&receiver_class, class_name) {
+        let message = format!("Class {} does not implement the requested interface {}", external_class_name(&receiver_class), external_class_name(class_name));
+        return Err(JVMError::exception("java/lang/IncompatibleClassChangeError", Some(message)));
     }
 
-    return Err("error with push constant".to_string());
+    let (class, method) = jvm.select_method(&receiver_class, class, method, name, descriptor)?;
+    locals.push(receiver);
+    locals.reverse();
+    match method {
+        JVMMethod::Native(f) => {
+            return Ok(Invocation::Completed(f(jvm, locals.as_slice())?));
+        },
+        JVMMethod::Invoker(f) => {
+            return f(jvm, locals.as_slice());
+        },
+        JVMMethod::Bytecode(info) => {
+            check_invoked_method(&class, &info, name, &method_descriptor, Some(&receiver_class))?;
+            return Ok(Invocation::Call(create_frame_with_arguments(class, info, locals)?));
+        }
+    }
 }
 
-fn do_iop(frame: &mut Frame, op: fn(i64, i64) -> i64) -> Result<RuntimeValue, String> {
-    let value1 = frame.pop_value_force()?;
-    let value2 = frame.pop_value_force()?;
-    match value1 {
-        RuntimeValue::Int(i1) => {
-            match value2 {
-                RuntimeValue::Int(i2) => {
-                    debug!("  iop {} {} = {}", i1, i2, op(i1, i2));
-                    return Ok(RuntimeValue::Int(op(i1, i2)));
+// the name and descriptor of a NameAndType constant
+fn lookup_name_and_type(constant_pool: &ConstantPool, index: usize) -> Result<(&str, &str), JVMError> {
+    match constant_pool_lookup(constant_pool, index) {
+        Some(ConstantPoolEntry::NameAndType{name_index, descriptor_index}) => {
+            match (lookup_utf8_constant(constant_pool, *name_index as usize), lookup_utf8_constant(constant_pool, *descriptor_index as usize)) {
+                (Some(name), Some(descriptor)) => {
+                    return Ok((name, descriptor));
                 },
                 _ => {
-                    return Err("invalid value type for integer op".to_string());
+                    return Err(format!("invalid name and type index {}", index).into());
                 }
             }
         },
         _ => {
-            return Err("invalid value type for integer op".to_string());
+            return Err(format!("unknown name and type index {}", index).into());
         }
     }
 }
 
-fn create_new_object(constant_pool: &ConstantPool, jvm: &RuntimeConst, index: usize) -> Result<RuntimeValue, String> {
-    match constant_pool_lookup(constant_pool, index) {
-        Some(ConstantPoolEntry::Classref(class_index)) => {
-            match constant_pool_lookup(constant_pool, *class_index as usize) {
-                Some(ConstantPoolEntry::Utf8(class_name)) => {
-                    debug!("  class_name={}", class_name);
-                    match jvm.lookup_class(class_name) {
-                        Some(class) => {
-                            return Ok(RuntimeValue::Object(rc::Rc::new(cell::RefCell::new(class.create_object()))))
-                        },
-                        None => {
-                            return Err(format!("no such class named '{}'", class_name).to_string());
-                        }
-                    }
-                },
-                _ => {
-                    debug!("  Unknown classref {}", *class_index);
-                }
-            }
+/* invoke the target of a call site, linking the call site by running its bootstrap method the
+ * first time the instruction runs
+ * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-6.html#jvms-6.5.invokedynamic
+ */
+fn invoke_dynamic(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &RuntimeConst, index: usize, pc: usize) -> Result<Invocation, JVMError> {
+    let (bootstrap_index, name, descriptor) = match constant_pool_lookup(constant_pool, index) {
+        Some(ConstantPoolEntry::InvokeDynamic{bootstrap_method_attr_index, name_and_type_index}) => {
+            let (name, descriptor) = lookup_name_and_type(constant_pool, *name_and_type_index as usize)?;
+            (*bootstrap_method_attr_index, name, descriptor)
         },
+        _ => {
+            return Err(format!("unknown invokedynamic constant {}", index).into());
+        }
+    };
+    debug!("invoke dynamic {}{}", name, descriptor);
+
+    let method_descriptor = parse_method_descriptor(descriptor)?;
+    let mut arguments = Vec::new();
+    for _i in 0..method_descriptor.parameters.len() {
+        arguments.push(frame.pop_value_force()?);
+    }
+    arguments.reverse();
+
+    let key = (frame.method.name_index, frame.method.descriptor_index, pc);
+    let linked = frame.class.call_sites.borrow().get(&key).cloned();
+    let target = match linked {
+        Some(target) => target,
         None => {
-            return Err(format!("unknown classref {}", index))
+            let target = link_call_site(jvm, &frame.class, bootstrap_index, name, descriptor)?;
+            frame.class.call_sites.borrow_mut().insert(key, target.clone());
+            target
         }
-        _ => {
-            return Err(format!("index {} was not a classref", index))
+    };
+
+    return method_handle_invocation(jvm, &target, arguments);
+}
+
+/* link a call site by calling its bootstrap method with the name and type of the call site. it
+ * returns a CallSite whose target the instruction invokes from then on
+ */
+fn link_call_site(jvm: &RuntimeConst, class: &rc::Rc<JVMClass>, bootstrap_index: u16, name: &str, descriptor: &str) -> Result<RuntimeValue, JVMError> {
+    let call_site = match invoke_bootstrap_method(jvm, class, bootstrap_index, name, new_method_type(descriptor)) {
+        Ok(call_site) => call_site,
+        Err(error) => return Err(bootstrap_method_error(jvm, error, "CallSite bootstrap method initialization exception")),
+    };
+
+    if let RuntimeValue::Object(call_site) = &call_site {
+        if jvm.is_assignable(&call_site.borrow().class, "java/lang/invoke/CallSite") {
+            return Ok(call_site.borrow().fields.get("target").cloned().unwrap_or(RuntimeValue::Null));
         }
     }
+    return Err(JVMError::exception("java/lang/BootstrapMethodError", Some("CallSite bootstrap method initialization exception".to_string())));
+}
 
-    return Err("error with create object".to_string());
+/* an exception other than an error thrown while resolving a call site or dynamic constant is
+ * wrapped in a BootstrapMethodError
+ */
+fn bootstrap_method_error(jvm: &RuntimeConst, error: JVMError, message: &str) -> JVMError {
+    match error {
+        JVMError::Exception(exception) => {
+            let exception_class = exception.borrow().class.clone();
+            if jvm.is_assignable(&exception_class, "java/lang/Error") {
+                return JVMError::Exception(exception);
+            }
+            let error = JVMError::exception("java/lang/BootstrapMethodError", Some(message.to_string()));
+            if let JVMError::Exception(object) = &error {
+                object.borrow_mut().fields.insert("cause".to_string(), RuntimeValue::Object(exception));
+            }
+            return error;
+        },
+        error => {
+            return error;
+        }
+    }
 }
 
-fn lookup_code_attribute(method: &MethodInfo) -> Option<&AttributeKind> {
-    for i in 0..method.attributes.len() {
-        match &method.attributes[i] {
-            AttributeKind::Code { max_stack: _, max_locals: _, code: _, exception_table: _, attributes: _ } => {
-                return Some(&method.attributes[i]);
-            },
-            _ => {
+/* call a bootstrap method of a class with a lookup on the class, the name and type of what it
+ * computes, a MethodType for a call site or a Class for a constant, and its static arguments
+ * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-5.html#jvms-5.4.3.6
+ */
+fn invoke_bootstrap_method(jvm: &RuntimeConst, class: &rc::Rc<JVMClass>, bootstrap_index: u16, name: &str, kind: RuntimeValue) -> Result<RuntimeValue, JVMError> {
+    let bootstrap = match class.bootstrap_methods.get(bootstrap_index as usize) {
+        Some(bootstrap) => bootstrap,
+        None => return Err(format!("no bootstrap method {} in class {}", bootstrap_index, class.class).into()),
+    };
+
+    let handle = method_handle_constant(&class.constant_pool, bootstrap.bootstrap_method_ref as usize)?;
+    let mut arguments = vec![new_lookup(&class.class), jvm.intern_str(name), kind];
+    for argument in bootstrap.bootstrap_arguments.iter() {
+        arguments.push(loadable_constant(jvm, class, *argument as usize)?);
+    }
+
+    /* a bootstrap method whose last parameter is an array, such as altMetafactory, gets the
+     * static arguments that do not fit its other parameters collected into that array. they are
+     * not boxed, since the jvm has no wrapper classes yet
+     */
+    let parameters = parse_method_descriptor(&string_field(&handle, "descriptor")?)?.parameters;
+    if let Some(Descriptor::Array(_)) = parameters.last() {
+        let fixed = parameters.len() + if int_field(&handle, "referenceKind")? == REF_INVOKE_STATIC { 0 } else { 1 } - 1;
+        if arguments.len() >= fixed && !(arguments.len() == fixed + 1 && matches!(arguments.last(), Some(RuntimeValue::Array(_)))) {
+            let rest = arguments.split_off(fixed);
+            let mut array = JVMArray::new("[Ljava/lang/Object;", rest.len() as i32)?;
+            for (index, value) in rest.into_iter().enumerate() {
+                array.store(index as i32, value)?;
             }
+            arguments.push(RuntimeValue::Array(rc::Rc::new(cell::RefCell::new(array))));
         }
     }
 
-    return None;
+    return invoke_method_handle(jvm, &handle, arguments);
 }
 
-fn putfield(constant_pool: &ConstantPool, jvm: &RuntimeConst, field_index: usize, object: RuntimeValue, field_value: RuntimeValue) -> Result<(), String> {
-    match constant_pool_lookup(constant_pool, field_index) {
-        Some(ConstantPoolEntry::Fieldref{class_index, name_and_type_index}) => {
-            match constant_pool_lookup(constant_pool, *name_and_type_index as usize) {
-                Some(ConstantPoolEntry::NameAndType{name_index, descriptor_index}) => {
-                    match lookup_utf8_constant(constant_pool, *name_index as usize) {
-                        Some(name) => {
-                            match object {
-                                RuntimeValue::Object(object) => {
-                                    debug!("  set field {}.{} = {:?}", object.borrow().class, name, field_value);
-                                    object.borrow_mut().fields.insert(name.to_string(), field_value);
-                                    return Ok(());
-                                },
-                                _ => {
-                                    return Err(format!("objectref was not an object: {:?}", object))
-                                }
-                            }
-                        },
-                        _ => {
-                            return Err(format!("unknown name index {}", name_index))
-                        }
-                    }
-                },
-                _ => {
-                    return Err(format!("unknown name and type index {}", name_and_type_index))
+/* the value of a dynamically computed constant, which its bootstrap method computes the first
+ * time it is loaded. a constant that needs itself to be computed, as a static argument of its
+ * bootstrap method, cannot be resolved, which hotspot finds out by overflowing the stack
+ */
+fn resolve_dynamic_constant(jvm: &RuntimeConst, class: &rc::Rc<JVMClass>, index: usize) -> Result<RuntimeValue, JVMError> {
+    match class.dynamic_constants.borrow().get(&index) {
+        Some(DynamicConstant::Resolved(value)) => {
+            return Ok(value.clone());
+        },
+        Some(DynamicConstant::Resolving) => {
+            return Err(JVMError::exception("java/lang/StackOverflowError", None));
+        },
+        Some(DynamicConstant::Failed{class: error_class, message, cause}) => {
+            let error = JVMError::exception(error_class, None);
+            if let JVMError::Exception(object) = &error {
+                let mut object = object.borrow_mut();
+                object.fields.insert("detailMessage".to_string(), message.clone());
+                if let Some((cause_class, cause_message)) = cause {
+                    let mut fields = HashMap::new();
+                    fields.insert("detailMessage".to_string(), cause_message.clone());
+                    fields.insert("cause".to_string(), RuntimeValue::Null);
+                    fields.insert("stackTrace".to_string(), capture_stack_trace(jvm, cause_class));
+                    object.fields.insert("cause".to_string(), RuntimeValue::Object(rc::Rc::new(cell::RefCell::new(JVMObject{
+                        class: cause_class.clone(),
+                        fields: fields,
+                    }))));
                 }
             }
+            return Err(error);
         },
-        _ => {
-            return Err(format!("unknown fieldref {}", field_index))
+        None => {
         }
     }
-}
 
-fn getfield(constant_pool: &ConstantPool, jvm: &RuntimeConst, field_index: usize, object: RuntimeValue) -> Result<RuntimeValue, String> {
-    match constant_pool_lookup(constant_pool, field_index) {
-        Some(ConstantPoolEntry::Fieldref{class_index, name_and_type_index}) => {
-            match constant_pool_lookup(constant_pool, *name_and_type_index as usize) {
-                Some(ConstantPoolEntry::NameAndType{name_index, descriptor_index}) => {
-                    match lookup_utf8_constant(constant_pool, *name_index as usize) {
-                        Some(name) => {
-                            match object {
-                                RuntimeValue::Object(object) => {
-                                    if let Some(value) = object.borrow().fields.get(name) {
-                                        return Ok(value.clone());
-                                    } else {
-                                        return Err(format!("no such field '{}' in class '{}'", name, object.borrow().class));
-                                    }
-                                },
-                                _ => {
-                                    return Err(format!("objectref was not an object: {:?}", object))
-                                }
-                            }
-                        },
-                        _ => {
-                            return Err(format!("unknown name index {}", name_index))
-                        }
-                    }
-                },
-                _ => {
-                    return Err(format!("unknown name and type index {}", name_and_type_index))
-                }
-            }
+    let (bootstrap_index, name, descriptor) = match constant_pool_lookup(&class.constant_pool, index) {
+        Some(ConstantPoolEntry::Dynamic{bootstrap_method_attr_index, name_and_type_index}) => {
+            let (name, descriptor) = lookup_name_and_type(&class.constant_pool, *name_and_type_index as usize)?;
+            (*bootstrap_method_attr_index, name, descriptor)
         },
         _ => {
-            return Err(format!("unknown fieldref {}", field_index))
+            return Err(format!("unknown dynamic constant {}", index).into());
+        }
+    };
+    debug!("resolve dynamic constant {}:{}", name, descriptor);
+    let kind = parse_type_descriptor(descriptor)?;
+
+    class.dynamic_constants.borrow_mut().insert(index, DynamicConstant::Resolving);
+    let result = invoke_bootstrap_method(jvm, class, bootstrap_index, name, jvm.class_object(&type_class_name(descriptor)))
+        .and_then(|value| convert_argument(jvm, value, &kind))
+        .map_err(|error| bootstrap_method_error(jvm, error, "bootstrap method initialization exception"));
+
+    match result {
+        Ok(value) => {
+            class.dynamic_constants.borrow_mut().insert(index, DynamicConstant::Resolved(value.clone()));
+            return Ok(value);
+        },
+        Err(JVMError::Exception(exception)) if jvm.is_assignable(&exception.borrow().class, "java/lang/LinkageError") => {
+            let failure = {
+                let exception = exception.borrow();
+                let cause = match exception.fields.get("cause") {
+                    Some(RuntimeValue::Object(cause)) => {
+                        let cause = cause.borrow();
+                        Some((cause.class.clone(), cause.fields.get("detailMessage").cloned().unwrap_or(RuntimeValue::Null)))
+                    },
+                    _ => None,
+                };
+                DynamicConstant::Failed{
+                    class: exception.class.clone(),
+                    message: exception.fields.get("detailMessage").cloned().unwrap_or(RuntimeValue::Null),
+                    cause: cause,
+                }
+            };
+            class.dynamic_constants.borrow_mut().insert(index, failure);
+            return Err(JVMError::Exception(exception));
+        },
+        Err(error) => {
+            class.dynamic_constants.borrow_mut().remove(&index);
+            return Err(error);
         }
     }
 }
 
-fn do_icompare(frame: &mut Frame, pc: usize, offset: i16, compare: fn(i64, i64) -> bool) -> Result<usize, String>{
-    let value2 = frame.pop_value_force()?;
-    let value1 = frame.pop_value_force()?;
-    match (value1, value2) {
-        (RuntimeValue::Int(i1), RuntimeValue::Int(i2)) => {
-            if compare(i1, i2) {
-                return Ok((pc as i16 + offset) as usize);
+/* the name of the class a field descriptor stands for, as Class.getName would give it but with
+ * slashes, such as int for I
+ */
+fn type_class_name(descriptor: &str) -> String {
+    match descriptor {
+        "B" => "byte".to_string(),
+        "C" => "char".to_string(),
+        "D" => "double".to_string(),
+        "F" => "float".to_string(),
+        "I" => "int".to_string(),
+        "J" => "long".to_string(),
+        "S" => "short".to_string(),
+        "Z" => "boolean".to_string(),
+        "V" => "void".to_string(),
+        _ => match descriptor.strip_prefix('L').and_then(|name| name.strip_suffix(';')) {
+            Some(name) => name.to_string(),
+            None => descriptor.to_string(),
+        },
+    }
+}
+
+/* the value of a loadable constant, as ldc pushes it and a bootstrap method gets it for a static
+ * argument
+ * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-4.html#jvms-4.4
+ */
+fn loadable_constant(jvm: &RuntimeConst, class: &rc::Rc<JVMClass>, index: usize) -> Result<RuntimeValue, JVMError> {
+    let constant_pool = &class.constant_pool;
+    match constant_pool_lookup(constant_pool, index) {
+        Some(ConstantPoolEntry::Integer(value)) => Ok(RuntimeValue::Int(*value)),
+        Some(ConstantPoolEntry::Long(value)) => Ok(RuntimeValue::Long(*value)),
+        Some(ConstantPoolEntry::Float(value)) => Ok(RuntimeValue::Float(*value)),
+        Some(ConstantPoolEntry::Double(value)) => Ok(RuntimeValue::Double(*value)),
+        Some(ConstantPoolEntry::Stringref(string_index)) => {
+            match lookup_string_constant(constant_pool, *string_index as usize) {
+                Some(string) => Ok(jvm.intern_contents(string)),
+                None => Err(format!("invalid string constant {}", string_index).into()),
             }
-        }
-        _ => {
-            return Err("invalid compare of non-int".to_string());
-        }
+        },
+        Some(ConstantPoolEntry::Classref(_)) => Ok(jvm.class_object(lookup_class_name(constant_pool, index)?)),
+        Some(ConstantPoolEntry::MethodHandle{..}) => method_handle_constant(constant_pool, index),
+        Some(ConstantPoolEntry::MethodType{descriptor_index}) => {
+            match lookup_utf8_constant(constant_pool, *descriptor_index as usize) {
+                Some(descriptor) => Ok(new_method_type(descriptor)),
+                None => Err(format!("invalid method type constant {}", index).into()),
+            }
+        },
+        Some(ConstantPoolEntry::Dynamic{..}) => resolve_dynamic_constant(jvm, class, index),
+        _ => Err(format!("constant {} is not loadable", index).into()),
     }
+}
 
-    return Ok(pc + 3)
+/* the kinds of method handles, named after the bytecode that does what invoking them does
+ * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-5.html#jvms-5.4.3.5
+ */
+const REF_GET_FIELD: i32 = 1;
+const REF_GET_STATIC: i32 = 2;
+const REF_PUT_FIELD: i32 = 3;
+const REF_PUT_STATIC: i32 = 4;
+const REF_INVOKE_VIRTUAL: i32 = 5;
+const REF_INVOKE_STATIC: i32 = 6;
+const REF_INVOKE_SPECIAL: i32 = 7;
+const REF_NEW_INVOKE_SPECIAL: i32 = 8;
+const REF_INVOKE_INTERFACE: i32 = 9;
+
+/* a direct method handle, referring to a method or field by its class, name and descriptor.
+ * `is_interface` is set when the class is an interface
+ */
+fn new_method_handle(reference_kind: i32, class_name: &str, name: &str, descriptor: &str, is_interface: bool) -> RuntimeValue {
+    let mut fields = HashMap::new();
+    fields.insert("referenceKind".to_string(), RuntimeValue::Int(reference_kind));
+    fields.insert("declaringClass".to_string(), new_string(class_name));
+    fields.insert("name".to_string(), new_string(name));
+    fields.insert("descriptor".to_string(), new_string(descriptor));
+    fields.insert("isInterface".to_string(), RuntimeValue::Int(if is_interface { 1 } else { 0 }));
+    fields.insert("type".to_string(), new_string(&direct_handle_type(reference_kind, class_name, descriptor)));
+    return new_handle_object("java/lang/invoke/DirectMethodHandle", fields);
 }
 
-fn make_int16(byte1:u8, byte2:u8) -> u16 {
-    return ((byte1 as u16) << 8) | (byte2 as u16)
+fn new_handle_object(class: &str, fields: HashMap<String, RuntimeValue>) -> RuntimeValue {
+    return RuntimeValue::Object(rc::Rc::new(cell::RefCell::new(JVMObject{
+        class: class.to_string(),
+        fields: fields,
+    })));
 }
 
-fn make_int32(byte1:u8, byte2:u8, byte3:u8, byte4:u8) -> u32 {
-    let b1 = byte1 as u32;
-    let b2 = byte2 as u32;
-    let b3 = byte3 as u32;
-    let b4 = byte4 as u32;
-    return (b1 << 24) | (b2 << 16) | (b3 << 8) | b4;
+/* the type of a direct method handle: the parameters and return type of what invoking it does,
+ * so a handle on an instance method takes the receiver first and a constructor returns the new
+ * object
+ */
+fn direct_handle_type(reference_kind: i32, class_name: &str, descriptor: &str) -> String {
+    let receiver = class_descriptor(class_name);
+    match reference_kind {
+        REF_GET_FIELD => format!("({}){}", receiver, descriptor),
+        REF_GET_STATIC => format!("(){}", descriptor),
+        REF_PUT_FIELD => format!("({}{})V", receiver, descriptor),
+        REF_PUT_STATIC => format!("({})V", descriptor),
+        REF_INVOKE_VIRTUAL | REF_INVOKE_SPECIAL | REF_INVOKE_INTERFACE => format!("({}{}", receiver, descriptor.strip_prefix('(').unwrap_or(descriptor)),
+        REF_NEW_INVOKE_SPECIAL => match split_method_descriptor(descriptor) {
+            Ok((parameters, _)) => format!("({}){}", parameters, receiver),
+            Err(_) => descriptor.to_string(),
+        },
+        _ => descriptor.to_string(),
+    }
 }
 
-fn do_execute_method(method: &MethodInfo, constant_pool: &ConstantPool, frame: &mut Frame, jvm: &RuntimeConst) -> Result<RuntimeValue, String> {
-    if let Some(AttributeKind::Code { max_stack, max_locals, code, exception_table, attributes }) = lookup_code_attribute(method) {
-        // FIXME: create frame based on max_stack and max_locals
-        debug!("Code attribute");
-        debug!("  max_stack={}", max_stack);
-        debug!("  max_locals={}", max_locals);
-        debug!("  code={}", code.len());
-        debug!("  exception_table={}", exception_table.len());
-        debug!("  attributes={}", attributes.len());
-
-        let mut pc = 0;
-        while pc < code.len() {
-            // println!("Opcopde {}: 0x{:x}", pc, code[pc]);
-            match code[pc] {
-                opcodes::ICONST0 => {
-                    frame.push_value(RuntimeValue::Int(0));
-                    pc += 1;
-                },
-                opcodes::ICONST1 => {
-                    frame.push_value(RuntimeValue::Int(1));
-                    pc += 1;
-                },
-                opcodes::ICONST2 => {
-                    frame.push_value(RuntimeValue::Int(2));
-                    pc += 1;
-                },
-                opcodes::ICONST3 => {
-                    frame.push_value(RuntimeValue::Int(3));
-                    pc += 1;
-                },
-                opcodes::ICONST4 => {
-                    frame.push_value(RuntimeValue::Int(4));
-                    pc += 1;
-                },
-                opcodes::ICONST5 => {
-                    frame.push_value(RuntimeValue::Int(5));
-                    pc += 1;
-                },
-                opcodes::PUSHBYTE => {
-                    let value = code[pc + 1] as i64;
-                    frame.push_value(RuntimeValue::Int(value));
-                    pc += 2;
-                },
-                opcodes::IRETURN => {
-                    pc += 1;
+// the method type of a method handle, as a method descriptor
+fn handle_type(handle: &RuntimeValue) -> Result<String, JVMError> {
+    return string_field(handle, "type");
+}
 
-                    // let value = frame.pop_value_force()?; 
-                    // println!("returning value {:?}", value);
+// a method handle that invokes another with the given values as its leading arguments
+fn bind_arguments(handle: RuntimeValue, values: Vec<RuntimeValue>) -> Result<RuntimeValue, JVMError> {
+    return insert_arguments(handle, 0, values);
+}
 
-                    return Ok(frame.pop_value_force()?);
-                    // return Ok(value);
-                },
-                opcodes::DUP => {
-                    pc += 1;
-                    let value = frame.pop_value_force()?;
-                    frame.push_value(value.clone());
-                    frame.push_value(value);
-                },
-                opcodes::ASTORE1 => {
-                    pc += 1;
-                    let value = frame.pop_value_force()?;
-                    frame.locals[1] = value;
-                },
-                opcodes::ISTORE => {
-                    let index = code[pc + 1] as usize;
-                    let value = frame.pop_value_force()?;
-                    frame.locals[index] = value;
-                    pc += 2;
-                },
-                opcodes::ISTORE0 => {
-                    pc += 1;
-                    let value = frame.pop_value_force()?;
-                    frame.locals[0] = value;
-                },
-                opcodes::ISTORE1 => {
-                    pc += 1;
-                    let value = frame.pop_value_force()?;
-                    frame.locals[1] = value;
-                },
-                opcodes::ISTORE2 => {
-                    pc += 1;
-                    let value = frame.pop_value_force()?;
-                    frame.locals[2] = value;
-                },
-                opcodes::ISTORE3 => {
-                    pc += 1;
-                    let value = frame.pop_value_force()?;
-                    frame.locals[3] = value;
-                },
-                opcodes::ALOAD0 => {
-                    pc += 1;
-                    let value = frame.locals[0].clone();
-                    frame.push_value(value);
+/* a method handle that invokes another with the given values inserted among its arguments at
+ * position
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/invoke/MethodHandles.html#insertArguments(java.lang.invoke.MethodHandle,int,java.lang.Object...)
+ */
+fn insert_arguments(handle: RuntimeValue, position: usize, values: Vec<RuntimeValue>) -> Result<RuntimeValue, JVMError> {
+    let target_type = handle_type(&handle)?;
+    let mut parameters = parameter_descriptors(&target_type)?;
+    if position + values.len() > parameters.len() {
+        return Err(JVMError::exception("java/lang/IllegalArgumentException", Some("too many values to insert".to_string())));
+    }
+    /* a value for a primitive parameter would have to be unboxed from its wrapper, which no
+     * reference can be yet
+     */
+    for (value, parameter) in values.iter().zip(parameters[position..].iter()) {
+        if let Ok(primitive) = parse_type_descriptor(parameter) {
+            let wrapper = PRIMITIVE_WRAPPERS.iter().find(|(_, _, name)| *name == descriptor_external_name(&primitive));
+            match (value, wrapper) {
+                (RuntimeValue::Null, Some(_)) => return Err(JVMError::null_pointer()),
+                (RuntimeValue::Object(_) | RuntimeValue::String(_) | RuntimeValue::Array(_), Some((wrapper, _, _))) => {
+                    let message = format!("class {} is not compatible with class {}", external_class_name(wrapper), external_class_name(&value.class_name().unwrap_or_default()));
+                    return Err(JVMError::exception("java/lang/ClassCastException", Some(message)));
                 },
-                opcodes::ALOAD1 => {
-                    pc += 1;
-                    let value = frame.locals[1].clone();
-                    frame.push_value(value);
+                _ => {
                 },
-                opcodes::TABLESWITCH => {
-                    let original_pc = pc;
+            }
+        }
+    }
+    parameters.drain(position..position + values.len());
 
-                    pc += 1;
-                    let padding = pc % 4;
-                    if padding != 0 {
-                        pc += 4 - padding;
-                    }
+    let mut bound = JVMArray::new("[Ljava/lang/Object;", values.len() as i32)?;
+    for (index, value) in values.into_iter().enumerate() {
+        bound.store(index as i32, value)?;
+    }
 
-                    let default = make_int32(code[pc], code[pc+1], code[pc+2], code[pc+3]);
-                    pc += 4;
-                    let low = make_int32(code[pc], code[pc+1], code[pc+2], code[pc+3]) as i64;
-                    pc += 4;
-                    let high = make_int32(code[pc], code[pc+1], code[pc+2], code[pc+3]) as i64;
-                    pc += 4;
+    let mut fields = HashMap::new();
+    fields.insert("target".to_string(), handle);
+    fields.insert("position".to_string(), RuntimeValue::Int(position as i32));
+    fields.insert("values".to_string(), RuntimeValue::Array(rc::Rc::new(cell::RefCell::new(bound))));
+    fields.insert("type".to_string(), new_string(&format!("({}){}", parameters.concat(), split_method_descriptor(&target_type)?.1)));
+    return Ok(new_handle_object("java/lang/invoke/BoundMethodHandle", fields));
+}
 
-                    // TODO: we don't really need to build this vector, we could just use the index
-                    // to directly map into the correct code[] offset
-                    let mut offsets = Vec::new();
-                    for _ in low..=high {
-                        let offset = make_int32(code[pc], code[pc+1], code[pc+2], code[pc+3]);
-                        pc += 4;
-                        offsets.push(offset);
-                    }
+/* a method handle that takes arguments of the given types at position and invokes another
+ * without them
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/invoke/MethodHandles.html#dropArguments(java.lang.invoke.MethodHandle,int,java.lang.Class...)
+ */
+fn drop_arguments(handle: RuntimeValue, position: usize, types: Vec<String>) -> Result<RuntimeValue, JVMError> {
+    let target_type = handle_type(&handle)?;
+    let mut parameters = parameter_descriptors(&target_type)?;
+    if position > parameters.len() {
+        return Err(JVMError::exception("java/lang/IllegalArgumentException", Some(format!("bad argument position {}", position))));
+    }
+    let count = types.len();
+    parameters.splice(position..position, types);
 
-                    let index = frame.pop_value_force()?;
+    let mut fields = HashMap::new();
+    fields.insert("target".to_string(), handle);
+    fields.insert("position".to_string(), RuntimeValue::Int(position as i32));
+    fields.insert("count".to_string(), RuntimeValue::Int(count as i32));
+    fields.insert("type".to_string(), new_string(&format!("({}){}", parameters.concat(), split_method_descriptor(&target_type)?.1)));
+    return Ok(new_handle_object("java/lang/invoke/DroppingMethodHandle", fields));
+}
 
-                    match index {
-                        RuntimeValue::Int(i) => {
-                            if i < low || i > high {
-                                pc = (original_pc as i32 + default as i32) as usize;
-                            } else {
-                                let offset = offsets[(i - low) as usize];
-                                pc = (original_pc as i32 + offset as i32) as usize;
-                            }
+/* a method handle of another type that invokes a handle, converting the arguments to the types it
+ * takes and its result to the type it returns. only the conversions that need no boxing are made,
+ * so that invoking it cannot fail on a value it was converted to take
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/invoke/MethodHandle.html#asType(java.lang.invoke.MethodType)
+ */
+fn as_type(handle: RuntimeValue, new_type: &str) -> Result<RuntimeValue, JVMError> {
+    let old_type = handle_type(&handle)?;
+    if old_type == new_type {
+        return Ok(handle);
+    }
+
+    let old_parameters = parameter_descriptors(&old_type)?;
+    let new_parameters = parameter_descriptors(new_type)?;
+    let (_, old_return) = split_method_descriptor(&old_type)?;
+    let (_, new_return) = split_method_descriptor(new_type)?;
+    let convertible = old_parameters.len() == new_parameters.len()
+        && new_parameters.iter().zip(old_parameters.iter()).all(|(from, to)| type_convertible(from, to))
+        && (new_return == "V" || old_return == "V" || type_convertible(old_return, new_return));
+    if !convertible {
+        return Err(JVMError::exception("java/lang/invoke/WrongMethodTypeException", Some(format!("cannot convert MethodHandle{} to {}", method_type_string(&old_type)?, method_type_string(new_type)?))));
+    }
+
+    let mut fields = HashMap::new();
+    fields.insert("target".to_string(), handle);
+    fields.insert("type".to_string(), new_string(new_type));
+    return Ok(new_handle_object("java/lang/invoke/ConvertingMethodHandle", fields));
+}
+
+/* whether asType can convert a value of one type to another: primitives widen and references are
+ * cast. a primitive is boxed into its wrapper class, which has to be of the type converted to, and
+ * a reference that is a wrapper or could be one is unboxed, then widened
+ */
+fn type_convertible(from: &str, to: &str) -> bool {
+    let is_reference = |descriptor: &str| descriptor.starts_with('L') || descriptor.starts_with('[');
+    let widens = |from: &str, to: &str| from == to || matches!((from, to), ("B", "S" | "I" | "J" | "F" | "D") | ("S" | "C", "I" | "J" | "F" | "D") | ("I", "J" | "F" | "D") | ("J", "F" | "D") | ("F", "D"));
+    // the wrapper class of a primitive type given by its descriptor, such as java/lang/Integer for I
+    let wrapper = |primitive: &str| parse_type_descriptor(primitive).ok().and_then(|primitive| wrapper_class(&primitive));
+    match (is_reference(from), is_reference(to)) {
+        (true, true) => true,
+        (false, false) => widens(from, to),
+        (false, true) => wrapper(from).is_some_and(|wrapper| wrapper_is_a(wrapper, to)),
+        (true, false) => {
+            match wrapped_primitive(&type_class_name(from)) {
+                Some(primitive) => widens(&class_descriptor(&descriptor_external_name(&primitive)), to),
+                None => wrapper(to).is_some_and(|wrapper| wrapper_is_a(wrapper, from)),
+            }
+        }
+    }
+}
+
+// whether the objects of a wrapper class are of the type of a descriptor, such as Ljava/lang/Number;
+fn wrapper_is_a(wrapper: &str, descriptor: &str) -> bool {
+    let class_name = type_class_name(descriptor);
+    let number = PRIMITIVE_WRAPPERS.iter().any(|(name, super_class, _)| *name == wrapper && *super_class == "java/lang/Number");
+    return class_name == wrapper
+        || matches!(class_name.as_str(), "java/lang/Object" | "java/io/Serializable" | "java/lang/Comparable")
+        || (number && class_name == "java/lang/Number");
+}
+
+/* the descriptors of the parameters of a method descriptor, such as I and [Ljava/lang/String; for
+ * (I[Ljava/lang/String;)V
+ */
+fn parameter_descriptors(descriptor: &str) -> Result<Vec<String>, JVMError> {
+    let (parameters, _) = split_method_descriptor(descriptor)?;
+    let mut result = Vec::new();
+    let mut start = 0;
+    let mut index = 0;
+    let bytes = parameters.as_bytes();
+    while index < bytes.len() {
+        match bytes[index] {
+            b'[' => {
+                index += 1;
+                continue;
+            },
+            b'L' => {
+                match parameters[index..].find(';') {
+                    Some(end) => index += end,
+                    None => return Err(format!("invalid method descriptor {}", descriptor).into()),
+                }
+            },
+            _ => {
+            }
+        }
+        index += 1;
+        result.push(parameters[start..index].to_string());
+        start = index;
+    }
+    return Ok(result);
+}
+
+// the descriptor of the type a java/lang/Class object stands for, such as I for int
+fn class_descriptor(class_name: &str) -> String {
+    match class_name {
+        "byte" => "B".to_string(),
+        "char" => "C".to_string(),
+        "double" => "D".to_string(),
+        "float" => "F".to_string(),
+        "int" => "I".to_string(),
+        "long" => "J".to_string(),
+        "short" => "S".to_string(),
+        "boolean" => "Z".to_string(),
+        "void" => "V".to_string(),
+        name if name.starts_with('[') => name.to_string(),
+        name => format!("L{};", name),
+    }
+}
+
+// the name of a type as Class.getSimpleName gives it, such as String[] for [Ljava/lang/String;
+fn type_simple_name(descriptor: &str) -> String {
+    match descriptor.strip_prefix('[') {
+        Some(component) => format!("{}[]", type_simple_name(component)),
+        None => {
+            let name = type_class_name(descriptor);
+            return name.rsplit(|c| c == '/' || c == '$').next().unwrap_or(&name).to_string();
+        }
+    }
+}
+
+// a method type as MethodType.toString gives it, such as (int,String)void
+fn method_type_string(descriptor: &str) -> Result<String, JVMError> {
+    let parameters: Vec<String> = parameter_descriptors(descriptor)?.iter().map(|parameter| type_simple_name(parameter)).collect();
+    let (_, return_type) = split_method_descriptor(descriptor)?;
+    return Ok(format!("({}){}", parameters.join(","), type_simple_name(return_type)));
+}
+
+fn new_method_type(descriptor: &str) -> RuntimeValue {
+    let mut fields = HashMap::new();
+    fields.insert("descriptor".to_string(), new_string(descriptor));
+    return RuntimeValue::Object(rc::Rc::new(cell::RefCell::new(JVMObject{
+        class: "java/lang/invoke/MethodType".to_string(),
+        fields: fields,
+    })));
+}
+
+// the lookup a bootstrap method gets, with the access of the class whose call site it links
+fn new_lookup(class_name: &str) -> RuntimeValue {
+    let mut fields = HashMap::new();
+    fields.insert("lookupClass".to_string(), new_string(class_name));
+    return RuntimeValue::Object(rc::Rc::new(cell::RefCell::new(JVMObject{
+        class: "java/lang/invoke/MethodHandles$Lookup".to_string(),
+        fields: fields,
+    })));
+}
+
+// a field of an object made by the jvm itself, such as the descriptor of a MethodType
+fn string_field(value: &RuntimeValue, name: &str) -> Result<String, JVMError> {
+    match value {
+        RuntimeValue::Object(object) => {
+            match object.borrow().fields.get(name) {
+                Some(RuntimeValue::String(string)) => Ok(string.borrow().to_string()),
+                _ => Err(format!("{} has no field {}", object.borrow().class, name).into()),
+            }
+        },
+        RuntimeValue::Null => Err(JVMError::null_pointer()),
+        value => Err(format!("expected an object with field {}, not {:?}", name, value).into()),
+    }
+}
+
+fn int_field(value: &RuntimeValue, name: &str) -> Result<i32, JVMError> {
+    match value {
+        RuntimeValue::Object(object) => {
+            match object.borrow().fields.get(name) {
+                Some(RuntimeValue::Int(int)) => Ok(*int),
+                _ => Err(format!("{} has no field {}", object.borrow().class, name).into()),
+            }
+        },
+        RuntimeValue::Null => Err(JVMError::null_pointer()),
+        value => Err(format!("expected an object with field {}, not {:?}", name, value).into()),
+    }
+}
+
+// the method handle a MethodHandle constant refers to
+fn method_handle_constant(constant_pool: &ConstantPool, index: usize) -> Result<RuntimeValue, JVMError> {
+    let (reference_kind, reference_index) = match constant_pool_lookup(constant_pool, index) {
+        Some(ConstantPoolEntry::MethodHandle{reference_kind, reference_index}) => (*reference_kind, *reference_index),
+        _ => return Err(format!("unknown method handle constant {}", index).into()),
+    };
+
+    let (class_index, name_and_type_index, is_interface) = match constant_pool_lookup(constant_pool, reference_index as usize) {
+        Some(ConstantPoolEntry::Methodref(class_index, name_and_type_index)) => (*class_index, *name_and_type_index, false),
+        Some(ConstantPoolEntry::InterfaceMethodref(class_index, name_and_type_index)) => (*class_index, *name_and_type_index, true),
+        Some(ConstantPoolEntry::Fieldref{class_index, name_and_type_index}) => (*class_index, *name_and_type_index, false),
+        _ => return Err(format!("invalid method handle reference {}", reference_index).into()),
+    };
+    let class_name = lookup_class_name(constant_pool, class_index as usize)?;
+    let (name, descriptor) = lookup_name_and_type(constant_pool, name_and_type_index as usize)?;
+    return Ok(new_method_handle(reference_kind as i32, class_name, name, descriptor, is_interface));
+}
+
+/* convert a value to the type a method expects, as invoking a method handle does: primitives are
+ * widened, wrappers unboxed and primitives boxed. with no type to go by, a primitive is boxed into
+ * the wrapper class of the kind of value it is, which convert_value does better
+ */
+fn convert_argument(jvm: &RuntimeConst, value: RuntimeValue, to: &Descriptor) -> Result<RuntimeValue, JVMError> {
+    match (value, to) {
+        (RuntimeValue::Int(value), Descriptor::Long) => Ok(RuntimeValue::Long(value as i64)),
+        (RuntimeValue::Int(value), Descriptor::Float) => Ok(RuntimeValue::Float(value as f32)),
+        (RuntimeValue::Int(value), Descriptor::Double) => Ok(RuntimeValue::Double(value as f64)),
+        (RuntimeValue::Long(value), Descriptor::Float) => Ok(RuntimeValue::Float(value as f32)),
+        (RuntimeValue::Long(value), Descriptor::Double) => Ok(RuntimeValue::Double(value as f64)),
+        (RuntimeValue::Float(value), Descriptor::Double) => Ok(RuntimeValue::Double(value as f64)),
+        (_, Descriptor::Void) => Ok(RuntimeValue::Void),
+        (value @ (RuntimeValue::Object(_) | RuntimeValue::String(_) | RuntimeValue::Array(_) | RuntimeValue::Null), Descriptor::Object(_) | Descriptor::Array(_)) => Ok(value),
+        (value @ (RuntimeValue::Object(_) | RuntimeValue::String(_) | RuntimeValue::Array(_) | RuntimeValue::Null), primitive) => {
+            let (unboxed, from) = unbox_value(&value, primitive)?;
+            let letter = |descriptor: &Descriptor| class_descriptor(&descriptor_external_name(descriptor));
+            if !type_convertible(&letter(&from), &letter(primitive)) {
+                return Err(cannot_cast(&value, wrapper_class(primitive).unwrap_or("java/lang/Object")));
+            }
+            return convert_argument(jvm, unboxed, primitive);
+        },
+        (value, Descriptor::Object(_) | Descriptor::Array(_)) => {
+            let primitive = match value {
+                RuntimeValue::Int(_) => Descriptor::Int,
+                RuntimeValue::Long(_) => Descriptor::Long,
+                RuntimeValue::Float(_) => Descriptor::Float,
+                RuntimeValue::Double(_) => Descriptor::Double,
+                value => return Err(format!("cannot convert {:?} to a reference", value).into()),
+            };
+            return box_value(jvm, value, &primitive);
+        },
+        (value, _) => Ok(value),
+    }
+}
+
+// convert a value of a known type, which tells the wrapper class a primitive is boxed into
+fn convert_value(jvm: &RuntimeConst, value: RuntimeValue, from: &Descriptor, to: &Descriptor) -> Result<RuntimeValue, JVMError> {
+    match (from, to) {
+        (Descriptor::Object(_) | Descriptor::Array(_) | Descriptor::Void, _) => convert_argument(jvm, value, to),
+        (primitive, Descriptor::Object(_) | Descriptor::Array(_)) => box_value(jvm, value, primitive),
+        (_, _) => convert_argument(jvm, value, to),
+    }
+}
+
+// what invoking a method with the given arguments leads to, without running a bytecode method yet
+fn method_invocation(jvm: &RuntimeConst, class: rc::Rc<JVMClass>, method: JVMMethod, name: &str, descriptor: &MethodDescriptor, receiver: Option<&str>, arguments: Vec<RuntimeValue>) -> Result<Invocation, JVMError> {
+    match method {
+        JVMMethod::Native(f) => {
+            return Ok(Invocation::Completed(f(jvm, arguments.as_slice())?));
+        },
+        JVMMethod::Invoker(f) => {
+            return f(jvm, arguments.as_slice());
+        },
+        JVMMethod::Bytecode(info) => {
+            check_invoked_method(&class, &info, name, descriptor, receiver)?;
+            return Ok(Invocation::Call(create_frame_with_arguments(class, info, arguments)?));
+        }
+    }
+}
+
+/* finish an invocation for a native that needs its result. a bytecode method runs in a nested
+ * run of the interpreter, the way static initializers do
+ */
+fn run_invocation(jvm: &RuntimeConst, invocation: Invocation) -> Result<RuntimeValue, JVMError> {
+    match invocation {
+        Invocation::Completed(value) => Ok(value),
+        Invocation::Call(frame) => run_method(jvm, frame),
+    }
+}
+
+// run a method to completion, for natives such as toString calling back into java code
+fn call_method(jvm: &RuntimeConst, class: rc::Rc<JVMClass>, method: JVMMethod, name: &str, descriptor: &MethodDescriptor, receiver: Option<&str>, arguments: Vec<RuntimeValue>) -> Result<RuntimeValue, JVMError> {
+    return run_invocation(jvm, method_invocation(jvm, class, method, name, descriptor, receiver, arguments)?);
+}
+
+/* call a method of an object like invokevirtual would, for natives that call back into java code
+ * such as toString. None if the class of the object has no such method
+ */
+fn call_virtual(jvm: &RuntimeConst, receiver: &RuntimeValue, name: &str, descriptor: &str, arguments: Vec<RuntimeValue>) -> Result<Option<RuntimeValue>, JVMError> {
+    let class_name = match receiver {
+        RuntimeValue::Object(_) | RuntimeValue::String(_) | RuntimeValue::Array(_) => receiver.method_class().unwrap_or_default(),
+        RuntimeValue::Null => return Err(JVMError::null_pointer()),
+        value => return Err(format!("cannot call {} on {:?}", name, value).into()),
+    };
+    match jvm.resolve_method_ref(&class_name, name, descriptor, false)? {
+        Some((class, method)) => {
+            let (class, method) = jvm.select_method(&class_name, class, method, name, descriptor)?;
+            let mut all = vec![receiver.clone()];
+            all.extend(arguments);
+            return Ok(Some(call_method(jvm, class, method, name, &parse_method_descriptor(descriptor)?, Some(&class_name), all)?));
+        },
+        None => {
+            return Ok(None);
+        }
+    }
+}
+
+/* invokeExact and invoke of MethodHandle are signature polymorphic: they take the arguments and
+ * return the type of the descriptor at the call site, whatever it is. invokeExact requires it to
+ * be the type of the handle, invoke converts the handle to it first
+ * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-2.html#jvms-2.9.3
+ */
+fn is_signature_polymorphic(class_name: &str, name: &str) -> bool {
+    return class_name == "java/lang/invoke/MethodHandle" && (name == "invokeExact" || name == "invoke");
+}
+
+fn invoke_polymorphic(jvm: &RuntimeConst, handle: RuntimeValue, name: &str, descriptor: &str, arguments: Vec<RuntimeValue>) -> Result<Invocation, JVMError> {
+    let handle_type = handle_type(&handle)?;
+    let handle = if name == "invokeExact" {
+        if handle_type != descriptor {
+            return Err(JVMError::exception("java/lang/invoke/WrongMethodTypeException", Some(format!("expected {} but found {}", method_type_string(&handle_type)?, method_type_string(descriptor)?))));
+        }
+        handle
+    } else {
+        as_type(handle, descriptor)?
+    };
+    return method_handle_invocation(jvm, &handle, arguments);
+}
+
+/* invoke a method handle: adapters rearrange the arguments and invoke the handle they adapt, until
+ * a direct method handle does what the bytecode its reference kind is named after
+ * would do. the arguments, after the receiver if there is one, are converted to the parameter
+ * types of the method
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/invoke/MethodHandleInfo.html
+ */
+fn invoke_method_handle(jvm: &RuntimeConst, handle: &RuntimeValue, arguments: Vec<RuntimeValue>) -> Result<RuntimeValue, JVMError> {
+    return run_invocation(jvm, method_handle_invocation(jvm, handle, arguments)?);
+}
+
+/* what invoking a method handle leads to. a handle for a bytecode method hands back its frame,
+ * for the invoke instructions to run it like any other
+ */
+fn method_handle_invocation(jvm: &RuntimeConst, handle: &RuntimeValue, arguments: Vec<RuntimeValue>) -> Result<Invocation, JVMError> {
+    let (class, target) = match handle {
+        RuntimeValue::Object(object) => {
+            let object = object.borrow();
+            (object.class.clone(), object.fields.get("target").cloned().unwrap_or(RuntimeValue::Null))
+        },
+        RuntimeValue::Null => return Err(JVMError::null_pointer()),
+        value => return Err(format!("not a method handle: {:?}", value).into()),
+    };
+
+    let mut arguments = arguments;
+    match class.as_str() {
+        "java/lang/invoke/BoundMethodHandle" => {
+            let position = int_field(handle, "position")? as usize;
+            let values = match handle {
+                RuntimeValue::Object(object) => object.borrow().fields.get("values").cloned().unwrap_or(RuntimeValue::Null),
+                _ => RuntimeValue::Null,
+            };
+            if position > arguments.len() {
+                return Err(format!("bound method handle invoked with {} arguments", arguments.len()).into());
+            }
+            arguments.splice(position..position, array_elements(&values)?);
+            return method_handle_invocation(jvm, &target, arguments);
+        },
+        "java/lang/invoke/DroppingMethodHandle" => {
+            let position = int_field(handle, "position")? as usize;
+            let count = int_field(handle, "count")? as usize;
+            if position + count > arguments.len() {
+                return Err(format!("dropping method handle invoked with {} arguments", arguments.len()).into());
+            }
+            arguments.drain(position..position + count);
+            return method_handle_invocation(jvm, &target, arguments);
+        },
+        "java/lang/invoke/ConvertingMethodHandle" => {
+            let target_type = parse_method_descriptor(&handle_type(&target)?)?;
+            let own_type = parse_method_descriptor(&handle_type(handle)?)?;
+            let mut converted = Vec::new();
+            for ((argument, from), to) in arguments.into_iter().zip(own_type.parameters.iter()).zip(target_type.parameters.iter()) {
+                converted.push(convert_value(jvm, argument, from, to)?);
+            }
+            let result = invoke_method_handle(jvm, &target, converted)?;
+            let result = match (result, &own_type.return_type) {
+                (_, Descriptor::Void) => RuntimeValue::Void,
+                // a handle returning void gives the zero of the type it is converted to
+                (RuntimeValue::Void, return_type) => default_value(return_type),
+                (result, return_type) => convert_value(jvm, result, &target_type.return_type, return_type)?,
+            };
+            return Ok(Invocation::Completed(result));
+        },
+        _ => {
+            return invoke_direct_handle(jvm, handle, arguments);
+        }
+    }
+}
+
+fn invoke_direct_handle(jvm: &RuntimeConst, handle: &RuntimeValue, arguments: Vec<RuntimeValue>) -> Result<Invocation, JVMError> {
+    let reference_kind = int_field(handle, "referenceKind")?;
+    let class_name = string_field(handle, "declaringClass")?;
+    let name = string_field(handle, "name")?;
+    let descriptor = string_field(handle, "descriptor")?;
+    let is_interface = int_field(handle, "isInterface")? != 0;
+    debug!("invoke method handle {} {}.{}{}", reference_kind, class_name, name, descriptor);
+
+    if matches!(reference_kind, REF_GET_FIELD | REF_GET_STATIC | REF_PUT_FIELD | REF_PUT_STATIC) {
+        return Ok(Invocation::Completed(access_field(jvm, reference_kind, &class_name, &name, &descriptor, arguments)?));
+    }
+
+    let method_descriptor = parse_method_descriptor(&descriptor)?;
+    let has_receiver = matches!(reference_kind, REF_INVOKE_VIRTUAL | REF_INVOKE_SPECIAL | REF_INVOKE_INTERFACE);
+    let receivers = if has_receiver { 1 } else { 0 };
+    if arguments.len() != method_descriptor.parameters.len() + receivers {
+        return Err(format!("method handle for {}.{}{} invoked with {} arguments", class_name, name, descriptor, arguments.len()).into());
+    }
+
+    let mut converted = Vec::new();
+    for (index, argument) in arguments.into_iter().enumerate() {
+        if index < receivers {
+            converted.push(argument);
+        } else {
+            converted.push(convert_argument(jvm, argument, &method_descriptor.parameters[index - receivers])?);
+        }
+    }
+    let arguments = converted;
+
+    match reference_kind {
+        REF_INVOKE_STATIC => {
+            let (class, method) = match jvm.resolve_method_ref(&class_name, &name, &descriptor, is_interface)? {
+                Some(resolved) => resolved,
+                None => return Err(unresolved_method(jvm, &class_name, &name, &method_descriptor)),
+            };
+            jvm.initialize_class(&class)?;
+            return method_invocation(jvm, class, method, &name, &method_descriptor, None, arguments);
+        },
+        REF_INVOKE_VIRTUAL | REF_INVOKE_SPECIAL | REF_INVOKE_INTERFACE => {
+            let receiver_class = match arguments.first() {
+                Some(receiver @ (RuntimeValue::Object(_) | RuntimeValue::String(_) | RuntimeValue::Array(_))) => receiver.method_class().unwrap_or_default(),
+                Some(RuntimeValue::Null) => return Err(JVMError::null_pointer()),
+                value => return Err(format!("method handle receiver was not a reference: {:?}", value).into()),
+            };
+            // a method reference such as int[]::clone names the array type, whose methods are those of Object
+            let class_name = if class_name.starts_with('[') { "java/lang/Object".to_string() } else { class_name };
+            let (class, method) = match jvm.resolve_method_ref(&class_name, &name, &descriptor, is_interface)? {
+                Some(resolved) => resolved,
+                None => return Err(unresolved_method(jvm, &class_name, &name, &method_descriptor)),
+            };
+            // like invokespecial, a special handle calls the method it refers to without selecting an override
+            let (class, method) = if reference_kind == REF_INVOKE_SPECIAL {
+                (class, method)
+            } else {
+                jvm.select_method(&receiver_class, class, method, &name, &descriptor)?
+            };
+            return method_invocation(jvm, class, method, &name, &method_descriptor, Some(&receiver_class), arguments);
+        },
+        REF_NEW_INVOKE_SPECIAL => {
+            let class = jvm.load_class(&class_name)?;
+            if class.access_flags & (ACC_ABSTRACT | ACC_INTERFACE) != 0 {
+                return Err(JVMError::exception("java/lang/InstantiationError", Some(external_class_name(&class_name))));
+            }
+            jvm.initialize_class(&class)?;
+            let constructor = match class.declared_method(&name, &descriptor) {
+                Some(constructor) => constructor.clone(),
+                None => return Err(unresolved_method(jvm, &class_name, &name, &method_descriptor)),
+            };
+
+            let object = rc::Rc::new(cell::RefCell::new(class.create_object()));
+            let mut arguments = arguments;
+            arguments.insert(0, RuntimeValue::Object(object.clone()));
+            call_method(jvm, class.clone(), constructor, &name, &method_descriptor, Some(&class_name), arguments)?;
+            return Ok(Invocation::Completed(RuntimeValue::Object(object)));
+        },
+        _ => {
+            return Err(format!("invalid method handle reference kind {}", reference_kind).into());
+        }
+    }
+}
+
+/* invoke a method handle on a field, which gets or sets it like getfield, getstatic, putfield and
+ * putstatic do
+ */
+fn access_field(jvm: &RuntimeConst, reference_kind: i32, class_name: &str, name: &str, descriptor: &str, arguments: Vec<RuntimeValue>) -> Result<RuntimeValue, JVMError> {
+    let field_type = parse_type_descriptor(descriptor)?;
+    match (reference_kind, arguments.as_slice()) {
+        (REF_GET_STATIC, []) => {
+            return get_static_field(jvm, class_name, name);
+        },
+        (REF_PUT_STATIC, [value]) => {
+            let class = static_field_class(jvm, class_name, name)?;
+            class.fields.borrow_mut().insert(name.to_string(), convert_argument(jvm, value.clone(), &field_type)?);
+            return Ok(RuntimeValue::Void);
+        },
+        (REF_GET_FIELD, [RuntimeValue::Object(object)]) => {
+            let key = instance_field_key(jvm, class_name, name, &object.borrow())?;
+            let value = object.borrow().fields.get(&key).cloned();
+            return value.ok_or_else(|| unresolved_field(jvm, class_name, name, false));
+        },
+        (REF_PUT_FIELD, [RuntimeValue::Object(object), value]) => {
+            let key = instance_field_key(jvm, class_name, name, &object.borrow())?;
+            object.borrow_mut().fields.insert(key, convert_argument(jvm, value.clone(), &field_type)?);
+            return Ok(RuntimeValue::Void);
+        },
+        (REF_GET_FIELD | REF_PUT_FIELD, [RuntimeValue::Null, ..]) => {
+            return Err(JVMError::null_pointer());
+        },
+        _ => {
+            return Err(format!("field method handle for {}.{} invoked with {} arguments", class_name, name, arguments.len()).into());
+        }
+    }
+}
+
+/* the error for a field that could not be resolved. finding a static field where an instance field
+ * was expected, or the other way around, is an IncompatibleClassChangeError
+ */
+fn unresolved_field(jvm: &RuntimeConst, class_name: &str, name: &str, is_static: bool) -> JVMError {
+    match jvm.load_class(class_name) {
+        Ok(class) => {
+            let other_kind = if is_static { class.instance_fields.contains_key(name) } else { class.fields.borrow().contains_key(name) };
+            if other_kind {
+                let message = format!("Expected {} field {}.{}", if is_static { "static" } else { "non-static" }, external_class_name(class_name), name);
+                return JVMError::exception("java/lang/IncompatibleClassChangeError", Some(message));
+            }
+
+            return JVMError::exception("java/lang/NoSuchFieldError", Some(name.to_string()));
+        },
+        Err(error) => {
+            return error;
+        }
+    }
+}
+
+/* the key an object stores an instance field under. the field is resolved against the class named
+ * by the fieldref, and the object may hide it behind a field of the same name from a subclass
+ */
+fn instance_field_key(jvm: &RuntimeConst, class_name: &str, name: &str, object: &JVMObject) -> Result<String, JVMError> {
+    let declaring_class = match jvm.resolve_instance_field(class_name, name) {
+        Some(declaring_class) => declaring_class,
+        None => return Err(unresolved_field(jvm, class_name, name, false)),
+    };
+
+    return match jvm.lookup_class(&object.class) {
+        Some(object_class) => Ok(object_class.field_key(&declaring_class.class, name)),
+        None => Ok(name.to_string()),
+    };
+}
+
+// the class and name of the field a fieldref refers to
+fn lookup_field_ref(constant_pool: &ConstantPool, index: usize) -> Result<(&str, &str), JVMError> {
+    match constant_pool_lookup(constant_pool, index) {
+        Some(ConstantPoolEntry::Fieldref{class_index, name_and_type_index}) => {
+            let class_name = lookup_class_name(constant_pool, *class_index as usize)?;
+            match constant_pool_lookup(constant_pool, *name_and_type_index as usize) {
+                Some(ConstantPoolEntry::NameAndType{name_index, ..}) => {
+                    match lookup_utf8_constant(constant_pool, *name_index as usize) {
+                        Some(name) => {
+                            return Ok((class_name, name));
                         },
-                        _ => {
-                            return Err(format!("Invalid index for tableswitch: {:?}", index));
+                        None => {
+                            return Err(format!("unknown name index {}", name_index).into());
                         }
                     }
                 },
-                opcodes::IFICOMPAREGREATEREQUAL => {
-                    pc = do_icompare(frame, pc, make_int16(code[pc+1], code[pc+2]) as i16, |i1, i2| i1 >= i2)?;
-                },
-                opcodes::GOTO => {
-                    let old = pc;
-                    let offset = make_int16(code[pc+1], code[pc+2]);
-                    pc = (pc as i16 + offset as i16) as usize;
-                },
-                opcodes::ILOAD => {
-                    let index = code[pc + 1] as usize;
-                    let value = frame.locals[index].clone();
-                    frame.push_value(value);
-                    pc += 2;
-                },
-                opcodes::ILOAD0 => {
-                    pc += 1;
-                    let value = frame.locals[0].clone();
-                    debug!("  load0: loading value {:?}", value);
-                    frame.push_value(value);
-                },
-                opcodes::ILOAD1 => {
-                    pc += 1;
-                    let value = frame.locals[1].clone();
-                    frame.push_value(value);
-                },
-                opcodes::ILOAD2 => {
-                    pc += 1;
-                    let value = frame.locals[2].clone();
-                    frame.push_value(value);
-                },
-                opcodes::ILOAD3 => {
-                    pc += 1;
-                    let value = frame.locals[3].clone();
-                    frame.push_value(value);
-                },
-                opcodes::IADD => {
-                    pc += 1;
-                    let value = do_iop(frame, |i1,i2| i1 + i2)?;
-                    frame.stack.push(value);
-                },
-                opcodes::IMUL => {
-                    pc += 1;
-                    let value = do_iop(frame, |i1,i2| i1 * i2)?;
-                    frame.stack.push(value);
-                },
-                opcodes::IDIV => {
-                    pc += 1;
-                    let value = do_iop(frame, |i1,i2| i1 / i2)?;
-                    frame.stack.push(value);
-                },
-                opcodes::IINC => {
-                    let index = code[pc + 1] as usize;
-                    let value = frame.locals[index].clone();
-                    let inc = code[pc + 2] as i64;
-                    match value {
-                        RuntimeValue::Int(i) => {
-                            frame.locals[index] = RuntimeValue::Int(i + inc)
-                        },
-                        _ => {
-                            return Err("inc on non-int".to_string());
-                        }
+                _ => {
+                    return Err(format!("unknown name and type index {}", name_and_type_index).into());
+                }
+            }
+        },
+        _ => {
+            return Err(format!("unknown fieldref {}", index).into());
+        }
+    }
+}
+
+/* the class that declares a static field, initialized so the field has its initial value
+ * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-6.html#jvms-6.5.getstatic
+ */
+fn static_field_class(jvm: &RuntimeConst, class_name: &str, name: &str) -> Result<rc::Rc<JVMClass>, JVMError> {
+    match jvm.resolve_static_field(class_name, name) {
+        Some(class) => {
+            jvm.initialize_class(&class)?;
+            return Ok(class);
+        },
+        None => {
+            return Err(unresolved_field(jvm, class_name, name, true));
+        }
+    }
+}
+
+fn op_getstatic(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &RuntimeConst, field_index: usize) -> Result<(), JVMError> {
+    let (class_name, name) = lookup_field_ref(constant_pool, field_index)?;
+    debug!("  get static {}.{}", class_name, name);
+    let class = static_field_class(jvm, class_name, name)?;
+    let value = class.fields.borrow().get(name).cloned();
+    match value {
+        Some(value) => {
+            frame.push_value(value);
+            return Ok(());
+        },
+        None => {
+            return Err(unresolved_field(jvm, class_name, name, true));
+        }
+    }
+}
+
+fn op_putstatic(constant_pool: &ConstantPool, jvm: &RuntimeConst, field_index: usize, field_value: RuntimeValue) -> Result<(), JVMError> {
+    let (class_name, name) = lookup_field_ref(constant_pool, field_index)?;
+    debug!("  put static {}.{} = {:?}", class_name, name, field_value);
+    let class = static_field_class(jvm, class_name, name)?;
+    if let Some(value) = class.fields.borrow_mut().get_mut(name) {
+        *value = field_value;
+        return Ok(());
+    }
+    return Err(unresolved_field(jvm, class_name, name, true));
+}
+
+fn push_runtime_constant(constant_pool: &ConstantPool, frame: &mut Frame, jvm: &RuntimeConst, index: usize) -> Result<(), JVMError> {
+    if index > 0 && index <= constant_pool.len() {
+        match constant_pool_lookup(constant_pool, index) {
+            Some(ConstantPoolEntry::Classref(_) | ConstantPoolEntry::MethodHandle{..} | ConstantPoolEntry::MethodType{..} | ConstantPoolEntry::Dynamic{..}) => {
+                let class = frame.class.clone();
+                frame.push_value(loadable_constant(jvm, &class, index)?);
+                return Ok(());
+            },
+            Some(ConstantPoolEntry::Utf8(name)) => {
+                debug!("Pushing constant utf8 {}", name);
+            },
+            Some(ConstantPoolEntry::Integer(value)) => {
+                frame.push_value(RuntimeValue::Int(*value));
+                return Ok(());
+            },
+            Some(ConstantPoolEntry::Float(value)) => {
+                frame.push_value(RuntimeValue::Float(*value));
+                return Ok(());
+            },
+            Some(ConstantPoolEntry::Long(value)) => {
+                frame.push_value(RuntimeValue::Long(*value));
+                return Ok(());
+            },
+            Some(ConstantPoolEntry::Double(value)) => {
+                frame.push_value(RuntimeValue::Double(*value));
+                return Ok(());
+            },
+            Some(ConstantPoolEntry::Stringref(string_index)) => {
+                debug!("Pushing constant string {}", string_index);
+                match constant_pool_lookup(constant_pool, *string_index as usize) {
+                    Some(ConstantPoolEntry::Utf8(name)) => {
+                        debug!("Pushing constant utf8 '{}'", name);
+                        frame.push_value(jvm.intern_str(name));
+                        return Ok(());
+                    },
+                    Some(ConstantPoolEntry::Utf16(chars)) => {
+                        frame.push_value(jvm.intern_contents(JVMString::new(chars.clone())));
+                        return Ok(());
+                    },
+                    None => {
+                        debug!("no such index {}", string_index);
                     }
-                    pc += 3;
+                    _ => {
+                        debug!("constant pool index {} is invalid", string_index);
+                    }
+                }
+            },
+            _ => {
+                debug!("ERROR: unhandled constant {}", &constant_pool[index-1].name());
+            }
+        }
+    } else {
+        return Err(format!("constant index {} out of range", index).to_string().into());
+    }
+
+    return Err("error with push constant".to_string().into());
+}
+
+// value2 is on top of the stack, so for `a - b` the op is called with op(a, b)
+fn do_iop(frame: &mut Frame, op: fn(i32, i32) -> i32) -> Result<RuntimeValue, JVMError> {
+    let value2 = frame.pop_int()?;
+    let value1 = frame.pop_int()?;
+    debug!("  iop {} {} = {}", value1, value2, op(value1, value2));
+    return Ok(RuntimeValue::Int(op(value1, value2)));
+}
+
+fn do_lop(frame: &mut Frame, op: fn(i64, i64) -> i64) -> Result<RuntimeValue, JVMError> {
+    let value2 = frame.pop_long()?;
+    let value1 = frame.pop_long()?;
+    return Ok(RuntimeValue::Long(op(value1, value2)));
+}
+
+fn do_fop(frame: &mut Frame, op: fn(f32, f32) -> f32) -> Result<RuntimeValue, JVMError> {
+    let value2 = frame.pop_float()?;
+    let value1 = frame.pop_float()?;
+    return Ok(RuntimeValue::Float(op(value1, value2)));
+}
+
+fn do_dop(frame: &mut Frame, op: fn(f64, f64) -> f64) -> Result<RuntimeValue, JVMError> {
+    let value2 = frame.pop_double()?;
+    let value1 = frame.pop_double()?;
+    return Ok(RuntimeValue::Double(op(value1, value2)));
+}
+
+// the shift distance of a long shift is an int
+fn do_lshift(frame: &mut Frame, op: fn(i64, u32) -> i64) -> Result<RuntimeValue, JVMError> {
+    let distance = frame.pop_int()?;
+    let value = frame.pop_long()?;
+    return Ok(RuntimeValue::Long(op(value, (distance & 0x3f) as u32)));
+}
+
+fn do_idivide(frame: &mut Frame, op: fn(i32, i32) -> i32) -> Result<RuntimeValue, JVMError> {
+    if let Some(RuntimeValue::Int(0)) = frame.stack.last() {
+        return Err(JVMError::exception("java/lang/ArithmeticException", Some("/ by zero".to_string())));
+    }
+    return do_iop(frame, op);
+}
+
+fn do_ldivide(frame: &mut Frame, op: fn(i64, i64) -> i64) -> Result<RuntimeValue, JVMError> {
+    if let Some(RuntimeValue::Long(0)) = frame.stack.last() {
+        return Err(JVMError::exception("java/lang/ArithmeticException", Some("/ by zero".to_string())));
+    }
+    return do_lop(frame, op);
+}
+
+/* fcmpl/dcmpl push -1 when either value is NaN, fcmpg/dcmpg push 1 */
+fn compare_floating(value1: f64, value2: f64, nan_result: i32) -> RuntimeValue {
+    if value1 > value2 {
+        return RuntimeValue::Int(1);
+    }
+    if value1 < value2 {
+        return RuntimeValue::Int(-1);
+    }
+    if value1 == value2 {
+        return RuntimeValue::Int(0);
+    }
+    return RuntimeValue::Int(nan_result);
+}
+
+fn create_new_object(constant_pool: &ConstantPool, jvm: &RuntimeConst, index: usize) -> Result<RuntimeValue, JVMError> {
+    match constant_pool_lookup(constant_pool, index) {
+        Some(ConstantPoolEntry::Classref(class_index)) => {
+            match constant_pool_lookup(constant_pool, *class_index as usize) {
+                Some(ConstantPoolEntry::Utf8(class_name)) => {
+                    debug!("  class_name={}", class_name);
+                    let class = jvm.load_class(class_name)?;
+                    if class.access_flags & (ACC_ABSTRACT | ACC_INTERFACE) != 0 {
+                        return Err(JVMError::exception("java/lang/InstantiationError", Some(external_class_name(class_name))));
+                    }
+                    jvm.initialize_class(&class)?;
+                    // a new string is empty until one of the constructors of String gives it its contents
+                    if class.class == "java/lang/String" {
+                        return Ok(new_string(""));
+                    }
+                    return Ok(RuntimeValue::Object(rc::Rc::new(cell::RefCell::new(class.create_object()))))
                 },
-                opcodes::NEW => {
-                    let b1 = code[pc+1] as usize;
-                    let b2 = code[pc+2] as usize;
-                    let total = (b1 << 8) | b2;
+                _ => {
+                    debug!("  Unknown classref {}", *class_index);
+                }
+            }
+        },
+        None => {
+            return Err(format!("unknown classref {}", index).into())
+        }
+        _ => {
+            return Err(format!("index {} was not a classref", index).into())
+        }
+    }
+
+    return Err("error with create object".to_string().into());
+}
+
+fn lookup_code_attribute(method: &MethodInfo) -> Option<&AttributeKind> {
+    for i in 0..method.attributes.len() {
+        match &method.attributes[i] {
+            AttributeKind::Code { max_stack: _, max_locals: _, code: _, exception_table: _, attributes: _ } => {
+                return Some(&method.attributes[i]);
+            },
+            _ => {
+            }
+        }
+    }
+
+    return None;
+}
+
+fn putfield(constant_pool: &ConstantPool, jvm: &RuntimeConst, field_index: usize, object: RuntimeValue, field_value: RuntimeValue) -> Result<(), JVMError> {
+    match constant_pool_lookup(constant_pool, field_index) {
+        Some(ConstantPoolEntry::Fieldref{class_index, name_and_type_index}) => {
+            match constant_pool_lookup(constant_pool, *name_and_type_index as usize) {
+                Some(ConstantPoolEntry::NameAndType{name_index, ..}) => {
+                    match lookup_utf8_constant(constant_pool, *name_index as usize) {
+                        Some(name) => {
+                            let class_name = lookup_class_name(constant_pool, *class_index as usize)?;
+                            match object {
+                                RuntimeValue::Object(object) => {
+                                    debug!("  set field {}.{} = {:?}", object.borrow().class, name, field_value);
+                                    let key = instance_field_key(jvm, class_name, name, &object.borrow())?;
+                                    let mut object = object.borrow_mut();
+                                    match object.fields.get_mut(&key) {
+                                        Some(value) => {
+                                            *value = field_value;
+                                        },
+                                        None => {
+                                            return Err(unresolved_field(jvm, class_name, name, false));
+                                        }
+                                    }
+                                    return Ok(());
+                                },
+                                RuntimeValue::Null => {
+                                    return Err(JVMError::null_pointer());
+                                },
+                                _ => {
+                                    return Err(format!("objectref was not an object: {:?}", object).into())
+                                }
+                            }
+                        },
+                        _ => {
+                            return Err(format!("unknown name index {}", name_index).into())
+                        }
+                    }
+                },
+                _ => {
+                    return Err(format!("unknown name and type index {}", name_and_type_index).into())
+                }
+            }
+        },
+        _ => {
+            return Err(format!("unknown fieldref {}", field_index).into())
+        }
+    }
+}
+
+fn getfield(constant_pool: &ConstantPool, jvm: &RuntimeConst, field_index: usize, object: RuntimeValue) -> Result<RuntimeValue, JVMError> {
+    match constant_pool_lookup(constant_pool, field_index) {
+        Some(ConstantPoolEntry::Fieldref{class_index, name_and_type_index}) => {
+            match constant_pool_lookup(constant_pool, *name_and_type_index as usize) {
+                Some(ConstantPoolEntry::NameAndType{name_index, ..}) => {
+                    match lookup_utf8_constant(constant_pool, *name_index as usize) {
+                        Some(name) => {
+                            let class_name = lookup_class_name(constant_pool, *class_index as usize)?;
+                            match object {
+                                RuntimeValue::Object(object) => {
+                                    let key = instance_field_key(jvm, class_name, name, &object.borrow())?;
+                                    if let Some(value) = object.borrow().fields.get(&key) {
+                                        return Ok(value.clone());
+                                    } else {
+                                        return Err(unresolved_field(jvm, class_name, name, false));
+                                    }
+                                },
+                                RuntimeValue::Null => {
+                                    return Err(JVMError::null_pointer());
+                                },
+                                _ => {
+                                    return Err(format!("objectref was not an object: {:?}", object).into())
+                                }
+                            }
+                        },
+                        _ => {
+                            return Err(format!("unknown name index {}", name_index).into())
+                        }
+                    }
+                },
+                _ => {
+                    return Err(format!("unknown name and type index {}", name_and_type_index).into())
+                }
+            }
+        },
+        _ => {
+            return Err(format!("unknown fieldref {}", field_index).into())
+        }
+    }
+}
+
+fn branch_target(pc: usize, offset: i16) -> usize {
+    return (pc as i64 + offset as i64) as usize;
+}
+
+fn do_icompare(frame: &mut Frame, pc: usize, offset: i16, compare: fn(i32, i32) -> bool) -> Result<usize, JVMError>{
+    let value2 = frame.pop_int()?;
+    let value1 = frame.pop_int()?;
+    if compare(value1, value2) {
+        return Ok(branch_target(pc, offset));
+    }
+
+    return Ok(pc + 3)
+}
+
+// ifeq, iflt, etc compare a single int against zero
+fn do_if(frame: &mut Frame, pc: usize, offset: i16, compare: fn(i32) -> bool) -> Result<usize, JVMError>{
+    let value = frame.pop_int()?;
+    if compare(value) {
+        return Ok(branch_target(pc, offset));
+    }
+
+    return Ok(pc + 3)
+}
+
+fn check_subroutines_allowed(class: &JVMClass) -> Result<(), JVMError> {
+    if class.major_version > MAX_SUBROUTINE_CLASS_VERSION {
+        return Err(JVMError::exception("java/lang/VerifyError", Some(format!("jsr/ret are not allowed in class {} with version {}", class.class, class.major_version))));
+    }
+
+    return Ok(());
+}
+
+fn make_int16(byte1:u8, byte2:u8) -> u16 {
+    return ((byte1 as u16) << 8) | (byte2 as u16)
+}
+
+fn make_int32(byte1:u8, byte2:u8, byte3:u8, byte4:u8) -> u32 {
+    let b1 = byte1 as u32;
+    let b2 = byte2 as u32;
+    let b3 = byte3 as u32;
+    let b4 = byte4 as u32;
+    return (b1 << 24) | (b2 << 16) | (b3 << 8) | b4;
+}
+
+/* run a method to completion. the methods it invokes are run by this same loop with their frames
+ * pushed on an explicit stack, so java recursion does not recurse in rust
+ */
+fn run_method(jvm: &RuntimeConst, frame: Frame) -> Result<RuntimeValue, JVMError> {
+    /* the frames of a run live on the heap, but each nested run takes up native stack, which
+     * runs out long before the java stack does when natives and java code keep calling each other
+     */
+    if jvm.nested_runs.get() >= MAX_NESTED_RUNS {
+        return Err(JVMError::exception("java/lang/StackOverflowError", None));
+    }
+    jvm.enter_method(&frame)?;
+    jvm.nested_runs.set(jvm.nested_runs.get() + 1);
+    let mut frames = vec![frame];
+    let result = run_frames(jvm, &mut frames);
+    jvm.nested_runs.set(jvm.nested_runs.get() - 1);
+
+    // frames left over after an error in the jvm itself are still on the call stack
+    for _frame in frames.iter() {
+        jvm.leave_method();
+    }
+    return result;
+}
+
+/* execute the innermost frame until it invokes a method, returns or throws. an exception unwinds
+ * frames until one of them has a handler for it
+ */
+fn run_frames(jvm: &RuntimeConst, frames: &mut Vec<Frame>) -> Result<RuntimeValue, JVMError> {
+    loop {
+        let frame = match frames.last_mut() {
+            Some(frame) => frame,
+            None => return Err("no frame to execute".to_string().into()),
+        };
+
+        let error = match execute_code(frame, jvm) {
+            Ok(Step::Call(callee)) => {
+                match jvm.enter_method(&callee) {
+                    Ok(()) => {
+                        frames.push(callee);
+                        continue;
+                    },
+                    // a StackOverflowError is thrown by the invoke instruction of the caller
+                    Err(error) => error,
+                }
+            },
+            Ok(Step::Return(value)) => {
+                frames.pop();
+                jvm.leave_method();
+                match frames.last_mut() {
+                    Some(caller) => {
+                        if !matches!(value, RuntimeValue::Void) {
+                            caller.push_value(value);
+                        }
+                        continue;
+                    },
+                    None => {
+                        return Ok(value);
+                    }
+                }
+            },
+            Err(error) => error,
+        };
+
+        let exception = match error {
+            JVMError::Exception(exception) => exception,
+            error => return Err(error),
+        };
+
+        loop {
+            match frames.last_mut() {
+                Some(frame) => {
+                    if catch_exception(frame, jvm, &exception)? {
+                        break;
+                    }
+                },
+                None => {
+                    return Err(JVMError::Exception(exception));
+                }
+            }
+            frames.pop();
+            jvm.leave_method();
+        }
+    }
+}
+
+/* look in the exception table of the frame's method for a handler covering the instruction being
+ * executed. the frame continues at the handler with only the exception on its operand stack
+ */
+fn catch_exception(frame: &mut Frame, jvm: &RuntimeConst, exception: &rc::Rc<cell::RefCell<JVMObject>>) -> Result<bool, JVMError> {
+    let current_pc = jvm.current_pc();
+    add_null_pointer_message(&frame.method, &frame.class.constant_pool, current_pc, exception);
+    ensure_stack_trace(jvm, exception);
+
+    let exception_table = match lookup_code_attribute(&frame.method) {
+        Some(AttributeKind::Code{exception_table, ..}) => exception_table,
+        _ => return Err("no code attribute".to_string().into()),
+    };
+
+    match find_exception_handler(&frame.class.constant_pool, jvm, exception_table, current_pc, exception)? {
+        Some(handler_pc) => {
+            debug!("exception {} caught at pc={} by handler at {}", exception.borrow().class, current_pc, handler_pc);
+            frame.stack.clear();
+            frame.push_value(RuntimeValue::Object(exception.clone()));
+            frame.pc = handler_pc;
+            return Ok(true);
+        },
+        None => {
+            return Ok(false);
+        }
+    }
+}
+
+// describe what was null for a NullPointerException the jvm raised in this method
+fn add_null_pointer_message(method: &MethodInfo, constant_pool: &ConstantPool, pc: usize, exception: &rc::Rc<cell::RefCell<JVMObject>>) {
+    let mut exception = exception.borrow_mut();
+    if let Some(RuntimeValue::Int(1)) = exception.fields.get("extendedMessageState") {
+        if let Some(message) = npe::null_pointer_message(method, constant_pool, pc) {
+            exception.fields.insert("detailMessage".to_string(), new_string(&message));
+        }
+        exception.fields.insert("extendedMessageState".to_string(), RuntimeValue::Int(2));
+    }
+}
+
+/* find the handler in the exception table that covers `pc` and catches the class of the exception
+ * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-6.html#jvms-6.5.athrow
+ */
+fn find_exception_handler(constant_pool: &ConstantPool, jvm: &RuntimeConst, exception_table: &[ExceptionTableEntry], pc: usize, exception: &rc::Rc<cell::RefCell<JVMObject>>) -> Result<Option<usize>, JVMError> {
+    let exception_class = exception.borrow().class.clone();
+    for entry in exception_table {
+        if pc < entry.start_pc as usize || pc >= entry.end_pc as usize {
+            continue;
+        }
+
+        // a catch_type of 0 catches everything, javac uses it for finally blocks
+        if entry.catch_type == 0 {
+            return Ok(Some(entry.handler_pc as usize));
+        }
+
+        let catch_class = lookup_class_name(constant_pool, entry.catch_type as usize)?;
+        if jvm.is_assignable(&exception_class, catch_class) {
+            return Ok(Some(entry.handler_pc as usize));
+        }
+    }
+
+    return Ok(None);
+}
+
+/* run the code of a frame from its pc until the method returns, invokes a bytecode method or throws.
+ * the pc of the instruction being executed is kept in the call stack so that a thrown exception
+ * can be matched against the exception table and its stack trace shows the right line
+ */
+fn execute_code(frame: &mut Frame, jvm: &RuntimeConst) -> Result<Step, JVMError> {
+    // the frame keeps its class and method alive, these are only borrowed while it runs
+    let class = frame.class.clone();
+    let method = frame.method.clone();
+    let constant_pool: &ConstantPool = &class.constant_pool;
+    let code: &[u8] = match lookup_code_attribute(&method) {
+        Some(AttributeKind::Code{code, ..}) => code,
+        _ => return Err("no code attribute".to_string().into()),
+    };
+    let mut pc = frame.pc;
+    while pc < code.len() {
+        jvm.set_current_pc(pc);
+        // println!("Opcopde {}: 0x{:x}", pc, code[pc]);
+        match code[pc] {
+            opcodes::ACONSTNULL => {
+                frame.push_value(RuntimeValue::Null);
+                pc += 1;
+            },
+            opcodes::ICONSTM1 => {
+                frame.push_value(RuntimeValue::Int(-1));
+                pc += 1;
+            },
+            opcodes::LCONST0 => {
+                frame.push_value(RuntimeValue::Long(0));
+                pc += 1;
+            },
+            opcodes::LCONST1 => {
+                frame.push_value(RuntimeValue::Long(1));
+                pc += 1;
+            },
+            opcodes::FCONST0 => {
+                frame.push_value(RuntimeValue::Float(0.0));
+                pc += 1;
+            },
+            opcodes::FCONST1 => {
+                frame.push_value(RuntimeValue::Float(1.0));
+                pc += 1;
+            },
+            opcodes::FCONST2 => {
+                frame.push_value(RuntimeValue::Float(2.0));
+                pc += 1;
+            },
+            opcodes::DCONST0 => {
+                frame.push_value(RuntimeValue::Double(0.0));
+                pc += 1;
+            },
+            opcodes::DCONST1 => {
+                frame.push_value(RuntimeValue::Double(1.0));
+                pc += 1;
+            },
+            opcodes::ICONST0 => {
+                frame.push_value(RuntimeValue::Int(0));
+                pc += 1;
+            },
+            opcodes::ICONST1 => {
+                frame.push_value(RuntimeValue::Int(1));
+                pc += 1;
+            },
+            opcodes::ICONST2 => {
+                frame.push_value(RuntimeValue::Int(2));
+                pc += 1;
+            },
+            opcodes::ICONST3 => {
+                frame.push_value(RuntimeValue::Int(3));
+                pc += 1;
+            },
+            opcodes::ICONST4 => {
+                frame.push_value(RuntimeValue::Int(4));
+                pc += 1;
+            },
+            opcodes::ICONST5 => {
+                frame.push_value(RuntimeValue::Int(5));
+                pc += 1;
+            },
+            opcodes::PUSHBYTE => {
+                let value = code[pc + 1] as i8 as i32;
+                frame.push_value(RuntimeValue::Int(value));
+                pc += 2;
+            },
+            opcodes::PUSHSHORT => {
+                let value = make_int16(code[pc + 1], code[pc + 2]) as i16 as i32;
+                frame.push_value(RuntimeValue::Int(value));
+                pc += 3;
+            },
+            opcodes::IRETURN => {
+                pc += 1;
+
+                // let value = frame.pop_value_force()?; 
+                // println!("returning value {:?}", value);
+
+                return Ok(Step::Return(frame.pop_value_force()?));
+                // return Ok(value);
+            },
+            opcodes::DUP => {
+                pc += 1;
+                frame.dup_words(1, 0)?;
+            },
+            opcodes::DUPX1 => {
+                pc += 1;
+                frame.dup_words(1, 1)?;
+            },
+            opcodes::DUPX2 => {
+                pc += 1;
+                frame.dup_words(1, 2)?;
+            },
+            opcodes::DUP2 => {
+                pc += 1;
+                frame.dup_words(2, 0)?;
+            },
+            opcodes::DUP2X1 => {
+                pc += 1;
+                frame.dup_words(2, 1)?;
+            },
+            opcodes::DUP2X2 => {
+                pc += 1;
+                frame.dup_words(2, 2)?;
+            },
+            opcodes::POP => {
+                pc += 1;
+                frame.pop_words(1)?;
+            },
+            opcodes::POP2 => {
+                pc += 1;
+                frame.pop_words(2)?;
+            },
+            opcodes::SWAP => {
+                pc += 1;
+                let value1 = frame.pop_words(1)?;
+                let value2 = frame.pop_words(1)?;
+                frame.push_values(&value1);
+                frame.push_values(&value2);
+            },
+            opcodes::ASTORE => {
+                let index = code[pc + 1] as usize;
+                let value = frame.pop_value_force()?;
+                frame.locals[index] = value;
+                pc += 2;
+            },
+            opcodes::ASTORE0 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.locals[0] = value;
+            },
+            opcodes::ASTORE1 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.locals[1] = value;
+            },
+            opcodes::ASTORE2 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.locals[2] = value;
+            },
+            opcodes::ASTORE3 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.locals[3] = value;
+            },
+            opcodes::ISTORE => {
+                let index = code[pc + 1] as usize;
+                let value = frame.pop_value_force()?;
+                frame.locals[index] = value;
+                pc += 2;
+            },
+            opcodes::ISTORE0 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.locals[0] = value;
+            },
+            opcodes::ISTORE1 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.locals[1] = value;
+            },
+            opcodes::ISTORE2 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.locals[2] = value;
+            },
+            opcodes::ISTORE3 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.locals[3] = value;
+            },
+            opcodes::LLOAD => {
+                let index = code[pc + 1] as usize;
+                frame.push_value(frame.load_local(index));
+                pc += 2;
+            },
+            opcodes::LLOAD0 => {
+                pc += 1;
+                frame.push_value(frame.load_local(0));
+            },
+            opcodes::LLOAD1 => {
+                pc += 1;
+                frame.push_value(frame.load_local(1));
+            },
+            opcodes::LLOAD2 => {
+                pc += 1;
+                frame.push_value(frame.load_local(2));
+            },
+            opcodes::LLOAD3 => {
+                pc += 1;
+                frame.push_value(frame.load_local(3));
+            },
+            opcodes::LSTORE => {
+                let index = code[pc + 1] as usize;
+                let value = frame.pop_value_force()?;
+                frame.store_local(index, value);
+                pc += 2;
+            },
+            opcodes::LSTORE0 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.store_local(0, value);
+            },
+            opcodes::LSTORE1 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.store_local(1, value);
+            },
+            opcodes::LSTORE2 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.store_local(2, value);
+            },
+            opcodes::LSTORE3 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.store_local(3, value);
+            },
+            opcodes::FLOAD => {
+                let index = code[pc + 1] as usize;
+                frame.push_value(frame.load_local(index));
+                pc += 2;
+            },
+            opcodes::FLOAD0 => {
+                pc += 1;
+                frame.push_value(frame.load_local(0));
+            },
+            opcodes::FLOAD1 => {
+                pc += 1;
+                frame.push_value(frame.load_local(1));
+            },
+            opcodes::FLOAD2 => {
+                pc += 1;
+                frame.push_value(frame.load_local(2));
+            },
+            opcodes::FLOAD3 => {
+                pc += 1;
+                frame.push_value(frame.load_local(3));
+            },
+            opcodes::FSTORE => {
+                let index = code[pc + 1] as usize;
+                let value = frame.pop_value_force()?;
+                frame.store_local(index, value);
+                pc += 2;
+            },
+            opcodes::FSTORE0 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.store_local(0, value);
+            },
+            opcodes::FSTORE1 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.store_local(1, value);
+            },
+            opcodes::FSTORE2 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.store_local(2, value);
+            },
+            opcodes::FSTORE3 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.store_local(3, value);
+            },
+            opcodes::DLOAD => {
+                let index = code[pc + 1] as usize;
+                frame.push_value(frame.load_local(index));
+                pc += 2;
+            },
+            opcodes::DLOAD0 => {
+                pc += 1;
+                frame.push_value(frame.load_local(0));
+            },
+            opcodes::DLOAD1 => {
+                pc += 1;
+                frame.push_value(frame.load_local(1));
+            },
+            opcodes::DLOAD2 => {
+                pc += 1;
+                frame.push_value(frame.load_local(2));
+            },
+            opcodes::DLOAD3 => {
+                pc += 1;
+                frame.push_value(frame.load_local(3));
+            },
+            opcodes::DSTORE => {
+                let index = code[pc + 1] as usize;
+                let value = frame.pop_value_force()?;
+                frame.store_local(index, value);
+                pc += 2;
+            },
+            opcodes::DSTORE0 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.store_local(0, value);
+            },
+            opcodes::DSTORE1 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.store_local(1, value);
+            },
+            opcodes::DSTORE2 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.store_local(2, value);
+            },
+            opcodes::DSTORE3 => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                frame.store_local(3, value);
+            },
+            opcodes::ALOAD => {
+                let index = code[pc + 1] as usize;
+                frame.push_value(frame.load_local(index));
+                pc += 2;
+            },
+            opcodes::IALOAD | opcodes::LALOAD | opcodes::FALOAD | opcodes::DALOAD |
+            opcodes::AALOAD | opcodes::BALOAD | opcodes::CALOAD | opcodes::SALOAD => {
+                pc += 1;
+                let index = frame.pop_int()?;
+                let array = pop_array(frame)?;
+                let value = array.borrow().load(index)?;
+                frame.push_value(value);
+            },
+            opcodes::IASTORE | opcodes::LASTORE | opcodes::FASTORE | opcodes::DASTORE |
+            opcodes::BASTORE | opcodes::CASTORE | opcodes::SASTORE => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                let index = frame.pop_int()?;
+                let array = pop_array(frame)?;
+                array.borrow_mut().store(index, value)?;
+            },
+            opcodes::AASTORE => {
+                pc += 1;
+                let value = frame.pop_value_force()?;
+                let index = frame.pop_int()?;
+                let array = pop_array(frame)?;
+                let mut array = array.borrow_mut();
+                if let (Some(value_class), Some(component_class)) = (value.class_name(), component_class_of(&array.class)) {
+                    if !jvm.is_assignable(&value_class, component_class) {
+                        return Err(JVMError::exception("java/lang/ArrayStoreException", Some(external_class_name(&value_class))));
+                    }
+                }
+                array.store(index, value)?;
+            },
+            opcodes::NEWARRAY => {
+                let class = primitive_array_class(code[pc+1])?;
+                let length = frame.pop_int()?;
+                frame.push_value(new_array(class, length)?);
+                pc += 2;
+            },
+            opcodes::ANEWARRAY => {
+                let component = lookup_class_name(constant_pool, make_int16(code[pc+1], code[pc+2]) as usize)?;
+                let length = frame.pop_int()?;
+                frame.push_value(new_array(&array_class_of(component), length)?);
+                pc += 3;
+            },
+            opcodes::MULTIANEWARRAY => {
+                let class = lookup_class_name(constant_pool, make_int16(code[pc+1], code[pc+2]) as usize)?;
+                let dimensions = code[pc+3] as usize;
+                let mut lengths = Vec::new();
+                for _i in 0..dimensions {
+                    lengths.push(frame.pop_int()?);
+                }
+                lengths.reverse();
+                frame.push_value(new_multi_array(class, &lengths)?);
+                pc += 4;
+            },
+            opcodes::CHECKCAST => {
+                let class = lookup_class_name(constant_pool, make_int16(code[pc+1], code[pc+2]) as usize)?;
+                let value = frame.pop_value_force()?;
+                // null can be cast to anything
+                if let Some(value_class) = value.class_name() {
+                    if !jvm.is_assignable(&value_class, class) {
+                        return Err(JVMError::exception("java/lang/ClassCastException", Some(class_cast_message(&value_class, class))));
+                    }
+                }
+                frame.push_value(value);
+                pc += 3;
+            },
+            opcodes::INSTANCEOF => {
+                let class = lookup_class_name(constant_pool, make_int16(code[pc+1], code[pc+2]) as usize)?;
+                let value = frame.pop_value_force()?;
+                let result = match value.class_name() {
+                    Some(value_class) => jvm.is_assignable(&value_class, class),
+                    None => false,
+                };
+                frame.push_value(RuntimeValue::Int(result as i32));
+                pc += 3;
+            },
+            opcodes::ARRAYLENGTH => {
+                pc += 1;
+                let array = pop_array(frame)?;
+                let length = array.borrow().length();
+                frame.push_value(RuntimeValue::Int(length as i32));
+            },
+            opcodes::MONITORENTER | opcodes::MONITOREXIT => {
+                // there is only one thread, so entering and leaving a monitor always succeeds
+                if let RuntimeValue::Null = frame.pop_value_force()? {
+                    return Err(JVMError::null_pointer());
+                }
+                pc += 1;
+            },
+            opcodes::ATHROW => {
+                match frame.pop_value_force()? {
+                    RuntimeValue::Object(exception) => {
+                        debug!("throw {}", exception.borrow().class);
+                        return Err(JVMError::Exception(exception));
+                    },
+                    RuntimeValue::Null => {
+                        return Err(JVMError::null_pointer());
+                    },
+                    value => {
+                        return Err(format!("cannot throw non-object {:?}", value).into());
+                    }
+                }
+            },
+            opcodes::ALOAD0 => {
+                pc += 1;
+                let value = frame.locals[0].clone();
+                frame.push_value(value);
+            },
+            opcodes::ALOAD1 => {
+                pc += 1;
+                let value = frame.locals[1].clone();
+                frame.push_value(value);
+            },
+            opcodes::ALOAD2 => {
+                pc += 1;
+                let value = frame.locals[2].clone();
+                frame.push_value(value);
+            },
+            opcodes::ALOAD3 => {
+                pc += 1;
+                let value = frame.locals[3].clone();
+                frame.push_value(value);
+            },
+            opcodes::TABLESWITCH => {
+                let original_pc = pc;
+
+                pc += 1;
+                let padding = pc % 4;
+                if padding != 0 {
+                    pc += 4 - padding;
+                }
+
+                let default = make_int32(code[pc], code[pc+1], code[pc+2], code[pc+3]);
+                pc += 4;
+                let low = make_int32(code[pc], code[pc+1], code[pc+2], code[pc+3]) as i32;
+                pc += 4;
+                let high = make_int32(code[pc], code[pc+1], code[pc+2], code[pc+3]) as i32;
+                pc += 4;
+
+                // TODO: we don't really need to build this vector, we could just use the index
+                // to directly map into the correct code[] offset
+                let mut offsets = Vec::new();
+                for _ in low..=high {
+                    let offset = make_int32(code[pc], code[pc+1], code[pc+2], code[pc+3]);
+                    pc += 4;
+                    offsets.push(offset);
+                }
+
+                let index = frame.pop_value_force()?;
+
+                match index {
+                    RuntimeValue::Int(i) => {
+                        if i < low || i > high {
+                            pc = (original_pc as i32 + default as i32) as usize;
+                        } else {
+                            let offset = offsets[(i as i64 - low as i64) as usize];
+                            pc = (original_pc as i32 + offset as i32) as usize;
+                        }
+                    },
+                    _ => {
+                        return Err(format!("Invalid index for tableswitch: {:?}", index).into());
+                    }
+                }
+            },
+            // https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-6.html#jvms-6.5.lookupswitch
+            opcodes::LOOKUPSWITCH => {
+                let original_pc = pc;
+
+                pc += 1;
+                let padding = pc % 4;
+                if padding != 0 {
+                    pc += 4 - padding;
+                }
+
+                let default = make_int32(code[pc], code[pc+1], code[pc+2], code[pc+3]) as i32;
+                pc += 4;
+                let pairs = make_int32(code[pc], code[pc+1], code[pc+2], code[pc+3]) as i32;
+                pc += 4;
+
+                let key = match frame.pop_value_force()? {
+                    RuntimeValue::Int(key) => key,
+                    value => return Err(format!("Invalid key for lookupswitch: {:?}", value).into()),
+                };
+
+                let mut target = default;
+                for _ in 0..pairs {
+                    let match_key = make_int32(code[pc], code[pc+1], code[pc+2], code[pc+3]) as i32;
+                    let offset = make_int32(code[pc+4], code[pc+5], code[pc+6], code[pc+7]) as i32;
+                    pc += 8;
+                    if match_key == key {
+                        target = offset;
+                        break;
+                    }
+                }
+                pc = (original_pc as i32 + target) as usize;
+            },
+            opcodes::IFICOMPAREGREATEREQUAL => {
+                pc = do_icompare(frame, pc, make_int16(code[pc+1], code[pc+2]) as i16, |i1, i2| i1 >= i2)?;
+            },
+            opcodes::IFICOMPAREEQUAL => {
+                pc = do_icompare(frame, pc, make_int16(code[pc+1], code[pc+2]) as i16, |i1, i2| i1 == i2)?;
+            },
+            opcodes::IFICOMPARENOTEQUAL => {
+                pc = do_icompare(frame, pc, make_int16(code[pc+1], code[pc+2]) as i16, |i1, i2| i1 != i2)?;
+            },
+            opcodes::IFICOMPARELESS => {
+                pc = do_icompare(frame, pc, make_int16(code[pc+1], code[pc+2]) as i16, |i1, i2| i1 < i2)?;
+            },
+            opcodes::IFICOMPAREGREATER => {
+                pc = do_icompare(frame, pc, make_int16(code[pc+1], code[pc+2]) as i16, |i1, i2| i1 > i2)?;
+            },
+            opcodes::IFICOMPARELESSEQUAL => {
+                pc = do_icompare(frame, pc, make_int16(code[pc+1], code[pc+2]) as i16, |i1, i2| i1 <= i2)?;
+            },
+            opcodes::IFEQUAL => {
+                pc = do_if(frame, pc, make_int16(code[pc+1], code[pc+2]) as i16, |i| i == 0)?;
+            },
+            opcodes::IFNOTEQUAL => {
+                pc = do_if(frame, pc, make_int16(code[pc+1], code[pc+2]) as i16, |i| i != 0)?;
+            },
+            opcodes::IFLESS => {
+                pc = do_if(frame, pc, make_int16(code[pc+1], code[pc+2]) as i16, |i| i < 0)?;
+            },
+            opcodes::IFGREATEREQUAL => {
+                pc = do_if(frame, pc, make_int16(code[pc+1], code[pc+2]) as i16, |i| i >= 0)?;
+            },
+            opcodes::IFGREATER => {
+                pc = do_if(frame, pc, make_int16(code[pc+1], code[pc+2]) as i16, |i| i > 0)?;
+            },
+            opcodes::IFLESSEQUAL => {
+                pc = do_if(frame, pc, make_int16(code[pc+1], code[pc+2]) as i16, |i| i <= 0)?;
+            },
+            opcodes::IFACOMPAREEQUAL | opcodes::IFACOMPARENOTEQUAL => {
+                let value2 = frame.pop_value_force()?;
+                let value1 = frame.pop_value_force()?;
+                let same = value1.same_reference(&value2)?;
+                if same == (code[pc] == opcodes::IFACOMPAREEQUAL) {
+                    pc = branch_target(pc, make_int16(code[pc+1], code[pc+2]) as i16);
+                } else {
+                    pc += 3;
+                }
+            },
+            opcodes::IFNULL | opcodes::IFNONNULL => {
+                let value = frame.pop_value_force()?;
+                let is_null = value.same_reference(&RuntimeValue::Null)?;
+                if is_null == (code[pc] == opcodes::IFNULL) {
+                    pc = branch_target(pc, make_int16(code[pc+1], code[pc+2]) as i16);
+                } else {
+                    pc += 3;
+                }
+            },
+            opcodes::GOTO => {
+                let offset = make_int16(code[pc+1], code[pc+2]);
+                pc = branch_target(pc, offset as i16);
+            },
+            opcodes::GOTOW => {
+                let offset = make_int32(code[pc+1], code[pc+2], code[pc+3], code[pc+4]);
+                pc = (pc as i64 + offset as i32 as i64) as usize;
+            },
+            opcodes::JSR => {
+                check_subroutines_allowed(&class)?;
+                let offset = make_int16(code[pc+1], code[pc+2]);
+                frame.push_value(RuntimeValue::ReturnAddress(pc + 3));
+                pc = branch_target(pc, offset as i16);
+            },
+            opcodes::JSRW => {
+                check_subroutines_allowed(&class)?;
+                let offset = make_int32(code[pc+1], code[pc+2], code[pc+3], code[pc+4]);
+                frame.push_value(RuntimeValue::ReturnAddress(pc + 5));
+                pc = (pc as i64 + offset as i32 as i64) as usize;
+            },
+            opcodes::RET => {
+                check_subroutines_allowed(&class)?;
+                let index = code[pc + 1] as usize;
+                match frame.locals[index] {
+                    RuntimeValue::ReturnAddress(address) => {
+                        pc = address;
+                    },
+                    ref value => {
+                        return Err(format!("ret on local {} that is not a returnAddress: {:?}", index, value).into());
+                    }
+                }
+            },
+            opcodes::ILOAD => {
+                let index = code[pc + 1] as usize;
+                let value = frame.locals[index].clone();
+                frame.push_value(value);
+                pc += 2;
+            },
+            opcodes::ILOAD0 => {
+                pc += 1;
+                let value = frame.locals[0].clone();
+                debug!("  load0: loading value {:?}", value);
+                frame.push_value(value);
+            },
+            opcodes::ILOAD1 => {
+                pc += 1;
+                let value = frame.locals[1].clone();
+                frame.push_value(value);
+            },
+            opcodes::ILOAD2 => {
+                pc += 1;
+                let value = frame.locals[2].clone();
+                frame.push_value(value);
+            },
+            opcodes::ILOAD3 => {
+                pc += 1;
+                let value = frame.locals[3].clone();
+                frame.push_value(value);
+            },
+            opcodes::IADD => {
+                pc += 1;
+                let value = do_iop(frame, |i1,i2| i1.wrapping_add(i2))?;
+                frame.stack.push(value);
+            },
+            opcodes::LADD => {
+                pc += 1;
+                let value = do_lop(frame, |l1,l2| l1.wrapping_add(l2))?;
+                frame.stack.push(value);
+            },
+            opcodes::FADD => {
+                pc += 1;
+                let value = do_fop(frame, |f1,f2| f1 + f2)?;
+                frame.stack.push(value);
+            },
+            opcodes::DADD => {
+                pc += 1;
+                let value = do_dop(frame, |d1,d2| d1 + d2)?;
+                frame.stack.push(value);
+            },
+            opcodes::ISUB => {
+                pc += 1;
+                let value = do_iop(frame, |i1,i2| i1.wrapping_sub(i2))?;
+                frame.stack.push(value);
+            },
+            opcodes::LSUB => {
+                pc += 1;
+                let value = do_lop(frame, |l1,l2| l1.wrapping_sub(l2))?;
+                frame.stack.push(value);
+            },
+            opcodes::FSUB => {
+                pc += 1;
+                let value = do_fop(frame, |f1,f2| f1 - f2)?;
+                frame.stack.push(value);
+            },
+            opcodes::DSUB => {
+                pc += 1;
+                let value = do_dop(frame, |d1,d2| d1 - d2)?;
+                frame.stack.push(value);
+            },
+            opcodes::IMUL => {
+                pc += 1;
+                let value = do_iop(frame, |i1,i2| i1.wrapping_mul(i2))?;
+                frame.stack.push(value);
+            },
+            opcodes::LMUL => {
+                pc += 1;
+                let value = do_lop(frame, |l1,l2| l1.wrapping_mul(l2))?;
+                frame.stack.push(value);
+            },
+            opcodes::FMUL => {
+                pc += 1;
+                let value = do_fop(frame, |f1,f2| f1 * f2)?;
+                frame.stack.push(value);
+            },
+            opcodes::DMUL => {
+                pc += 1;
+                let value = do_dop(frame, |d1,d2| d1 * d2)?;
+                frame.stack.push(value);
+            },
+            opcodes::IDIV => {
+                pc += 1;
+                let value = do_idivide(frame, |i1,i2| i1.wrapping_div(i2))?;
+                frame.stack.push(value);
+            },
+            opcodes::LDIV => {
+                pc += 1;
+                let value = do_ldivide(frame, |l1,l2| l1.wrapping_div(l2))?;
+                frame.stack.push(value);
+            },
+            opcodes::FDIV => {
+                pc += 1;
+                let value = do_fop(frame, |f1,f2| f1 / f2)?;
+                frame.stack.push(value);
+            },
+            opcodes::DDIV => {
+                pc += 1;
+                let value = do_dop(frame, |d1,d2| d1 / d2)?;
+                frame.stack.push(value);
+            },
+            opcodes::IREM => {
+                pc += 1;
+                let value = do_idivide(frame, |i1,i2| i1.wrapping_rem(i2))?;
+                frame.stack.push(value);
+            },
+            opcodes::LREM => {
+                pc += 1;
+                let value = do_ldivide(frame, |l1,l2| l1.wrapping_rem(l2))?;
+                frame.stack.push(value);
+            },
+            opcodes::FREM => {
+                pc += 1;
+                let value = do_fop(frame, |f1,f2| f1 % f2)?;
+                frame.stack.push(value);
+            },
+            opcodes::DREM => {
+                pc += 1;
+                let value = do_dop(frame, |d1,d2| d1 % d2)?;
+                frame.stack.push(value);
+            },
+            opcodes::ISHL => {
+                pc += 1;
+                let value = do_iop(frame, |i1,i2| i1.wrapping_shl(i2 as u32))?;
+                frame.stack.push(value);
+            },
+            opcodes::LSHL => {
+                pc += 1;
+                let value = do_lshift(frame, |l1,distance| l1.wrapping_shl(distance))?;
+                frame.stack.push(value);
+            },
+            opcodes::ISHR => {
+                pc += 1;
+                let value = do_iop(frame, |i1,i2| i1.wrapping_shr(i2 as u32))?;
+                frame.stack.push(value);
+            },
+            opcodes::LSHR => {
+                pc += 1;
+                let value = do_lshift(frame, |l1,distance| l1.wrapping_shr(distance))?;
+                frame.stack.push(value);
+            },
+            opcodes::IUSHR => {
+                pc += 1;
+                let value = do_iop(frame, |i1,i2| (i1 as u32).wrapping_shr(i2 as u32) as i32)?;
+                frame.stack.push(value);
+            },
+            opcodes::LUSHR => {
+                pc += 1;
+                let value = do_lshift(frame, |l1,distance| (l1 as u64).wrapping_shr(distance) as i64)?;
+                frame.stack.push(value);
+            },
+            opcodes::IAND => {
+                pc += 1;
+                let value = do_iop(frame, |i1,i2| i1 & i2)?;
+                frame.stack.push(value);
+            },
+            opcodes::LAND => {
+                pc += 1;
+                let value = do_lop(frame, |l1,l2| l1 & l2)?;
+                frame.stack.push(value);
+            },
+            opcodes::IOR => {
+                pc += 1;
+                let value = do_iop(frame, |i1,i2| i1 | i2)?;
+                frame.stack.push(value);
+            },
+            opcodes::LOR => {
+                pc += 1;
+                let value = do_lop(frame, |l1,l2| l1 | l2)?;
+                frame.stack.push(value);
+            },
+            opcodes::IXOR => {
+                pc += 1;
+                let value = do_iop(frame, |i1,i2| i1 ^ i2)?;
+                frame.stack.push(value);
+            },
+            opcodes::LXOR => {
+                pc += 1;
+                let value = do_lop(frame, |l1,l2| l1 ^ l2)?;
+                frame.stack.push(value);
+            },
+            opcodes::INEG => {
+                pc += 1;
+                let value = RuntimeValue::Int(frame.pop_int()?.wrapping_neg());
+                frame.push_value(value);
+            },
+            opcodes::LNEG => {
+                pc += 1;
+                let value = RuntimeValue::Long(frame.pop_long()?.wrapping_neg());
+                frame.push_value(value);
+            },
+            opcodes::FNEG => {
+                pc += 1;
+                let value = RuntimeValue::Float(-frame.pop_float()?);
+                frame.push_value(value);
+            },
+            opcodes::DNEG => {
+                pc += 1;
+                let value = RuntimeValue::Double(-frame.pop_double()?);
+                frame.push_value(value);
+            },
+            opcodes::I2L => {
+                pc += 1;
+                let value = RuntimeValue::Long(frame.pop_int()? as i64);
+                frame.push_value(value);
+            },
+            opcodes::I2F => {
+                pc += 1;
+                let value = RuntimeValue::Float(frame.pop_int()? as f32);
+                frame.push_value(value);
+            },
+            opcodes::I2D => {
+                pc += 1;
+                let value = RuntimeValue::Double(frame.pop_int()? as f64);
+                frame.push_value(value);
+            },
+            opcodes::L2I => {
+                pc += 1;
+                let value = RuntimeValue::Int(frame.pop_long()? as i32);
+                frame.push_value(value);
+            },
+            opcodes::L2F => {
+                pc += 1;
+                let value = RuntimeValue::Float(frame.pop_long()? as f32);
+                frame.push_value(value);
+            },
+            opcodes::L2D => {
+                pc += 1;
+                let value = RuntimeValue::Double(frame.pop_long()? as f64);
+                frame.push_value(value);
+            },
+            opcodes::F2I => {
+                pc += 1;
+                // float to int casts in rust saturate and turn NaN into 0, just like the jvm
+                let value = RuntimeValue::Int(frame.pop_float()? as i32);
+                frame.push_value(value);
+            },
+            opcodes::F2L => {
+                pc += 1;
+                let value = RuntimeValue::Long(frame.pop_float()? as i64);
+                frame.push_value(value);
+            },
+            opcodes::F2D => {
+                pc += 1;
+                let value = RuntimeValue::Double(frame.pop_float()? as f64);
+                frame.push_value(value);
+            },
+            opcodes::D2I => {
+                pc += 1;
+                let value = RuntimeValue::Int(frame.pop_double()? as i32);
+                frame.push_value(value);
+            },
+            opcodes::D2L => {
+                pc += 1;
+                let value = RuntimeValue::Long(frame.pop_double()? as i64);
+                frame.push_value(value);
+            },
+            opcodes::D2F => {
+                pc += 1;
+                let value = RuntimeValue::Float(frame.pop_double()? as f32);
+                frame.push_value(value);
+            },
+            opcodes::I2B => {
+                pc += 1;
+                let value = RuntimeValue::Int(frame.pop_int()? as i8 as i32);
+                frame.push_value(value);
+            },
+            opcodes::I2C => {
+                pc += 1;
+                let value = RuntimeValue::Int(frame.pop_int()? as u16 as i32);
+                frame.push_value(value);
+            },
+            opcodes::I2S => {
+                pc += 1;
+                let value = RuntimeValue::Int(frame.pop_int()? as i16 as i32);
+                frame.push_value(value);
+            },
+            opcodes::LCMP => {
+                pc += 1;
+                let value2 = frame.pop_long()?;
+                let value1 = frame.pop_long()?;
+                frame.push_value(RuntimeValue::Int(value1.cmp(&value2) as i32));
+            },
+            opcodes::FCMPL => {
+                pc += 1;
+                let value2 = frame.pop_float()?;
+                let value1 = frame.pop_float()?;
+                frame.push_value(compare_floating(value1 as f64, value2 as f64, -1));
+            },
+            opcodes::FCMPG => {
+                pc += 1;
+                let value2 = frame.pop_float()?;
+                let value1 = frame.pop_float()?;
+                frame.push_value(compare_floating(value1 as f64, value2 as f64, 1));
+            },
+            opcodes::DCMPL => {
+                pc += 1;
+                let value2 = frame.pop_double()?;
+                let value1 = frame.pop_double()?;
+                frame.push_value(compare_floating(value1, value2, -1));
+            },
+            opcodes::DCMPG => {
+                pc += 1;
+                let value2 = frame.pop_double()?;
+                let value1 = frame.pop_double()?;
+                frame.push_value(compare_floating(value1, value2, 1));
+            },
+            opcodes::IINC => {
+                let index = code[pc + 1] as usize;
+                let value = frame.locals[index].clone();
+                let inc = code[pc + 2] as i8 as i32;
+                match value {
+                    RuntimeValue::Int(i) => {
+                        frame.locals[index] = RuntimeValue::Int(i.wrapping_add(inc))
+                    },
+                    _ => {
+                        return Err("inc on non-int".to_string().into());
+                    }
+                }
+                pc += 3;
+            },
+            /* the local variable instructions with a two byte index, and iinc with a two byte
+             * constant as well, for methods with more than 256 locals or larger increments
+             * https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-6.html#jvms-6.5.wide
+             */
+            opcodes::WIDE => {
+                let index = make_int16(code[pc+2], code[pc+3]) as usize;
+                match code[pc + 1] {
+                    opcodes::ILOAD | opcodes::LLOAD | opcodes::FLOAD | opcodes::DLOAD | opcodes::ALOAD => {
+                        frame.push_value(frame.load_local(index));
+                        pc += 4;
+                    },
+                    opcodes::ISTORE | opcodes::LSTORE | opcodes::FSTORE | opcodes::DSTORE | opcodes::ASTORE => {
+                        let value = frame.pop_value_force()?;
+                        frame.store_local(index, value);
+                        pc += 4;
+                    },
+                    opcodes::RET => {
+                        check_subroutines_allowed(&class)?;
+                        match frame.locals[index] {
+                            RuntimeValue::ReturnAddress(address) => {
+                                pc = address;
+                            },
+                            ref value => {
+                                return Err(format!("ret on local {} that is not a returnAddress: {:?}", index, value).into());
+                            }
+                        }
+                    },
+                    opcodes::IINC => {
+                        let inc = make_int16(code[pc+4], code[pc+5]) as i16 as i32;
+                        match frame.locals[index] {
+                            RuntimeValue::Int(i) => {
+                                frame.locals[index] = RuntimeValue::Int(i.wrapping_add(inc))
+                            },
+                            _ => {
+                                return Err("inc on non-int".to_string().into());
+                            }
+                        }
+                        pc += 6;
+                    },
+                    opcode => {
+                        return Err(format!("invalid opcode 0x{:x} after wide at pc={}", opcode, pc).into());
+                    }
+                }
+            },
+            opcodes::NEW => {
+                let b1 = code[pc+1] as usize;
+                let b2 = code[pc+2] as usize;
+                let total = (b1 << 8) | b2;
+
+                frame.push_value(create_new_object(constant_pool, jvm, total)?);
+
+                pc += 3;
+            },
+            opcodes::GETFIELD => {
+                let b1 = code[pc+1] as usize;
+                let b2 = code[pc+2] as usize;
+                let total = (b1 << 8) | b2;
+                let objectref = frame.pop_value_force()?;
+                frame.push_value(getfield(constant_pool, jvm, total, objectref)?);
+                pc += 3;
+            },
+            opcodes::PUTFIELD => {
+                let b1 = code[pc+1] as usize;
+                let b2 = code[pc+2] as usize;
+                let total = (b1 << 8) | b2;
+
+                let value = frame.pop_value_force()?;
+                let objectref = frame.pop_value_force()?;
+
+                putfield(constant_pool, jvm, total, objectref, value)?;
+
+                pc += 3;
+            },
+            opcodes::INVOKESPECIAL => {
+                let b1 = code[pc+1] as usize;
+                let b2 = code[pc+2] as usize;
+                let total = (b1 << 8) | b2;
+                pc += 3;
+
+                let invocation = invoke_special(constant_pool, frame, jvm, total)?;
+                if let Some(callee) = complete_invocation(frame, invocation) {
+                    frame.pc = pc;
+                    return Ok(Step::Call(callee));
+                }
+            },
+            opcodes::INVOKESTATIC => {
+                let b1 = code[pc+1] as usize;
+                let b2 = code[pc+2] as usize;
+                let total = (b1 << 8) | b2;
+                pc += 3;
+
+                let invocation = invoke_static(constant_pool, frame, jvm, total)?;
+                if let Some(callee) = complete_invocation(frame, invocation) {
+                    frame.pc = pc;
+                    return Ok(Step::Call(callee));
+                }
+            },
+            opcodes::GETSTATIC => {
+                debug!("Get static");
+                let b1 = code[pc+1] as usize;
+                let b2 = code[pc+2] as usize;
+                let total = (b1 << 8) | b2;
+
+                pc += 2;
+
+                op_getstatic(constant_pool, frame, jvm, total)?;
+
+                pc += 1;
+            },
+            opcodes::PUTSTATIC => {
+                let index = make_int16(code[pc+1], code[pc+2]) as usize;
+                let value = frame.pop_value_force()?;
+                op_putstatic(constant_pool, jvm, index, value)?;
+                pc += 3;
+            },
+            opcodes::INVOKEDYNAMIC => {
+                let b1 = code[pc+1] as usize;
+                let b2 = code[pc+2] as usize;
+                let total = (b1 << 8) | b2;
+
+                let invocation = invoke_dynamic(constant_pool, frame, jvm, total, pc)?;
+                pc += 5;
+                if let Some(callee) = complete_invocation(frame, invocation) {
+                    frame.pc = pc;
+                    return Ok(Step::Call(callee));
+                }
+            },
+            opcodes::INVOKEINTERFACE => {
+                let b1 = code[pc+1] as usize;
+                let b2 = code[pc+2] as usize;
+                let total = (b1 << 8) | b2;
+                // the count and zero operands that follow the index are not needed
+                pc += 5;
+
+                let invocation = invoke_interface(constant_pool, frame, jvm, total)?;
+                if let Some(callee) = complete_invocation(frame, invocation) {
+                    frame.pc = pc;
+                    return Ok(Step::Call(callee));
+                }
+            },
+            opcodes::INVOKEVIRTUAL => {
+                let b1 = code[pc+1] as usize;
+                let b2 = code[pc+2] as usize;
+                let total = (b1 << 8) | b2;
+                pc += 3;
+
+                let invocation = invoke_virtual(constant_pool, frame, jvm, total)?;
+                if let Some(callee) = complete_invocation(frame, invocation) {
+                    frame.pc = pc;
+                    return Ok(Step::Call(callee));
+                }
+            },
+            opcodes::LRETURN | opcodes::FRETURN | opcodes::DRETURN | opcodes::ARETURN => {
+                return Ok(Step::Return(frame.pop_value_force()?));
+            },
+            opcodes::RETURN => {
+                return Ok(Step::Return(RuntimeValue::Void));
+            },
+            opcodes::PUSHRUNTIMECONSTANT => {
+                let index = code[pc+1] as usize;
+                push_runtime_constant(constant_pool, frame, jvm, index)?;
+                pc += 2;
+            },
+            opcodes::PUSHRUNTIMECONSTANTWIDE | opcodes::PUSHRUNTIMECONSTANT2WIDE => {
+                let index = make_int16(code[pc+1], code[pc+2]) as usize;
+                push_runtime_constant(constant_pool, frame, jvm, index)?;
+                pc += 3;
+            },
+            _ => {
+                return Err(format!("Unknown opcode pc={} opcode=0x{:x}", pc, code[pc]).into());
+            }
+        }
+    }
+
+    return Ok(Step::Return(RuntimeValue::Void));
+}
+
+/* push the result of a native method onto the stack of the frame that invoked it. a bytecode
+ * method hands back the frame it is to run in
+ */
+fn complete_invocation(frame: &mut Frame, invocation: Invocation) -> Option<Frame> {
+    match invocation {
+        Invocation::Completed(RuntimeValue::Void) => None,
+        Invocation::Completed(value) => {
+            frame.push_value(value);
+            None
+        },
+        Invocation::Call(callee) => Some(callee),
+    }
+}
+
+// the PrintStream behind System.out (file descriptor 1) or System.err (file descriptor 2)
+fn create_print_stream_object(fd: i32) -> rc::Rc<cell::RefCell<JVMObject>> {
+    let mut fields = HashMap::new();
+    fields.insert("fd".to_string(), RuntimeValue::Int(fd));
+
+    return rc::Rc::new(cell::RefCell::new(JVMObject{
+        class: "java/io/PrintStream".to_string(),
+        fields: fields
+    }));
+}
+
+/* lay out the shortest digits that round trip (as produced by rust's {:e} formatting) the way
+ * Double.toString and Float.toString do: plain decimal notation when 10^-3 <= |value| < 10^7,
+ * otherwise computerized scientific notation such as 1.0E10
+ */
+fn format_java_floating(scientific: &str) -> String {
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((scientific, "0"));
+    let exponent = exponent.parse::<i32>().unwrap_or(0);
+    let negative = mantissa.starts_with('-');
+    let digits = mantissa.chars().filter(|c| c.is_ascii_digit()).collect::<String>();
+
+    let mut out = String::new();
+    if negative {
+        out.push('-');
+    }
+
+    if exponent >= -3 && exponent < 7 {
+        if exponent >= 0 {
+            let integer_length = (exponent + 1) as usize;
+            if digits.len() > integer_length {
+                out.push_str(&digits[..integer_length]);
+                out.push('.');
+                out.push_str(&digits[integer_length..]);
+            } else {
+                out.push_str(&digits);
+                out.push_str(&"0".repeat(integer_length - digits.len()));
+                out.push_str(".0");
+            }
+        } else {
+            out.push_str("0.");
+            out.push_str(&"0".repeat((-exponent - 1) as usize));
+            out.push_str(&digits);
+        }
+    } else {
+        out.push_str(&digits[..1]);
+        out.push('.');
+        if digits.len() > 1 {
+            out.push_str(&digits[1..]);
+        } else {
+            out.push('0');
+        }
+        out.push('E');
+        out.push_str(&exponent.to_string());
+    }
+
+    return out;
+}
+
+fn java_double_to_string(value: f64) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "Infinity".to_string() } else { "-Infinity".to_string() };
+    }
+    if value == 0.0 {
+        return if value.is_sign_negative() { "-0.0".to_string() } else { "0.0".to_string() };
+    }
+
+    return format_java_floating(&format!("{:e}", value));
+}
+
+fn java_float_to_string(value: f32) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "Infinity".to_string() } else { "-Infinity".to_string() };
+    }
+    if value == 0.0 {
+        return if value.is_sign_negative() { "-0.0".to_string() } else { "0.0".to_string() };
+    }
+
+    return format_java_floating(&format!("{:e}", value));
+}
+
+// the text print and println write for a value
+fn print_argument_to_string(arg: &RuntimeValue) -> String {
+    match arg {
+        RuntimeValue::String(s) => s.borrow().to_string(),
+        RuntimeValue::Int(i) => i.to_string(),
+        RuntimeValue::Null => "null".to_string(),
+        RuntimeValue::Long(l) => l.to_string(),
+        RuntimeValue::Float(f) => java_float_to_string(*f),
+        RuntimeValue::Double(d) => java_double_to_string(*d),
+        _ => format!("Unknown value type for println: {:?}", arg),
+    }
+}
+
+/* write text to a PrintStream, which is either System.out or System.err */
+fn print_to_stream(stream: &RuntimeValue, text: &str) -> Result<(), JVMError> {
+    match stream {
+        RuntimeValue::Object(stream) => {
+            match stream.borrow().fields.get("fd") {
+                Some(RuntimeValue::Int(2)) => eprint!("{}", text),
+                _ => print!("{}", text),
+            }
+            return Ok(());
+        },
+        RuntimeValue::Null => {
+            return Err(JVMError::null_pointer());
+        },
+        _ => {
+            return Err(format!("not a print stream: {:?}", stream).into());
+        }
+    }
+}
+
+fn create_java_io_print_stream() -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("println".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let mut text = String::new();
+        for arg in &args[1..] {
+            text.push_str(&print_argument_to_string(arg));
+        }
+        text.push('\n');
+        print_to_stream(&args[0], &text)?;
+
+        return Ok(RuntimeValue::Void);
+    }));
+    methods.insert("print".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        for arg in &args[1..] {
+            print_to_stream(&args[0], &print_argument_to_string(arg))?;
+        }
+
+        return Ok(RuntimeValue::Void);
+    }));
+    // an object is printed as String.valueOf gives it
+    methods.insert(method_key("println", "(Ljava/lang/Object;)V"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        print_to_stream(&args[0], &format!("{}\n", string_value_of(jvm, &args[1], &Descriptor::Object("java/lang/Object".to_string()))?))?;
+        return Ok(RuntimeValue::Void);
+    }));
+    methods.insert(method_key("print", "(Ljava/lang/Object;)V"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        print_to_stream(&args[0], &string_value_of(jvm, &args[1], &Descriptor::Object("java/lang/Object".to_string()))?)?;
+        return Ok(RuntimeValue::Void);
+    }));
+    // booleans and chars are ints on the stack, so their overloads are told apart by descriptor
+    methods.insert(method_key("println", "(Z)V"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        print_to_stream(&args[0], &format!("{}\n", string_value_of(jvm, &args[1], &Descriptor::Boolean)?))?;
+        return Ok(RuntimeValue::Void);
+    }));
+    methods.insert(method_key("println", "(C)V"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        print_to_stream(&args[0], &format!("{}\n", string_value_of(jvm, &args[1], &Descriptor::Char)?))?;
+        return Ok(RuntimeValue::Void);
+    }));
+    methods.insert(method_key("print", "(Z)V"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        print_to_stream(&args[0], &string_value_of(jvm, &args[1], &Descriptor::Boolean)?)?;
+        return Ok(RuntimeValue::Void);
+    }));
+    methods.insert(method_key("print", "(C)V"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        print_to_stream(&args[0], &string_value_of(jvm, &args[1], &Descriptor::Char)?)?;
+        return Ok(RuntimeValue::Void);
+    }));
+
+    let fields = HashMap::new();
+
+    return JVMClass{
+        class: "java/io/PrintStream".to_string(),
+        access_flags: ACC_PUBLIC,
+        major_version: 0,
+        super_class: Some("java/lang/Object".to_string()),
+        interfaces: Vec::new(),
+        source_file: None,
+        constant_pool: rc::Rc::new(Vec::new()),
+        methods: methods,
+        fields: cell::RefCell::new(fields),
+        instance_fields: HashMap::new(),
+        object_fields: HashMap::new(),
+        field_owners: HashMap::new(),
+        bootstrap_methods: Vec::new(),
+        call_sites: cell::RefCell::new(HashMap::new()),
+        dynamic_constants: cell::RefCell::new(HashMap::new()),
+        subtype_cache: cell::RefCell::new(HashMap::new()),
+        initialization: cell::RefCell::new(Initialization::NotStarted),
+    }
+}
+
+/* String(char[] value) and String(char[] value, int offset, int count), which copy the chars
+ * into the string
+ */
+fn string_from_chars(_jvm: &RuntimeConst, args: &[RuntimeValue]) -> Result<RuntimeValue, JVMError> {
+    let chars = char_array_argument(args, 1)?;
+    let chars = if args.len() > 2 {
+        let (offset, count) = (int_argument(args, 2)?, int_argument(args, 3)?);
+        if offset < 0 || count < 0 || offset as usize + count as usize > chars.len() {
+            let message = format!("offset {}, count {}, length {}", offset, count, chars.len());
+            return Err(JVMError::exception("java/lang/StringIndexOutOfBoundsException", Some(message)));
+        }
+        chars[offset as usize..(offset + count) as usize].to_vec()
+    } else {
+        chars
+    };
+    string_argument(args, 0)?.borrow_mut().set(chars);
+    return Ok(RuntimeValue::Void);
+}
+
+// the chars of a char[] argument
+fn char_array_argument(args: &[RuntimeValue], index: usize) -> Result<Vec<u16>, JVMError> {
+    match args.get(index) {
+        Some(RuntimeValue::Array(array)) => match &array.borrow().elements {
+            ArrayElements::Char(chars) => Ok(chars.clone()),
+            _ => Err(format!("expected a char[] argument, not {}", array.borrow().class).into()),
+        },
+        Some(RuntimeValue::Null) => Err(JVMError::null_pointer()),
+        value => Err(format!("expected a char[] argument, not {:?}", value).into()),
+    }
+}
+
+// the java/lang/Class objects, which are made by RuntimeConst::class_object
+fn create_java_lang_class() -> JVMClass {
+    let mut methods = HashMap::new();
+    // the binary name, such as java.lang.String, [I or int
+    methods.insert(method_key("getName", "()Ljava/lang/String;"), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(new_string(&external_class_name(&class_of(&args[0])?)));
+    }));
+    let mut class = create_native_class("java/lang/Class", "java/lang/Object", methods);
+    class.access_flags = ACC_PUBLIC | ACC_FINAL;
+    return class;
+}
+
+fn create_java_lang_system() -> JVMClass {
+    let mut fields = HashMap::new();
+
+    fields.insert("out".to_string(), RuntimeValue::Object(create_print_stream_object(1)));
+    fields.insert("err".to_string(), RuntimeValue::Object(create_print_stream_object(2)));
+
+    let mut methods = HashMap::new();
+    methods.insert(method_key("identityHashCode", "(Ljava/lang/Object;)I"), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::Int(args[0].identity_hash_code()));
+    }));
+
+    return JVMClass{
+        class: "java/lang/System".to_string(),
+        access_flags: ACC_PUBLIC,
+        major_version: 0,
+        super_class: Some("java/lang/Object".to_string()),
+        interfaces: Vec::new(),
+        source_file: None,
+        constant_pool: rc::Rc::new(Vec::new()),
+        methods: methods,
+        fields: cell::RefCell::new(fields),
+        instance_fields: HashMap::new(),
+        object_fields: HashMap::new(),
+        field_owners: HashMap::new(),
+        bootstrap_methods: Vec::new(),
+        call_sites: cell::RefCell::new(HashMap::new()),
+        dynamic_constants: cell::RefCell::new(HashMap::new()),
+        subtype_cache: cell::RefCell::new(HashMap::new()),
+        initialization: cell::RefCell::new(Initialization::NotStarted),
+    };
+}
+
+/* local variables start out with the zero value of their type. the type of a local is only
+ * known if the class was compiled with a LocalVariableTable (javac -g), otherwise it starts out null
+ */
+fn create_frame(class: rc::Rc<JVMClass>, method: rc::Rc<MethodInfo>) -> Result<Frame, JVMError> {
+    if let Some(AttributeKind::Code { max_stack: _, max_locals, code: _, exception_table: _, attributes }) = lookup_code_attribute(&method) {
+        let mut locals = Vec::new();
+
+        for _i in 0..*max_locals {
+            locals.push(RuntimeValue::Null);
+        }
+
+        for attribute in attributes {
+            if let AttributeKind::LocalVariableTable { local_variable_table } = attribute {
+                for entry in local_variable_table.iter().rev() {
+                    if let Some(descriptor) = lookup_utf8_constant(&class.constant_pool, entry.descriptor_index as usize) {
+                        let value = default_value(&parse_type_descriptor(descriptor)?);
+                        let index = entry.index as usize;
+                        if index + (if value.is_category2() { 2 } else { 1 }) <= locals.len() {
+                            locals[index] = value;
+                        }
+                    }
+                }
+            }
+        }
+
+        return Ok(Frame{
+            class: class.clone(),
+            method: method.clone(),
+            pc: 0,
+            stack: Vec::new(),
+            locals,
+        })
+    }
+
+    return Err("no code attribute".to_string().into());
+}
+
+/* lay out the arguments in the local variable array of a new frame, where a long or double
+ * argument takes up two slots
+ */
+fn create_frame_with_arguments(class: rc::Rc<JVMClass>, method: rc::Rc<MethodInfo>, arguments: Vec<RuntimeValue>) -> Result<Frame, JVMError> {
+    let mut frame = create_frame(class, method)?;
+
+    let mut index = 0;
+    for argument in arguments {
+        let size = if argument.is_category2() { 2 } else { 1 };
+        if index + size > frame.locals.len() {
+            return Err(format!("too many arguments for {} local variables", frame.locals.len()).into());
+        }
+        frame.store_local(index, argument);
+        index += size;
+    }
+
+    return Ok(frame);
+}
+
+fn create_java_lang_object() -> JVMClass {
+    let mut fields = HashMap::new();
+    let mut methods = HashMap::new();
+
+    methods.insert("<init>".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, _args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::Void);
+    }));
+    methods.insert(method_key("hashCode", "()I"), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::Int(args[0].identity_hash_code()));
+    }));
+    methods.insert(method_key("equals", "(Ljava/lang/Object;)Z"), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::Int(if args[0].same_reference(&args[1])? { 1 } else { 0 }));
+    }));
+    // the name of the class and the hash code in hex, which is whatever hashCode returns
+    methods.insert(method_key("toString", "()Ljava/lang/String;"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let hash = match call_virtual(jvm, &args[0], "hashCode", "()I", Vec::new())? {
+            Some(RuntimeValue::Int(hash)) => hash,
+            _ => args[0].identity_hash_code(),
+        };
+        return Ok(new_string(&format!("{}@{:x}", external_class_name(&args[0].class_name().unwrap_or_default()), hash)));
+    }));
+    methods.insert(method_key("getClass", "()Ljava/lang/Class;"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(jvm.class_object(&args[0].class_name().unwrap_or_default()));
+    }));
+    /* a shallow copy of an array, or of an object whose class implements Cloneable
+     * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/Object.html#clone()
+     */
+    methods.insert(method_key("clone", "()Ljava/lang/Object;"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match &args[0] {
+            RuntimeValue::Array(array) => {
+                return Ok(RuntimeValue::Array(rc::Rc::new(cell::RefCell::new(array.borrow().clone()))));
+            },
+            RuntimeValue::Object(object) if jvm.is_assignable(&object.borrow().class, "java/lang/Cloneable") => {
+                let object = object.borrow();
+                return Ok(RuntimeValue::Object(rc::Rc::new(cell::RefCell::new(JVMObject{
+                    class: object.class.clone(),
+                    fields: object.fields.clone(),
+                }))));
+            },
+            value => {
+                let class_name = external_class_name(&value.class_name().unwrap_or_default());
+                return Err(JVMError::exception("java/lang/CloneNotSupportedException", Some(class_name)));
+            }
+        }
+    }));
+    /* there is only one thread, so there is never another thread to wake up or to wait for. the
+     * monitors are not tracked either, so these do not check that the thread owns the monitor
+     */
+    for name in ["notify", "notifyAll"] {
+        methods.insert(method_key(name, "()V"), JVMMethod::Native(|_jvm: &RuntimeConst, _args: &[RuntimeValue]| {
+            return Ok(RuntimeValue::Void);
+        }));
+    }
+    // waiting without a timeout would never end, so it wakes up right away, which wait may do spuriously
+    methods.insert(method_key("wait", "()V"), JVMMethod::Native(|_jvm: &RuntimeConst, _args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::Void);
+    }));
+    methods.insert(method_key("wait", "(J)V"), JVMMethod::Native(object_wait));
+    methods.insert(method_key("wait", "(JI)V"), JVMMethod::Native(object_wait));
+
+    return JVMClass{
+        class: "java/lang/Object".to_string(),
+        access_flags: ACC_PUBLIC,
+        major_version: 0,
+        super_class: None,
+        interfaces: Vec::new(),
+        source_file: None,
+        constant_pool: rc::Rc::new(Vec::new()),
+        methods: methods,
+        fields: cell::RefCell::new(fields),
+        instance_fields: HashMap::new(),
+        object_fields: HashMap::new(),
+        field_owners: HashMap::new(),
+        bootstrap_methods: Vec::new(),
+        call_sites: cell::RefCell::new(HashMap::new()),
+        dynamic_constants: cell::RefCell::new(HashMap::new()),
+        subtype_cache: cell::RefCell::new(HashMap::new()),
+        initialization: cell::RefCell::new(Initialization::NotStarted),
+    };
+}
+
+/* wait(long timeoutMillis) and wait(long timeoutMillis, int nanos), which sleep for the timeout as
+ * nothing can notify the thread. a timeout of 0 waits forever, which wakes up right away like wait()
+ */
+fn object_wait(_jvm: &RuntimeConst, args: &[RuntimeValue]) -> Result<RuntimeValue, JVMError> {
+    let millis = match args.get(1) {
+        Some(RuntimeValue::Long(millis)) => *millis,
+        value => return Err(format!("expected a long timeout, not {:?}", value).into()),
+    };
+    if millis < 0 {
+        return Err(JVMError::exception("java/lang/IllegalArgumentException", Some("timeout value is negative".to_string())));
+    }
+    let nanos = if args.len() > 2 { int_argument(args, 2)? } else { 0 };
+    if !(0..=999999).contains(&nanos) {
+        return Err(JVMError::exception("java/lang/IllegalArgumentException", Some("nanosecond timeout value out of range".to_string())));
+    }
+    let millis = if nanos > 0 && millis < i64::MAX { millis + 1 } else { millis };
+    if millis > 0 {
+        std::thread::sleep(std::time::Duration::from_millis(millis as u64));
+    }
+    return Ok(RuntimeValue::Void);
+}
+
+// a string a native gets as an argument, such as the receiver of a method of String
+fn string_argument(args: &[RuntimeValue], index: usize) -> Result<rc::Rc<cell::RefCell<JVMString>>, JVMError> {
+    match args.get(index) {
+        Some(RuntimeValue::String(string)) => Ok(string.clone()),
+        Some(RuntimeValue::Null) => Err(JVMError::null_pointer()),
+        value => Err(format!("expected a string argument, not {:?}", value).into()),
+    }
+}
+
+/* the chars of a CharSequence argument. String uses the toString of a CharSequence that is not
+ * a string, as does this
+ */
+fn char_sequence_argument(jvm: &RuntimeConst, args: &[RuntimeValue], index: usize) -> Result<Vec<u16>, JVMError> {
+    match args.get(index) {
+        Some(sequence @ RuntimeValue::Object(_)) => {
+            match call_virtual(jvm, sequence, "toString", "()Ljava/lang/String;", Vec::new())? {
+                Some(RuntimeValue::String(string)) => Ok(string.borrow().chars().into_owned()),
+                Some(RuntimeValue::Null) => Err(JVMError::null_pointer()),
+                value => Err(format!("toString of a CharSequence returned {:?}", value).into()),
+            }
+        },
+        _ => Ok(string_argument(args, index)?.borrow().chars().into_owned()),
+    }
+}
+
+// a char or code point argument as UTF-16, which takes two chars for a supplementary character
+fn code_point_chars(code_point: i32) -> Vec<u16> {
+    match char::from_u32(code_point as u32) {
+        Some(c) => c.encode_utf16(&mut [0; 2]).to_vec(),
+        None => vec![code_point as u16],
+    }
+}
+
+fn boolean_value(value: bool) -> RuntimeValue {
+    return RuntimeValue::Int(if value { 1 } else { 0 });
+}
+
+// what Character.toUpperCase and toLowerCase do with a char, which stays the same unless it maps to a single char
+fn map_char(c: u16, map: fn(char) -> String) -> u16 {
+    let mapped: Vec<u16> = match char::from_u32(c as u32) {
+        Some(c) => map(c).encode_utf16().collect(),
+        None => return c,
+    };
+    return if mapped.len() == 1 { mapped[0] } else { c };
+}
+
+/* a string mapped to upper or lower case. the string itself if that changes nothing, as
+ * String.toUpperCase and toLowerCase return it then
+ */
+fn map_string(string: &rc::Rc<cell::RefCell<JVMString>>, map: fn(&str) -> String) -> RuntimeValue {
+    let mapped: Vec<u16> = map(&string.borrow().to_string()).encode_utf16().collect();
+    if mapped == *string.borrow().chars() {
+        return RuntimeValue::String(string.clone());
+    }
+    return new_string_of_chars(mapped);
+}
+
+/* String.split for the regular expressions that match themselves, which are those without
+ * metacharacters and a single escaped character that is not a letter or digit. hotspot splits
+ * on those without compiling the expression as well
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/String.html#split(java.lang.String,int)
+ */
+fn split_string(string: &JVMString, regex: &JVMString, limit: i32) -> Result<Vec<Vec<u16>>, JVMError> {
+    let regex_text = regex.to_string();
+    let literal = match regex_text.strip_prefix('\\') {
+        Some(escaped) if escaped.chars().count() == 1 && !escaped.chars().all(|c| c.is_ascii_alphanumeric()) => escaped.to_string(),
+        _ if !regex_text.chars().any(|c| ".$|()[{^?*+\\".contains(c)) => regex_text.clone(),
+        _ => return Err(format!("regular expressions are not supported: {}", regex_text).into()),
+    };
+    let delimiter: Vec<u16> = literal.encode_utf16().collect();
+    let chars = string.chars();
+    if delimiter.is_empty() {
+        return Err(format!("regular expressions are not supported: {}", regex_text).into());
+    }
+
+    let mut parts = Vec::new();
+    let mut offset = 0;
+    loop {
+        let next = string.index_of(&delimiter, offset as i32);
+        if next < 0 {
+            break;
+        }
+        if limit > 0 && parts.len() as i32 == limit - 1 {
+            break;
+        }
+        parts.push(chars[offset..next as usize].to_vec());
+        offset = next as usize + delimiter.len();
+    }
+
+    // without a match the result is the string itself
+    if offset == 0 {
+        return Ok(vec![chars.to_vec()]);
+    }
+    parts.push(chars[offset..].to_vec());
+    if limit == 0 {
+        while parts.last().map_or(false, |part| part.is_empty()) {
+            parts.pop();
+        }
+    }
+    return Ok(parts);
+}
+
+/* java/lang/String, whose values are RuntimeValue::String rather than objects with fields. new
+ * makes an empty string that the constructors fill in
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/String.html
+ */
+fn create_java_lang_string() -> JVMClass {
+    /* javac calls valueOf in some string concatenations. the overloads are told apart by their
+     * full descriptor, since their arguments look the same on the stack
+     */
+    let mut methods = HashMap::new();
+    methods.insert(method_key("valueOf", "(Ljava/lang/Object;)Ljava/lang/String;"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(new_string(&string_value_of(jvm, &args[0], &Descriptor::Object("java/lang/Object".to_string()))?));
+    }));
+    methods.insert(method_key("valueOf", "(Z)Ljava/lang/String;"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(new_string(&string_value_of(jvm, &args[0], &Descriptor::Boolean)?));
+    }));
+    methods.insert(method_key("valueOf", "(C)Ljava/lang/String;"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(new_string(&string_value_of(jvm, &args[0], &Descriptor::Char)?));
+    }));
+    for descriptor in ["(I)Ljava/lang/String;", "(J)Ljava/lang/String;", "(F)Ljava/lang/String;", "(D)Ljava/lang/String;"] {
+        methods.insert(method_key("valueOf", descriptor), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+            return Ok(new_string(&string_value_of(jvm, &args[0], &Descriptor::Int)?));
+        }));
+    }
+    for descriptor in ["([C)Ljava/lang/String;", "([CII)Ljava/lang/String;"] {
+        let copy_value_of = JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+            let string = new_string("");
+            let mut arguments = vec![string.clone()];
+            arguments.extend_from_slice(args);
+            string_from_chars(jvm, &arguments)?;
+            return Ok(string);
+        });
+        methods.insert(method_key("valueOf", descriptor), copy_value_of.clone());
+        methods.insert(method_key("copyValueOf", descriptor), copy_value_of);
+    }
+
+    methods.insert(method_key("<init>", "()V"), JVMMethod::Native(|_jvm: &RuntimeConst, _args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::Void);
+    }));
+    methods.insert(method_key("<init>", "(Ljava/lang/String;)V"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let chars = char_sequence_argument(jvm, args, 1)?;
+        string_argument(args, 0)?.borrow_mut().set(chars);
+        return Ok(RuntimeValue::Void);
+    }));
+    methods.insert(method_key("<init>", "([C)V"), JVMMethod::Native(string_from_chars));
+    for builder in ["java/lang/StringBuilder", "java/lang/StringBuffer"] {
+        methods.insert(method_key("<init>", &format!("(L{};)V", builder)), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+            let chars = builder_argument(args, 1)?.borrow().chars().into_owned();
+            string_argument(args, 0)?.borrow_mut().set(chars);
+            return Ok(RuntimeValue::Void);
+        }));
+    }
+    methods.insert(method_key("<init>", "([CII)V"), JVMMethod::Native(string_from_chars));
+    // bytes are decoded as UTF-8, the default charset, with malformed input replaced
+    methods.insert(method_key("<init>", "([B)V"), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let bytes = match args.get(1) {
+            Some(RuntimeValue::Array(array)) => match &array.borrow().elements {
+                ArrayElements::Byte(bytes) => bytes.iter().map(|byte| *byte as u8).collect::<Vec<_>>(),
+                _ => return Err("String(byte[]) called without a byte[]".to_string().into()),
+            },
+            Some(RuntimeValue::Null) => return Err(JVMError::null_pointer()),
+            value => return Err(format!("String(byte[]) called with {:?}", value).into()),
+        };
+        let chars = String::from_utf8_lossy(&bytes).encode_utf16().collect();
+        string_argument(args, 0)?.borrow_mut().set(chars);
+        return Ok(RuntimeValue::Void);
+    }));
+
+    methods.insert("length".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::Int(string_argument(args, 0)?.borrow().length() as i32));
+    }));
+    methods.insert("isEmpty".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(boolean_value(string_argument(args, 0)?.borrow().length() == 0));
+    }));
+    methods.insert("charAt".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::Int(string_argument(args, 0)?.borrow().char_at(int_argument(args, 1)?)? as i32));
+    }));
+    methods.insert("equals".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let equal = match &args[1] {
+            RuntimeValue::String(other) => *string_argument(args, 0)?.borrow() == *other.borrow(),
+            _ => false,
+        };
+        return Ok(boolean_value(equal));
+    }));
+    methods.insert("equalsIgnoreCase".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let this = string_argument(args, 0)?;
+        let equal = match &args[1] {
+            RuntimeValue::String(other) => {
+                let (this, other) = (this.borrow(), other.borrow());
+                this.length() == other.length() && this.chars().iter().zip(other.chars().iter()).all(|(a, b)| {
+                    let (upper_a, upper_b) = (map_char(*a, |c| c.to_uppercase().collect()), map_char(*b, |c| c.to_uppercase().collect()));
+                    a == b || upper_a == upper_b || map_char(upper_a, |c| c.to_lowercase().collect()) == map_char(upper_b, |c| c.to_lowercase().collect())
+                })
+            },
+            _ => false,
+        };
+        return Ok(boolean_value(equal));
+    }));
+    methods.insert("hashCode".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::Int(string_argument(args, 0)?.borrow().hash_code()));
+    }));
+    methods.insert("compareTo".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let (this, other) = (string_argument(args, 0)?, string_argument(args, 1)?);
+        let result = this.borrow().compare_to(&other.borrow());
+        return Ok(RuntimeValue::Int(result));
+    }));
+    // indexOf(int ch), indexOf(int ch, int fromIndex), indexOf(String str) and indexOf(String str, int fromIndex)
+    methods.insert("indexOf".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let target = match args.get(1) {
+            Some(RuntimeValue::Int(code_point)) => code_point_chars(*code_point),
+            _ => char_sequence_argument(jvm, args, 1)?,
+        };
+        let from = if args.len() > 2 { int_argument(args, 2)? } else { 0 };
+        return Ok(RuntimeValue::Int(string_argument(args, 0)?.borrow().index_of(&target, from)));
+    }));
+    methods.insert("lastIndexOf".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let target = match args.get(1) {
+            Some(RuntimeValue::Int(code_point)) => code_point_chars(*code_point),
+            _ => char_sequence_argument(jvm, args, 1)?,
+        };
+        let this = string_argument(args, 0)?;
+        let from = if args.len() > 2 { int_argument(args, 2)? } else { this.borrow().length() as i32 };
+        let index = this.borrow().last_index_of(&target, from);
+        return Ok(RuntimeValue::Int(index));
+    }));
+    methods.insert("contains".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let target = char_sequence_argument(jvm, args, 1)?;
+        return Ok(boolean_value(string_argument(args, 0)?.borrow().index_of(&target, 0) >= 0));
+    }));
+    methods.insert("startsWith".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let prefix = char_sequence_argument(jvm, args, 1)?;
+        let offset = if args.len() > 2 { int_argument(args, 2)? } else { 0 };
+        let this = string_argument(args, 0)?;
+        let this = this.borrow();
+        let starts = offset >= 0 && (offset as usize) <= this.length() && this.chars()[offset as usize..].starts_with(&prefix);
+        return Ok(boolean_value(starts));
+    }));
+    methods.insert("endsWith".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let suffix = char_sequence_argument(jvm, args, 1)?;
+        return Ok(boolean_value(string_argument(args, 0)?.borrow().chars().ends_with(&suffix)));
+    }));
+    // substring(int beginIndex) and substring(int beginIndex, int endIndex)
+    for name in ["substring", "subSequence"] {
+        methods.insert(name.to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+            let this = string_argument(args, 0)?;
+            let begin = int_argument(args, 1)?;
+            let end = if args.len() > 2 { int_argument(args, 2)? } else { this.borrow().length() as i32 };
+            if begin == 0 && end == this.borrow().length() as i32 {
+                return Ok(RuntimeValue::String(this));
+            }
+            let substring = this.borrow().substring(begin, end)?;
+            return Ok(RuntimeValue::String(rc::Rc::new(cell::RefCell::new(substring))));
+        }));
+    }
+    methods.insert("concat".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let (this, other) = (string_argument(args, 0)?, string_argument(args, 1)?);
+        if other.borrow().length() == 0 {
+            return Ok(RuntimeValue::String(this));
+        }
+        let mut result = this.borrow().clone();
+        result.push_str(&other.borrow());
+        return Ok(RuntimeValue::String(rc::Rc::new(cell::RefCell::new(result))));
+    }));
+    // replace(char oldChar, char newChar) and replace(CharSequence target, CharSequence replacement)
+    methods.insert("replace".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let this = string_argument(args, 0)?;
+        let (target, replacement) = match (&args[1], &args[2]) {
+            (RuntimeValue::Int(target), RuntimeValue::Int(replacement)) => (vec![*target as u16], vec![*replacement as u16]),
+            _ => (char_sequence_argument(jvm, args, 1)?, char_sequence_argument(jvm, args, 2)?),
+        };
+        if this.borrow().index_of(&target, 0) < 0 {
+            return Ok(RuntimeValue::String(this));
+        }
+
+        let this = this.borrow();
+        let mut chars = Vec::new();
+        let mut offset = 0;
+        loop {
+            let next = this.index_of(&target, offset as i32);
+            if next < 0 {
+                break;
+            }
+            chars.extend_from_slice(&this.chars()[offset..next as usize]);
+            chars.extend_from_slice(&replacement);
+            // an empty target matches between every two chars
+            offset = next as usize + target.len();
+            if target.is_empty() {
+                if offset >= this.length() {
+                    offset = this.length();
+                    break;
+                }
+                chars.push(this.chars()[offset]);
+                offset += 1;
+            }
+        }
+        chars.extend_from_slice(&this.chars()[offset..]);
+        return Ok(new_string_of_chars(chars));
+    }));
+    // trim removes the chars up to and including space from both ends
+    methods.insert("trim".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let this = string_argument(args, 0)?;
+        let (begin, end) = {
+            let chars = this.borrow();
+            let chars = chars.chars();
+            let begin = chars.iter().position(|c| *c > ' ' as u16).unwrap_or(chars.len());
+            let end = chars.iter().rposition(|c| *c > ' ' as u16).map_or(begin, |end| end + 1);
+            (begin, end)
+        };
+        if begin == 0 && end == this.borrow().length() {
+            return Ok(RuntimeValue::String(this));
+        }
+        let trimmed = this.borrow().substring(begin as i32, end as i32)?;
+        return Ok(RuntimeValue::String(rc::Rc::new(cell::RefCell::new(trimmed))));
+    }));
+    methods.insert("toUpperCase".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(map_string(&string_argument(args, 0)?, |s| s.to_uppercase()));
+    }));
+    methods.insert("toLowerCase".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(map_string(&string_argument(args, 0)?, |s| s.to_lowercase()));
+    }));
+    methods.insert("repeat".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let this = string_argument(args, 0)?;
+        let count = int_argument(args, 1)?;
+        if count < 0 {
+            return Err(JVMError::exception("java/lang/IllegalArgumentException", Some(format!("count is negative: {}", count))));
+        }
+        if count == 1 {
+            return Ok(RuntimeValue::String(this));
+        }
+        let mut result = JVMString::new(Vec::new());
+        for _ in 0..count {
+            result.push_str(&this.borrow());
+        }
+        return Ok(RuntimeValue::String(rc::Rc::new(cell::RefCell::new(result))));
+    }));
+    methods.insert("toCharArray".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let chars = string_argument(args, 0)?.borrow().chars().to_vec();
+        return Ok(RuntimeValue::Array(rc::Rc::new(cell::RefCell::new(JVMArray{
+            class: "[C".to_string(),
+            elements: ArrayElements::Char(chars),
+        }))));
+    }));
+    // split(String regex) and split(String regex, int limit)
+    methods.insert("split".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let (this, regex) = (string_argument(args, 0)?, string_argument(args, 1)?);
+        let limit = if args.len() > 2 { int_argument(args, 2)? } else { 0 };
+        let parts = split_string(&this.borrow(), &regex.borrow(), limit)?;
+        return Ok(RuntimeValue::Array(rc::Rc::new(cell::RefCell::new(JVMArray{
+            class: "[Ljava/lang/String;".to_string(),
+            elements: ArrayElements::Reference(parts.into_iter().map(new_string_of_chars).collect()),
+        }))));
+    }));
+    methods.insert("intern".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(jvm.intern(&string_argument(args, 0)?));
+    }));
+    methods.insert("toString".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::String(string_argument(args, 0)?));
+    }));
+
+    return JVMClass{
+        class: "java/lang/String".to_string(),
+        access_flags: ACC_PUBLIC,
+        major_version: 0,
+        super_class: Some("java/lang/Object".to_string()),
+        interfaces: vec!["java/io/Serializable".to_string(), "java/lang/Comparable".to_string(), "java/lang/CharSequence".to_string()],
+        source_file: None,
+        constant_pool: rc::Rc::new(Vec::new()),
+        methods: methods,
+        fields: cell::RefCell::new(HashMap::new()),
+        instance_fields: HashMap::new(),
+        object_fields: HashMap::new(),
+        field_owners: HashMap::new(),
+        bootstrap_methods: Vec::new(),
+        call_sites: cell::RefCell::new(HashMap::new()),
+        dynamic_constants: cell::RefCell::new(HashMap::new()),
+        subtype_cache: cell::RefCell::new(HashMap::new()),
+        initialization: cell::RefCell::new(Initialization::NotStarted),
+    };
+}
+
+/* the contents of a StringBuilder or StringBuffer, which is a string in its "value" field that
+ * only the natives get to see and change
+ */
+fn builder_argument(args: &[RuntimeValue], index: usize) -> Result<rc::Rc<cell::RefCell<JVMString>>, JVMError> {
+    match args.get(index) {
+        Some(RuntimeValue::Object(object)) => match object.borrow().fields.get("value") {
+            Some(RuntimeValue::String(value)) => Ok(value.clone()),
+            _ => Err(format!("{} has no contents", object.borrow().class).into()),
+        },
+        Some(RuntimeValue::Null) => Err(JVMError::null_pointer()),
+        value => Err(format!("expected a string builder, not {:?}", value).into()),
+    }
+}
+
+fn set_builder_contents(builder: &RuntimeValue, contents: JVMString) -> Result<RuntimeValue, JVMError> {
+    match builder {
+        RuntimeValue::Object(object) => {
+            object.borrow_mut().fields.insert("value".to_string(), RuntimeValue::String(rc::Rc::new(cell::RefCell::new(contents))));
+            return Ok(RuntimeValue::Void);
+        },
+        value => Err(format!("expected a string builder, not {:?}", value).into()),
+    }
+}
+
+/* the text append and insert add for a value, as String.valueOf gives it. the type tells apart
+ * the primitives that are all ints on the stack
+ */
+fn builder_text(jvm: &RuntimeConst, value: &RuntimeValue, kind: &Descriptor) -> Result<JVMString, JVMError> {
+    match (value, kind) {
+        (RuntimeValue::String(string), _) => Ok(string.borrow().clone()),
+        (RuntimeValue::Int(c), Descriptor::Char) => Ok(JVMString::new(vec![*c as u16])),
+        (value, kind) => Ok(JVMString::from(string_value_of(jvm, value, kind)?.as_str())),
+    }
+}
+
+fn builder_append(jvm: &RuntimeConst, args: &[RuntimeValue], kind: &Descriptor) -> Result<RuntimeValue, JVMError> {
+    let text = builder_text(jvm, &args[1], kind)?;
+    builder_argument(args, 0)?.borrow_mut().push_str(&text);
+    return Ok(args[0].clone());
+}
+
+fn builder_insert(jvm: &RuntimeConst, args: &[RuntimeValue], kind: &Descriptor) -> Result<RuntimeValue, JVMError> {
+    let builder = builder_argument(args, 0)?;
+    let offset = int_argument(args, 1)?;
+    let length = builder.borrow().length();
+    if offset < 0 || offset as usize > length {
+        return Err(JVMError::exception("java/lang/StringIndexOutOfBoundsException", Some(format!("offset {}, length {}", offset, length))));
+    }
+    let text = builder_text(jvm, &args[2], kind)?;
+    builder.borrow_mut().insert(offset as usize, &text);
+    return Ok(args[0].clone());
+}
+
+// an index into the contents of a builder
+fn check_index(index: i32, length: usize) -> Result<(), JVMError> {
+    if index < 0 || index as usize >= length {
+        return Err(JVMError::exception("java/lang/StringIndexOutOfBoundsException", Some(format!("index {}, length {}", index, length))));
+    }
+    return Ok(());
+}
+
+// the part of something appended, which must lie within its length
+fn check_range(start: i32, end: i32, length: usize) -> Result<(), JVMError> {
+    if start < 0 || start > end || end as usize > length {
+        let message = format!("start {}, end {}, length {}", start, end, length);
+        return Err(JVMError::exception("java/lang/IndexOutOfBoundsException", Some(message)));
+    }
+    return Ok(());
+}
+
+/* java/lang/StringBuilder and java/lang/StringBuffer, which only differ in StringBuffer being
+ * synchronized. the methods that return the builder itself are declared to return its own class,
+ * so both get their own descriptors. appending to the contents grows them like a Vec does
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/StringBuilder.html
+ */
+fn create_string_builder(name: &str) -> JVMClass {
+    let returning_self = |parameters: &str| format!("({})L{};", parameters, name);
+    let mut methods = HashMap::new();
+
+    methods.insert(method_key("<init>", "()V"), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return set_builder_contents(&args[0], JVMString::new(Vec::new()));
+    }));
+    // the capacity is only checked, the contents grow as needed anyway
+    methods.insert(method_key("<init>", "(I)V"), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let capacity = int_argument(args, 1)?;
+        if capacity < 0 {
+            return Err(JVMError::exception("java/lang/NegativeArraySizeException", Some(capacity.to_string())));
+        }
+        return set_builder_contents(&args[0], JVMString::new(Vec::new()));
+    }));
+    methods.insert(method_key("<init>", "(Ljava/lang/String;)V"), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let contents = string_argument(args, 1)?.borrow().clone();
+        return set_builder_contents(&args[0], contents);
+    }));
+    methods.insert(method_key("<init>", "(Ljava/lang/CharSequence;)V"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let contents = JVMString::new(char_sequence_argument(jvm, args, 1)?);
+        return set_builder_contents(&args[0], contents);
+    }));
+
+    // append(String), append(Object), append(CharSequence), append(int) and the others that are not ints on the stack
+    methods.insert("append".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return builder_append(jvm, args, &Descriptor::Int);
+    }));
+    methods.insert(method_key("append", &returning_self("Z")), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return builder_append(jvm, args, &Descriptor::Boolean);
+    }));
+    methods.insert(method_key("append", &returning_self("C")), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return builder_append(jvm, args, &Descriptor::Char);
+    }));
+    methods.insert(method_key("append", &returning_self("[C")), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let chars = char_array_argument(args, 1)?;
+        builder_argument(args, 0)?.borrow_mut().push_str(&JVMString::new(chars));
+        return Ok(args[0].clone());
+    }));
+    methods.insert(method_key("append", &returning_self("[CII")), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let chars = char_array_argument(args, 1)?;
+        let (offset, length) = (int_argument(args, 2)?, int_argument(args, 3)?);
+        check_range(offset, offset.wrapping_add(length), chars.len())?;
+        let chars = chars[offset as usize..(offset + length) as usize].to_vec();
+        builder_argument(args, 0)?.borrow_mut().push_str(&JVMString::new(chars));
+        return Ok(args[0].clone());
+    }));
+    // a null sequence appends part of "null"
+    methods.insert(method_key("append", &returning_self("Ljava/lang/CharSequence;II")), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let chars = match args[1] {
+            RuntimeValue::Null => "null".encode_utf16().collect(),
+            _ => char_sequence_argument(jvm, args, 1)?,
+        };
+        let (start, end) = (int_argument(args, 2)?, int_argument(args, 3)?);
+        check_range(start, end, chars.len())?;
+        let chars = chars[start as usize..end as usize].to_vec();
+        builder_argument(args, 0)?.borrow_mut().push_str(&JVMString::new(chars));
+        return Ok(args[0].clone());
+    }));
+    methods.insert("appendCodePoint".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let code_point = int_argument(args, 1)?;
+        if !(0..=0x10ffff).contains(&code_point) {
+            return Err(JVMError::exception("java/lang/IllegalArgumentException", Some(format!("Not a valid Unicode code point: 0x{:X}", code_point))));
+        }
+        let builder = builder_argument(args, 0)?;
+        for c in code_point_chars(code_point) {
+            builder.borrow_mut().push(c);
+        }
+        return Ok(args[0].clone());
+    }));
+
+    // insert(int offset, ...) like append
+    methods.insert("insert".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return builder_insert(jvm, args, &Descriptor::Int);
+    }));
+    methods.insert(method_key("insert", &returning_self("IZ")), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return builder_insert(jvm, args, &Descriptor::Boolean);
+    }));
+    methods.insert(method_key("insert", &returning_self("IC")), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return builder_insert(jvm, args, &Descriptor::Char);
+    }));
+    methods.insert(method_key("insert", &returning_self("I[C")), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let chars = new_string_of_chars(char_array_argument(args, 2)?);
+        return builder_insert(jvm, &[args[0].clone(), args[1].clone(), chars], &Descriptor::Int);
+    }));
+
+    methods.insert("reverse".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        builder_argument(args, 0)?.borrow_mut().reverse();
+        return Ok(args[0].clone());
+    }));
+    methods.insert("setLength".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let length = int_argument(args, 1)?;
+        if length < 0 {
+            return Err(JVMError::exception("java/lang/StringIndexOutOfBoundsException", Some(format!("String index out of range: {}", length))));
+        }
+        builder_argument(args, 0)?.borrow_mut().set_length(length as usize);
+        return Ok(RuntimeValue::Void);
+    }));
+    methods.insert("deleteCharAt".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let builder = builder_argument(args, 0)?;
+        let index = int_argument(args, 1)?;
+        check_index(index, builder.borrow().length())?;
+        builder.borrow_mut().remove(index as usize);
+        return Ok(args[0].clone());
+    }));
+
+    methods.insert("length".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::Int(builder_argument(args, 0)?.borrow().length() as i32));
+    }));
+    methods.insert("charAt".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let builder = builder_argument(args, 0)?;
+        let index = int_argument(args, 1)?;
+        check_index(index, builder.borrow().length())?;
+        let c = builder.borrow().char_at(index)?;
+        return Ok(RuntimeValue::Int(c as i32));
+    }));
+    // substring(int start), substring(int start, int end) and subSequence
+    for method_name in ["substring", "subSequence"] {
+        methods.insert(method_name.to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+            let builder = builder_argument(args, 0)?;
+            let start = int_argument(args, 1)?;
+            let end = if args.len() > 2 { int_argument(args, 2)? } else { builder.borrow().length() as i32 };
+            let length = builder.borrow().length();
+            if start < 0 || start > end || end as usize > length {
+                let message = format!("start {}, end {}, length {}", start, end, length);
+                return Err(JVMError::exception("java/lang/StringIndexOutOfBoundsException", Some(message)));
+            }
+            let substring = builder.borrow().substring(start, end)?;
+            return Ok(RuntimeValue::String(rc::Rc::new(cell::RefCell::new(substring))));
+        }));
+    }
+    methods.insert("compareTo".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let (this, other) = (builder_argument(args, 0)?, builder_argument(args, 1)?);
+        let result = this.borrow().compare_to(&other.borrow());
+        return Ok(RuntimeValue::Int(result));
+    }));
+    methods.insert("toString".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let contents = builder_argument(args, 0)?.borrow().clone();
+        return Ok(RuntimeValue::String(rc::Rc::new(cell::RefCell::new(contents))));
+    }));
+
+    let mut builder = create_native_class(name, "java/lang/Object", methods);
+    builder.access_flags = ACC_PUBLIC | ACC_FINAL;
+    builder.interfaces = vec!["java/io/Serializable".to_string(), "java/lang/Comparable".to_string(), "java/lang/CharSequence".to_string()];
+    return builder;
+}
+
+/* the interfaces provided by the jvm, along with their superinterfaces and the abstract methods
+ * they declare, so that invokeinterface can resolve those methods, as when a lambda implements
+ * one of them
+ */
+const INTERFACES: &[(&str, &[&str], &[(&str, &str)])] = &[
+    ("java/io/Serializable", &[], &[]),
+    ("java/lang/AutoCloseable", &[], &[("close", "()V")]),
+    ("java/io/Closeable", &["java/lang/AutoCloseable"], &[("close", "()V")]),
+    ("java/lang/Cloneable", &[], &[]),
+    ("java/lang/Comparable", &[], &[("compareTo", "(Ljava/lang/Object;)I")]),
+    ("java/lang/CharSequence", &[], &[("length", "()I"), ("charAt", "(I)C"), ("subSequence", "(II)Ljava/lang/CharSequence;")]),
+    ("java/lang/Runnable", &[], &[("run", "()V")]),
+    ("java/lang/Iterable", &[], &[]),
+    /* the functional interfaces lambdas are most often given as, with only their abstract methods
+     * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/util/function/package-summary.html
+     */
+    ("java/util/function/Function", &[], &[("apply", "(Ljava/lang/Object;)Ljava/lang/Object;")]),
+    ("java/util/function/BiFunction", &[], &[("apply", "(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;")]),
+    ("java/util/function/UnaryOperator", &["java/util/function/Function"], &[]),
+    ("java/util/function/BinaryOperator", &["java/util/function/BiFunction"], &[]),
+    ("java/util/function/Supplier", &[], &[("get", "()Ljava/lang/Object;")]),
+    ("java/util/function/Consumer", &[], &[("accept", "(Ljava/lang/Object;)V")]),
+    ("java/util/function/BiConsumer", &[], &[("accept", "(Ljava/lang/Object;Ljava/lang/Object;)V")]),
+    ("java/util/function/Predicate", &[], &[("test", "(Ljava/lang/Object;)Z")]),
+    ("java/util/function/BiPredicate", &[], &[("test", "(Ljava/lang/Object;Ljava/lang/Object;)Z")]),
+    ("java/util/function/IntFunction", &[], &[("apply", "(I)Ljava/lang/Object;")]),
+    ("java/util/function/IntUnaryOperator", &[], &[("applyAsInt", "(I)I")]),
+    ("java/util/function/IntBinaryOperator", &[], &[("applyAsInt", "(II)I")]),
+    ("java/util/function/IntPredicate", &[], &[("test", "(I)Z")]),
+    ("java/util/function/IntSupplier", &[], &[("getAsInt", "()I")]),
+    ("java/util/function/IntConsumer", &[], &[("accept", "(I)V")]),
+    ("java/util/function/ToIntFunction", &[], &[("applyAsInt", "(Ljava/lang/Object;)I")]),
+    ("java/util/function/ToIntBiFunction", &[], &[("applyAsInt", "(Ljava/lang/Object;Ljava/lang/Object;)I")]),
+    ("java/util/function/ObjIntConsumer", &[], &[("accept", "(Ljava/lang/Object;I)V")]),
+    ("java/util/function/LongUnaryOperator", &[], &[("applyAsLong", "(J)J")]),
+    ("java/util/function/LongBinaryOperator", &[], &[("applyAsLong", "(JJ)J")]),
+    ("java/util/function/LongSupplier", &[], &[("getAsLong", "()J")]),
+    ("java/util/function/ToLongFunction", &[], &[("applyAsLong", "(Ljava/lang/Object;)J")]),
+    ("java/util/function/DoubleUnaryOperator", &[], &[("applyAsDouble", "(D)D")]),
+    ("java/util/function/DoubleBinaryOperator", &[], &[("applyAsDouble", "(DD)D")]),
+    ("java/util/function/DoubleSupplier", &[], &[("getAsDouble", "()D")]),
+    ("java/util/function/ToDoubleFunction", &[], &[("applyAsDouble", "(Ljava/lang/Object;)D")]),
+    ("java/util/function/BooleanSupplier", &[], &[("getAsBoolean", "()Z")]),
+];
+
+fn create_interface(name: &str, interfaces: &[&str], abstract_methods: &[(&str, &str)]) -> JVMClass {
+    let mut methods = HashMap::new();
+    for (method_name, descriptor) in abstract_methods {
+        methods.insert(method_key(method_name, descriptor), JVMMethod::Bytecode(rc::Rc::new(MethodInfo{
+            access_flags: ACC_PUBLIC | ACC_ABSTRACT,
+            name_index: 0,
+            descriptor_index: 0,
+            attributes: Vec::new(),
+        })));
+    }
+
+    return JVMClass{
+        class: name.to_string(),
+        access_flags: ACC_PUBLIC | ACC_INTERFACE | ACC_ABSTRACT,
+        major_version: 0,
+        super_class: Some("java/lang/Object".to_string()),
+        interfaces: interfaces.iter().map(|interface| interface.to_string()).collect(),
+        source_file: None,
+        constant_pool: rc::Rc::new(Vec::new()),
+        methods: methods,
+        fields: cell::RefCell::new(HashMap::new()),
+        instance_fields: HashMap::new(),
+        object_fields: HashMap::new(),
+        field_owners: HashMap::new(),
+        bootstrap_methods: Vec::new(),
+        call_sites: cell::RefCell::new(HashMap::new()),
+        dynamic_constants: cell::RefCell::new(HashMap::new()),
+        subtype_cache: cell::RefCell::new(HashMap::new()),
+        initialization: cell::RefCell::new(Initialization::NotStarted),
+    };
+}
+
+// a class provided by the jvm that only has native methods
+fn create_native_class(name: &str, super_class: &str, methods: HashMap<String, JVMMethod>) -> JVMClass {
+    return JVMClass{
+        class: name.to_string(),
+        access_flags: ACC_PUBLIC,
+        major_version: 0,
+        super_class: Some(super_class.to_string()),
+        interfaces: Vec::new(),
+        source_file: None,
+        constant_pool: rc::Rc::new(Vec::new()),
+        methods: methods,
+        fields: cell::RefCell::new(HashMap::new()),
+        instance_fields: HashMap::new(),
+        object_fields: HashMap::new(),
+        field_owners: HashMap::new(),
+        bootstrap_methods: Vec::new(),
+        call_sites: cell::RefCell::new(HashMap::new()),
+        dynamic_constants: cell::RefCell::new(HashMap::new()),
+        subtype_cache: cell::RefCell::new(HashMap::new()),
+        initialization: cell::RefCell::new(Initialization::NotStarted),
+    };
+}
+
+// a call site whose target is a native of the bootstrap method's class with some values bound to it
+fn create_bound_call_site(class_name: &str, name: &str, descriptor: &str, bound: Vec<RuntimeValue>) -> Result<RuntimeValue, JVMError> {
+    let target = bind_arguments(new_method_handle(REF_INVOKE_STATIC, class_name, name, descriptor, false), bound)?;
+    let mut call_site_fields = HashMap::new();
+    call_site_fields.insert("target".to_string(), target);
+    return Ok(RuntimeValue::Object(rc::Rc::new(cell::RefCell::new(JVMObject{
+        class: "java/lang/invoke/ConstantCallSite".to_string(),
+        fields: call_site_fields,
+    }))));
+}
+
+// the parameters and return type of a method descriptor, apart
+fn split_method_descriptor(descriptor: &str) -> Result<(&str, &str), JVMError> {
+    match (descriptor.find('('), descriptor.rfind(')')) {
+        (Some(start), Some(end)) if start < end => Ok((&descriptor[start + 1..end], &descriptor[end + 1..])),
+        _ => Err(format!("invalid method descriptor {}", descriptor).into()),
+    }
+}
+
+// the elements of a reference array, such as the labels a switch bootstrap method gets
+fn array_elements(value: &RuntimeValue) -> Result<Vec<RuntimeValue>, JVMError> {
+    match value {
+        RuntimeValue::Array(array) => {
+            let array = array.borrow();
+            let mut elements = Vec::new();
+            for index in 0..array.length() {
+                elements.push(array.load(index as i32)?);
+            }
+            return Ok(elements);
+        },
+        RuntimeValue::Null => Err(JVMError::null_pointer()),
+        value => Err(format!("expected an array, not {:?}", value).into()),
+    }
+}
+
+/* the bootstrap methods of switches on patterns. the target of the call site takes the value
+ * switched on and the index of the first label to try, and returns the index of the first label
+ * from there on that matches, -1 for null or the number of labels if none does. javac calls it
+ * again with the next index when a guard fails
+ * https://docs.oracle.com/en/java/javase/21/docs/api/java.base/java/lang/runtime/SwitchBootstraps.html
+ */
+fn create_java_lang_runtime_switch_bootstraps() -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("typeSwitch".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return create_switch_call_site("typeSwitchIndex", args);
+    }));
+    methods.insert("enumSwitch".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return create_switch_call_site("enumSwitchIndex", args);
+    }));
+    methods.insert("typeSwitchIndex".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return switch_index(jvm, args, false);
+    }));
+    methods.insert("enumSwitchIndex".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return switch_index(jvm, args, true);
+    }));
+
+    return create_native_class("java/lang/runtime/SwitchBootstraps", "java/lang/Object", methods);
+}
+
+fn create_switch_call_site(target: &str, args: &[RuntimeValue]) -> Result<RuntimeValue, JVMError> {
+    let (invocation_type, labels) = match args {
+        [_lookup, _name, invocation_type, labels] => (invocation_type, labels),
+        _ => return Err(format!("invalid arguments to switch bootstrap: {:?}", args).into()),
+    };
+    for label in array_elements(labels)? {
+        if let RuntimeValue::Null = label {
+            return Err(JVMError::exception("java/lang/IllegalArgumentException", Some("label with illegal type found: null".to_string())));
+        }
+    }
+
+    let invoked = string_field(invocation_type, "descriptor")?;
+    let (parameters, return_type) = split_method_descriptor(&invoked)?;
+    let descriptor = format!("([Ljava/lang/Object;{}){}", parameters, return_type);
+    return create_bound_call_site("java/lang/runtime/SwitchBootstraps", target, &descriptor, vec![labels.clone()]);
+}
+
+/* the labels of a type switch are classes, strings and integers. those of an enum switch are
+ * classes and the names of the enum constants
+ */
+fn switch_index(jvm: &RuntimeConst, args: &[RuntimeValue], enum_switch: bool) -> Result<RuntimeValue, JVMError> {
+    let (labels, target, restart) = match args {
+        [labels, target, RuntimeValue::Int(restart)] => (array_elements(labels)?, target, *restart),
+        _ => return Err(format!("invalid arguments to switch: {:?}", args).into()),
+    };
+    if let RuntimeValue::Null = target {
+        return Ok(RuntimeValue::Int(-1));
+    }
+
+    let target_class = target.class_name();
+    for (index, label) in labels.iter().enumerate().skip(restart.max(0) as usize) {
+        let matches = match (label, target) {
+            (RuntimeValue::Object(label), _) if label.borrow().class == "java/lang/Class" => {
+                match (label.borrow().fields.get("name"), &target_class) {
+                    (Some(RuntimeValue::String(label_class)), Some(target_class)) => jvm.is_assignable(target_class, &label_class.borrow().to_string()),
+                    _ => false,
+                }
+            },
+            (RuntimeValue::String(name), RuntimeValue::Object(constant)) if enum_switch => {
+                matches!(constant.borrow().fields.get("name"), Some(RuntimeValue::String(constant_name)) if *constant_name.borrow() == *name.borrow())
+            },
+            (RuntimeValue::String(label), RuntimeValue::String(target)) => *label.borrow() == *target.borrow(),
+            (RuntimeValue::Int(label), RuntimeValue::Int(target)) => label == target,
+            _ => false,
+        };
+        if matches {
+            return Ok(RuntimeValue::Int(index as i32));
+        }
+    }
+    return Ok(RuntimeValue::Int(labels.len() as i32));
+}
+
+/* the superclass of enums. the name and ordinal javac passes to its constructor are kept in the
+ * fields Enum declares for them
+ */
+fn create_java_lang_enum() -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("<init>".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match args {
+            [RuntimeValue::Object(this), name, ordinal] => {
+                let mut this = this.borrow_mut();
+                this.fields.insert("name".to_string(), name.clone());
+                this.fields.insert("ordinal".to_string(), ordinal.clone());
+                return Ok(RuntimeValue::Void);
+            },
+            _ => {
+                return Err(format!("invalid arguments to Enum.<init>: {:?}", args).into());
+            }
+        }
+    }));
+    methods.insert("name".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(receiver_field(args, "name"));
+    }));
+    methods.insert("toString".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(receiver_field(args, "name"));
+    }));
+    methods.insert("ordinal".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(receiver_field(args, "ordinal"));
+    }));
+    methods.insert("compareTo".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match (receiver_field(&args[..1], "ordinal"), args.get(1)) {
+            (RuntimeValue::Int(ordinal), Some(other @ RuntimeValue::Object(_))) => {
+                match receiver_field(std::slice::from_ref(other), "ordinal") {
+                    RuntimeValue::Int(other_ordinal) => Ok(RuntimeValue::Int(ordinal - other_ordinal)),
+                    _ => Err("compareTo called with a non-enum".to_string().into()),
+                }
+            },
+            (_, Some(RuntimeValue::Null)) => Err(JVMError::null_pointer()),
+            _ => Err(format!("invalid arguments to Enum.compareTo: {:?}", args).into()),
+        }
+    }));
+    /* the constant of an enum class with the given name, which the valueOf javac gives every enum
+     * calls. the constants are the ones in the $VALUES array javac gives it as well
+     * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/Enum.html#valueOf(java.lang.Class,java.lang.String)
+     */
+    methods.insert(method_key("valueOf", "(Ljava/lang/Class;Ljava/lang/String;)Ljava/lang/Enum;"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let class_name = match args.first() {
+            Some(class @ RuntimeValue::Object(_)) => class_of(class)?,
+            _ => return Err(JVMError::null_pointer()),
+        };
+        jvm.load_class(&class_name)?;
+        let class = match jvm.resolve_static_field(&class_name, "$VALUES") {
+            Some(class) => class,
+            None => return Err(JVMError::exception("java/lang/IllegalArgumentException", Some(format!("{} is not an enum class", external_class_name(&class_name))))),
+        };
+        jvm.initialize_class(&class)?;
+        let name = match args.get(1) {
+            Some(RuntimeValue::String(name)) => name.clone(),
+            _ => return Err(JVMError::exception("java/lang/NullPointerException", Some("Name is null".to_string()))),
+        };
+
+        let values = class.fields.borrow().get("$VALUES").cloned().unwrap_or(RuntimeValue::Null);
+        for constant in array_elements(&values)? {
+            if let RuntimeValue::String(constant_name) = receiver_field(std::slice::from_ref(&constant), "name") {
+                if *constant_name.borrow() == *name.borrow() {
+                    return Ok(constant);
+                }
+            }
+        }
+        // the canonical name of a nested enum, such as Main.Color
+        let message = format!("No enum constant {}.{}", external_class_name(&class_name).replace('$', "."), name.borrow());
+        return Err(JVMError::exception("java/lang/IllegalArgumentException", Some(message)));
+    }));
+
+    let mut class = create_native_class("java/lang/Enum", "java/lang/Object", methods);
+    class.access_flags = ACC_PUBLIC | ACC_ABSTRACT;
+    class.interfaces = vec!["java/lang/Comparable".to_string(), "java/io/Serializable".to_string()];
+    return class;
+}
+
+fn create_java_lang_record() -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("<init>".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, _args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::Void);
+    }));
+
+    let mut class = create_native_class("java/lang/Record", "java/lang/Object", methods);
+    class.access_flags = ACC_PUBLIC | ACC_ABSTRACT;
+    return class;
+}
+
+/* the bootstrap method of the toString, equals and hashCode methods javac gives records. it gets
+ * the record class, the names of its components separated by semicolons and a getter for each
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/runtime/ObjectMethods.html
+ */
+fn create_java_lang_runtime_object_methods() -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("bootstrap".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let (method_name, invocation_type, record_class, names, getters) = match args {
+            [_lookup, RuntimeValue::String(method_name), invocation_type, record_class, names, getters] => (method_name.borrow().to_string(), invocation_type, record_class, names, getters),
+            _ => return Err(format!("invalid arguments to ObjectMethods.bootstrap: {:?}", args).into()),
+        };
+        let target = match method_name.as_str() {
+            "toString" => "recordToString",
+            "equals" => "recordEquals",
+            "hashCode" => "recordHashCode",
+            _ => return Err(JVMError::exception("java/lang/IllegalArgumentException", Some(method_name))),
+        };
+
+        let invoked = string_field(invocation_type, "descriptor")?;
+        let (parameters, return_type) = split_method_descriptor(&invoked)?;
+        let descriptor = format!("(Ljava/lang/Class;Ljava/lang/String;[Ljava/lang/invoke/MethodHandle;{}){}", parameters, return_type);
+        return create_bound_call_site("java/lang/runtime/ObjectMethods", target, &descriptor, vec![record_class.clone(), names.clone(), getters.clone()]);
+    }));
+    methods.insert("recordToString".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let (record_class, names, getters, this) = match args {
+            [record_class, RuntimeValue::String(names), getters, this] => (record_class, names.borrow().to_string(), array_elements(getters)?, this),
+            _ => return Err(format!("invalid arguments to record toString: {:?}", args).into()),
+        };
+        let class_name = string_field(record_class, "name")?;
+        let simple_name = class_name.rsplit(|c| c == '/' || c == '$').next().unwrap_or(&class_name);
+
+        let mut components = Vec::new();
+        for (name, getter) in names.split(';').zip(getters.iter()) {
+            let value = invoke_method_handle(jvm, getter, vec![this.clone()])?;
+            let kind = parse_type_descriptor(&string_field(getter, "descriptor")?)?;
+            components.push(format!("{}={}", name, string_value_of(jvm, &value, &kind)?));
+        }
+        return Ok(new_string(&format!("{}[{}]", simple_name, components.join(", "))));
+    }));
+    methods.insert("recordEquals".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let (getters, this, other) = match args {
+            [_record_class, _names, getters, this, other] => (array_elements(getters)?, this, other),
+            _ => return Err(format!("invalid arguments to record equals: {:?}", args).into()),
+        };
+        if this.class_name() != other.class_name() {
+            return Ok(RuntimeValue::Int(0));
+        }
+        for getter in getters.iter() {
+            let value = invoke_method_handle(jvm, getter, vec![this.clone()])?;
+            let other_value = invoke_method_handle(jvm, getter, vec![other.clone()])?;
+            if !values_equal(jvm, &value, &other_value)? {
+                return Ok(RuntimeValue::Int(0));
+            }
+        }
+        return Ok(RuntimeValue::Int(1));
+    }));
+    methods.insert("recordHashCode".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let (getters, this) = match args {
+            [_record_class, _names, getters, this] => (array_elements(getters)?, this),
+            _ => return Err(format!("invalid arguments to record hashCode: {:?}", args).into()),
+        };
+        let mut hash: i32 = 0;
+        for getter in getters.iter() {
+            let value = invoke_method_handle(jvm, getter, vec![this.clone()])?;
+            let kind = parse_type_descriptor(&string_field(getter, "descriptor")?)?;
+            hash = hash.wrapping_mul(31).wrapping_add(hash_code(jvm, &value, &kind)?);
+        }
+        return Ok(RuntimeValue::Int(hash));
+    }));
+
+    return create_native_class("java/lang/runtime/ObjectMethods", "java/lang/Object", methods);
+}
+
+/* whether two components of records are equal: primitives compare like their wrapper classes'
+ * equals, so NaN equals itself and 0.0 does not equal -0.0, and references like Objects.equals
+ */
+fn values_equal(jvm: &RuntimeConst, value: &RuntimeValue, other: &RuntimeValue) -> Result<bool, JVMError> {
+    match (value, other) {
+        (RuntimeValue::Int(a), RuntimeValue::Int(b)) => Ok(a == b),
+        (RuntimeValue::Long(a), RuntimeValue::Long(b)) => Ok(a == b),
+        (RuntimeValue::Float(a), RuntimeValue::Float(b)) => Ok((a.is_nan() && b.is_nan()) || a.to_bits() == b.to_bits()),
+        (RuntimeValue::Double(a), RuntimeValue::Double(b)) => Ok((a.is_nan() && b.is_nan()) || a.to_bits() == b.to_bits()),
+        (RuntimeValue::String(a), RuntimeValue::String(b)) => Ok(*a.borrow() == *b.borrow()),
+        (RuntimeValue::Null, _) | (_, RuntimeValue::Null) => Ok(matches!((value, other), (RuntimeValue::Null, RuntimeValue::Null))),
+        (RuntimeValue::Object(_), _) => {
+            if value.same_reference(other)? {
+                return Ok(true);
+            }
+            match call_virtual(jvm, value, "equals", "(Ljava/lang/Object;)Z", vec![other.clone()])? {
+                Some(RuntimeValue::Int(equal)) => Ok(equal != 0),
+                _ => Ok(false),
+            }
+        },
+        _ => value.same_reference(other),
+    }
+}
+
+// the hash code of a value as its wrapper class or its own hashCode computes it
+fn hash_code(jvm: &RuntimeConst, value: &RuntimeValue, kind: &Descriptor) -> Result<i32, JVMError> {
+    match (value, kind) {
+        (RuntimeValue::Int(value), Descriptor::Boolean) => Ok(if *value != 0 { 1231 } else { 1237 }),
+        (RuntimeValue::Int(value), _) => Ok(*value),
+        (RuntimeValue::Long(value), _) => Ok((value ^ ((*value as u64) >> 32) as i64) as i32),
+        (RuntimeValue::Float(value), _) => Ok(if value.is_nan() { 0x7fc00000 } else { value.to_bits() as i32 }),
+        (RuntimeValue::Double(value), _) => {
+            let bits = if value.is_nan() { 0x7ff8000000000000 } else { value.to_bits() };
+            Ok((bits ^ (bits >> 32)) as i32)
+        },
+        (RuntimeValue::String(value), _) => Ok(value.borrow().hash_code()),
+        (RuntimeValue::Null, _) => Ok(0),
+        (RuntimeValue::Object(_), _) => {
+            match call_virtual(jvm, value, "hashCode", "()I", Vec::new())? {
+                Some(RuntimeValue::Int(hash)) => Ok(hash),
+                _ => Ok(value.identity_hash_code()),
+            }
+        },
+        (RuntimeValue::Array(_), _) => Ok(value.identity_hash_code()),
+        (value, _) => Err(format!("cannot hash {:?}", value).into()),
+    }
+}
+
+/* bootstrap methods for dynamically computed constants. they get a lookup, the name and the
+ * Class of the constant and their static arguments
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/invoke/ConstantBootstraps.html
+ */
+fn create_java_lang_invoke_constant_bootstraps() -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("nullConstant".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, _args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::Null);
+    }));
+    methods.insert("primitiveClass".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match args.get(1).map(|name| name.to_rust_string()) {
+            Some(Some(name)) if name.len() == 1 && name != "L" && name != "[" => Ok(jvm.class_object(&type_class_name(&name))),
+            Some(Some(name)) => Err(JVMError::exception("java/lang/IllegalArgumentException", Some(name))),
+            _ => Err(format!("invalid arguments to primitiveClass: {:?}", args).into()),
+        }
+    }));
+    // an enum constant is a static field of its enum named after it
+    methods.insert("enumConstant".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match args {
+            [_lookup, RuntimeValue::String(name), kind] => get_static_field(jvm, &string_field(kind, "name")?, &name.borrow().to_string()),
+            _ => Err(format!("invalid arguments to enumConstant: {:?}", args).into()),
+        }
+    }));
+    methods.insert("getStaticFinal".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match args {
+            [_lookup, RuntimeValue::String(name), kind] => get_static_field(jvm, &string_field(kind, "name")?, &name.borrow().to_string()),
+            [_lookup, RuntimeValue::String(name), _kind, declaring_class] => get_static_field(jvm, &string_field(declaring_class, "name")?, &name.borrow().to_string()),
+            _ => Err(format!("invalid arguments to getStaticFinal: {:?}", args).into()),
+        }
+    }));
+    methods.insert("invoke".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match args {
+            [_lookup, _name, _kind, handle, arguments] => invoke_method_handle(jvm, handle, array_elements(arguments)?),
+            _ => Err(format!("invalid arguments to invoke: {:?}", args).into()),
+        }
+    }));
+    methods.insert("explicitCast".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match args {
+            [_lookup, _name, _kind, value] => Ok(value.clone()),
+            _ => Err(format!("invalid arguments to explicitCast: {:?}", args).into()),
+        }
+    }));
+
+    return create_native_class("java/lang/invoke/ConstantBootstraps", "java/lang/Object", methods);
+}
+
+fn get_static_field(jvm: &RuntimeConst, class_name: &str, name: &str) -> Result<RuntimeValue, JVMError> {
+    let class = static_field_class(jvm, class_name, name)?;
+    let value = class.fields.borrow().get(name).cloned();
+    return value.ok_or_else(|| unresolved_field(jvm, class_name, name, true));
+}
+
+// the name of the class a java/lang/Class object stands for
+fn class_of(value: &RuntimeValue) -> Result<String, JVMError> {
+    return string_field(value, "name");
+}
+
+fn int_argument(args: &[RuntimeValue], index: usize) -> Result<i32, JVMError> {
+    match args.get(index) {
+        Some(RuntimeValue::Int(value)) => Ok(*value),
+        value => Err(format!("expected an int argument, not {:?}", value).into()),
+    }
+}
+
+/* the descriptors of the parameter types a MethodType factory gets, which are classes, arrays of
+ * classes or the parameters of another MethodType
+ */
+fn parameter_types(args: &[RuntimeValue]) -> Result<Vec<String>, JVMError> {
+    let mut parameters = Vec::new();
+    for arg in args {
+        match arg {
+            RuntimeValue::Array(_) => {
+                for element in array_elements(arg)? {
+                    parameters.push(class_descriptor(&class_of(&element)?));
+                }
+            },
+            RuntimeValue::Object(object) if object.borrow().class == "java/lang/invoke/MethodType" => {
+                parameters.extend(parameter_descriptors(&string_field(arg, "descriptor")?)?);
+            },
+            _ => {
+                parameters.push(class_descriptor(&class_of(arg)?));
+            }
+        }
+    }
+    if parameters.iter().any(|parameter| parameter == "V") {
+        return Err(JVMError::exception("java/lang/IllegalArgumentException", Some("parameter type cannot be void".to_string())));
+    }
+    return Ok(parameters);
+}
+
+// a MethodType with the parameters of another after they have been changed
+fn change_parameters(method_type: &RuntimeValue, change: impl FnOnce(&mut Vec<String>) -> Result<(), JVMError>) -> Result<RuntimeValue, JVMError> {
+    let descriptor = string_field(method_type, "descriptor")?;
+    let mut parameters = parameter_descriptors(&descriptor)?;
+    change(&mut parameters)?;
+    if parameters.iter().any(|parameter| parameter == "V") {
+        return Err(JVMError::exception("java/lang/IllegalArgumentException", Some("parameter type cannot be void".to_string())));
+    }
+    return Ok(new_method_type(&format!("({}){}", parameters.concat(), split_method_descriptor(&descriptor)?.1)));
+}
+
+fn parameter_index(index: i32, count: usize) -> Result<usize, JVMError> {
+    if index < 0 || index as usize > count {
+        return Err(JVMError::exception("java/lang/IndexOutOfBoundsException", Some(index.to_string())));
+    }
+    return Ok(index as usize);
+}
+
+/* the type of a method handle: its parameter types and return type
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/invoke/MethodType.html
+ */
+fn create_java_lang_invoke_method_type() -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("methodType".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let return_type = match args.first() {
+            Some(return_type) => class_descriptor(&class_of(return_type)?),
+            None => return Err("methodType called without a return type".to_string().into()),
+        };
+        return Ok(new_method_type(&format!("({}){}", parameter_types(&args[1..])?.concat(), return_type)));
+    }));
+    methods.insert("parameterCount".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(RuntimeValue::Int(parameter_descriptors(&string_field(&args[0], "descriptor")?)?.len() as i32));
+    }));
+    methods.insert("parameterType".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let parameters = parameter_descriptors(&string_field(&args[0], "descriptor")?)?;
+        let index = int_argument(args, 1)?;
+        match parameters.get(index as usize) {
+            Some(parameter) if index >= 0 => Ok(jvm.class_object(&type_class_name(parameter))),
+            _ => Err(JVMError::exception("java/lang/IndexOutOfBoundsException", Some(index.to_string()))),
+        }
+    }));
+    methods.insert("returnType".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let descriptor = string_field(&args[0], "descriptor")?;
+        return Ok(jvm.class_object(&type_class_name(split_method_descriptor(&descriptor)?.1)));
+    }));
+    methods.insert("changeReturnType".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let descriptor = string_field(&args[0], "descriptor")?;
+        let (parameters, _) = split_method_descriptor(&descriptor)?;
+        return Ok(new_method_type(&format!("({}){}", parameters, class_descriptor(&class_of(&args[1])?))));
+    }));
+    methods.insert("changeParameterType".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let index = int_argument(args, 1)?;
+        let parameter = class_descriptor(&class_of(&args[2])?);
+        return change_parameters(&args[0], |parameters| {
+            let index = parameter_index(index, parameters.len().saturating_sub(1))?;
+            parameters[index] = parameter;
+            return Ok(());
+        });
+    }));
+    methods.insert("insertParameterTypes".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let index = int_argument(args, 1)?;
+        let inserted = parameter_types(&args[2..])?;
+        return change_parameters(&args[0], |parameters| {
+            let index = parameter_index(index, parameters.len())?;
+            parameters.splice(index..index, inserted);
+            return Ok(());
+        });
+    }));
+    methods.insert("appendParameterTypes".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let appended = parameter_types(&args[1..])?;
+        return change_parameters(&args[0], |parameters| {
+            parameters.extend(appended);
+            return Ok(());
+        });
+    }));
+    methods.insert("dropParameterTypes".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let (start, end) = (int_argument(args, 1)?, int_argument(args, 2)?);
+        return change_parameters(&args[0], |parameters| {
+            let end = parameter_index(end, parameters.len())?;
+            if start < 0 || start as usize > end {
+                return Err(JVMError::exception("java/lang/IndexOutOfBoundsException", Some(format!("start={} end={}", start, end))));
+            }
+            parameters.drain(start as usize..end);
+            return Ok(());
+        });
+    }));
+    methods.insert("toMethodDescriptorString".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(new_string(&string_field(&args[0], "descriptor")?));
+    }));
+    methods.insert("descriptorString".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(new_string(&string_field(&args[0], "descriptor")?));
+    }));
+    methods.insert("toString".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(new_string(&method_type_string(&string_field(&args[0], "descriptor")?)?));
+    }));
+    // method types are equal when their descriptors are, though they are not interned
+    methods.insert("equals".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let equal = match &args[1] {
+            RuntimeValue::Object(object) if object.borrow().class == "java/lang/invoke/MethodType" => string_field(&args[0], "descriptor")? == string_field(&args[1], "descriptor")?,
+            _ => false,
+        };
+        return Ok(RuntimeValue::Int(if equal { 1 } else { 0 }));
+    }));
+    methods.insert("hashCode".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let descriptor = new_string(&string_field(&args[0], "descriptor")?);
+        return Ok(RuntimeValue::Int(hash_code(jvm, &descriptor, &Descriptor::Object("java/lang/String".to_string()))?));
+    }));
+
+    return create_native_class("java/lang/invoke/MethodType", "java/lang/Object", methods);
+}
+
+/* the methods of method handles other than the signature polymorphic ones, which invokevirtual
+ * takes care of
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/invoke/MethodHandle.html
+ */
+fn create_java_lang_invoke_method_handle() -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("type".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(new_method_type(&handle_type(&args[0])?));
+    }));
+    methods.insert("bindTo".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let parameters = parameter_descriptors(&handle_type(&args[0])?)?;
+        match parameters.first() {
+            Some(parameter) if parameter.starts_with('L') || parameter.starts_with('[') => {
+                return bind_arguments(args[0].clone(), vec![args[1].clone()]);
+            },
+            _ => {
+                return Err(JVMError::exception("java/lang/IllegalArgumentException", Some("no leading reference parameter".to_string())));
+            }
+        }
+    }));
+    methods.insert("asType".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return as_type(args[0].clone(), &string_field(&args[1], "descriptor")?);
+    }));
+    methods.insert("invokeWithArguments".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let arguments = array_elements(&args[1])?;
+        let handle_type = handle_type(&args[0])?;
+        // the arguments come in an Object[], as if invoked with Objects and returning an Object
+        let generic = format!("({})Ljava/lang/Object;", "Ljava/lang/Object;".repeat(arguments.len()));
+        if parameter_descriptors(&handle_type)?.len() != arguments.len() {
+            return Err(JVMError::exception("java/lang/invoke/WrongMethodTypeException", Some(format!("cannot convert MethodHandle{} to {}", method_type_string(&handle_type)?, method_type_string(&generic)?))));
+        }
+        let handle_type = parse_method_descriptor(&handle_type)?;
+        let mut converted = Vec::new();
+        for (argument, parameter) in arguments.into_iter().zip(handle_type.parameters.iter()) {
+            converted.push(convert_argument(jvm, argument, parameter)?);
+        }
+        return match invoke_method_handle(jvm, &args[0], converted)? {
+            RuntimeValue::Void => Ok(RuntimeValue::Null),
+            result => convert_value(jvm, result, &handle_type.return_type, &Descriptor::Object("java/lang/Object".to_string())),
+        };
+    }));
+    methods.insert("toString".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(new_string(&format!("MethodHandle{}", method_type_string(&handle_type(&args[0])?)?)));
+    }));
+
+    let mut class = create_native_class("java/lang/invoke/MethodHandle", "java/lang/Object", methods);
+    class.access_flags = ACC_PUBLIC | ACC_ABSTRACT;
+    return class;
+}
+
+fn create_java_lang_invoke_method_handles() -> JVMClass {
+    let mut methods = HashMap::new();
+    // the lookup has the access of the class calling lookup(), the method on top of the call stack
+    methods.insert("lookup".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, _args: &[RuntimeValue]| {
+        let caller = match jvm.call_stack.borrow().last() {
+            Some(call) => call.class.clone(),
+            None => "java/lang/Object".to_string(),
+        };
+        return Ok(new_lookup(&caller));
+    }));
+    methods.insert("publicLookup".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, _args: &[RuntimeValue]| {
+        return Ok(new_lookup("java/lang/Object"));
+    }));
+    methods.insert("insertArguments".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let position = parameter_index(int_argument(args, 1)?, usize::MAX)?;
+        return insert_arguments(args[0].clone(), position, array_elements(&args[2])?);
+    }));
+    methods.insert("dropArguments".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let position = parameter_index(int_argument(args, 1)?, usize::MAX)?;
+        return drop_arguments(args[0].clone(), position, parameter_types(&args[2..])?);
+    }));
+
+    return create_native_class("java/lang/invoke/MethodHandles", "java/lang/Object", methods);
+}
+
+/* how findVirtual and the like describe a member they did not find, such as
+ * no such method: Main.missing()int/invokeStatic
+ */
+fn no_such_member(exception: &str, kind: &str, class_name: &str, name: &str, type_string: &str, reference_kind: &str) -> JVMError {
+    return JVMError::exception(exception, Some(format!("no such {}: {}.{}{}/{}", kind, external_class_name(class_name), name, type_string, reference_kind)));
+}
+
+/* a direct method handle on a method found by a lookup. a method that exists but is static when
+ * it should not be, or the other way around, is not accessible
+ */
+fn find_method(jvm: &RuntimeConst, args: &[RuntimeValue], want_static: bool) -> Result<RuntimeValue, JVMError> {
+    let (class_name, name, descriptor) = match args {
+        [_lookup, class, RuntimeValue::String(name), method_type, ..] => (class_of(class)?, name.borrow().to_string(), string_field(method_type, "descriptor")?),
+        _ => return Err(format!("invalid arguments to find a method: {:?}", args).into()),
+    };
+    let class = jvm.load_class(&class_name)?;
+    let is_interface = class.access_flags & ACC_INTERFACE != 0;
+    let reference_kind = if want_static { REF_INVOKE_STATIC } else if is_interface { REF_INVOKE_INTERFACE } else { REF_INVOKE_VIRTUAL };
+    let kind_name = match reference_kind {
+        REF_INVOKE_STATIC => "invokeStatic",
+        REF_INVOKE_INTERFACE => "invokeInterface",
+        _ => "invokeVirtual",
+    };
+
+    match jvm.resolve_method_ref(&class_name, &name, &descriptor, is_interface)? {
+        Some((_, method)) => {
+            if (method_access_flags(&method) & ACC_STATIC != 0) != want_static {
+                return Err(no_such_member("java/lang/IllegalAccessException", "method", &class_name, &name, &method_type_string(&descriptor)?, kind_name));
+            }
+            return Ok(new_method_handle(reference_kind, &class_name, &name, &descriptor, is_interface));
+        },
+        None => {
+            return Err(no_such_member("java/lang/NoSuchMethodException", "method", &class_name, &name, &method_type_string(&descriptor)?, kind_name));
+        }
+    }
+}
+
+// a direct method handle that gets or sets a field found by a lookup
+fn find_field(jvm: &RuntimeConst, args: &[RuntimeValue], reference_kind: i32) -> Result<RuntimeValue, JVMError> {
+    let (class_name, name, descriptor) = match args {
+        [_lookup, class, RuntimeValue::String(name), field_type] => (class_of(class)?, name.borrow().to_string(), class_descriptor(&class_of(field_type)?)),
+        _ => return Err(format!("invalid arguments to find a field: {:?}", args).into()),
+    };
+    let (want_static, kind_name) = match reference_kind {
+        REF_GET_FIELD => (false, "getField"),
+        REF_PUT_FIELD => (false, "putField"),
+        REF_GET_STATIC => (true, "getStatic"),
+        _ => (true, "putStatic"),
+    };
+    let type_string = format!("/{}", type_simple_name(&descriptor));
+
+    jvm.load_class(&class_name)?;
+    let found = if want_static { jvm.resolve_static_field(&class_name, &name) } else { jvm.resolve_instance_field(&class_name, &name) };
+    if found.is_none() {
+        let other = if want_static { jvm.resolve_instance_field(&class_name, &name) } else { jvm.resolve_static_field(&class_name, &name) };
+        let exception = if other.is_some() { "java/lang/IllegalAccessException" } else { "java/lang/NoSuchFieldException" };
+        return Err(no_such_member(exception, "field", &class_name, &name, &type_string, kind_name));
+    }
+    return Ok(new_method_handle(reference_kind, &class_name, &name, &descriptor, false));
+}
+
+/* a lookup finds methods, constructors and fields and makes method handles on them
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/invoke/MethodHandles.Lookup.html
+ */
+fn create_java_lang_invoke_method_handles_lookup() -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("findStatic".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return find_method(jvm, args, true);
+    }));
+    methods.insert("findVirtual".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return find_method(jvm, args, false);
+    }));
+    methods.insert("bind".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let receiver = match args.get(1) {
+            Some(RuntimeValue::Null) | None => return Err(JVMError::null_pointer()),
+            Some(receiver) => receiver.clone(),
+        };
+        let class = match receiver.class_name() {
+            Some(class) => jvm.class_object(&class),
+            None => return Err(format!("cannot bind {:?}", receiver).into()),
+        };
+        let handle = find_method(jvm, &[args[0].clone(), class, args[2].clone(), args[3].clone()], false)?;
+        return bind_arguments(handle, vec![receiver]);
+    }));
+    methods.insert("findSpecial".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let handle = find_method(jvm, &args[..4], false)?;
+        let (class_name, name, descriptor) = (string_field(&handle, "declaringClass")?, string_field(&handle, "name")?, string_field(&handle, "descriptor")?);
+        let handle = new_method_handle(REF_INVOKE_SPECIAL, &class_name, &name, &descriptor, int_field(&handle, "isInterface")? != 0);
+        // the receiver is of the class the special call is made from
+        let special_caller = class_descriptor(&class_of(&args[4])?);
+        if let RuntimeValue::Object(object) = &handle {
+            let special_type = format!("({}{}", special_caller, descriptor.strip_prefix('(').unwrap_or(&descriptor));
+            object.borrow_mut().fields.insert("type".to_string(), new_string(&special_type));
+        }
+        return Ok(handle);
+    }));
+    methods.insert("findConstructor".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let (class_name, descriptor) = match args {
+            [_lookup, class, method_type] => (class_of(class)?, string_field(method_type, "descriptor")?),
+            _ => return Err(format!("invalid arguments to findConstructor: {:?}", args).into()),
+        };
+        let class = jvm.load_class(&class_name)?;
+        if class.declared_method("<init>", &descriptor).is_none() || class.access_flags & (ACC_ABSTRACT | ACC_INTERFACE) != 0 {
+            return Err(no_such_member("java/lang/NoSuchMethodException", "constructor", &class_name, "<init>", &method_type_string(&descriptor)?, "newInvokeSpecial"));
+        }
+        return Ok(new_method_handle(REF_NEW_INVOKE_SPECIAL, &class_name, "<init>", &descriptor, false));
+    }));
+    methods.insert("findGetter".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return find_field(jvm, args, REF_GET_FIELD);
+    }));
+    methods.insert("findSetter".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return find_field(jvm, args, REF_PUT_FIELD);
+    }));
+    methods.insert("findStaticGetter".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return find_field(jvm, args, REF_GET_STATIC);
+    }));
+    methods.insert("findStaticSetter".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return find_field(jvm, args, REF_PUT_STATIC);
+    }));
+    methods.insert("lookupClass".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(jvm.class_object(&string_field(&args[0], "lookupClass")?));
+    }));
+    methods.insert("toString".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(new_string(&external_class_name(&string_field(&args[0], "lookupClass")?)));
+    }));
+
+    return create_native_class("java/lang/invoke/MethodHandles$Lookup", "java/lang/Object", methods);
+}
+
+/* the wrapper classes of the primitive types. their objects hold a value of the primitive type in
+ * their value field, and the classes the Class of it, which int.class and the like compile to
+ */
+const PRIMITIVE_WRAPPERS: &[(&str, &str, &str)] = &[
+    ("java/lang/Boolean", "java/lang/Object", "boolean"),
+    ("java/lang/Character", "java/lang/Object", "char"),
+    ("java/lang/Byte", "java/lang/Number", "byte"),
+    ("java/lang/Short", "java/lang/Number", "short"),
+    ("java/lang/Integer", "java/lang/Number", "int"),
+    ("java/lang/Long", "java/lang/Number", "long"),
+    ("java/lang/Float", "java/lang/Number", "float"),
+    ("java/lang/Double", "java/lang/Number", "double"),
+    ("java/lang/Void", "java/lang/Object", "void"),
+];
+
+// the wrapper class of a primitive type such as I
+fn wrapper_class(primitive: &Descriptor) -> Option<&'static str> {
+    let name = descriptor_external_name(primitive);
+    return PRIMITIVE_WRAPPERS.iter().find(|(_, _, wrapped)| *wrapped == name).map(|(wrapper, _, _)| *wrapper);
+}
+
+// the primitive type a wrapper class holds a value of, None for other classes and Void
+fn wrapped_primitive(class_name: &str) -> Option<Descriptor> {
+    return PRIMITIVE_WRAPPERS.iter()
+        .find(|(wrapper, _, primitive)| *wrapper == class_name && *primitive != "void")
+        .and_then(|(_, _, primitive)| parse_type_descriptor(&class_descriptor(primitive)).ok());
+}
+
+/* box a primitive value into an object of its wrapper class, the way valueOf does. the values
+ * valueOf caches are boxed into the same object every time
+ * https://docs.oracle.com/javase/specs/jls/se17/html/jls-5.html#jls-5.1.7
+ */
+fn box_value(jvm: &RuntimeConst, value: RuntimeValue, primitive: &Descriptor) -> Result<RuntimeValue, JVMError> {
+    let wrapper = match wrapper_class(primitive) {
+        Some(wrapper) => wrapper,
+        None => return Err(format!("cannot box a value of type {}", descriptor_external_name(primitive)).into()),
+    };
+    let value = convert_argument(jvm, value, primitive)?;
+    let cached = match (&value, primitive) {
+        (RuntimeValue::Int(_), Descriptor::Boolean | Descriptor::Byte) => true,
+        (RuntimeValue::Int(value), Descriptor::Char) => (0..=127).contains(value),
+        (RuntimeValue::Int(value), _) => (-128..=127).contains(value),
+        (RuntimeValue::Long(value), _) => (-128..=127).contains(value),
+        _ => false,
+    };
+    let create = || {
+        let mut fields = HashMap::new();
+        fields.insert("value".to_string(), value.clone());
+        return rc::Rc::new(cell::RefCell::new(JVMObject{
+            class: wrapper.to_string(),
+            fields: fields,
+        }));
+    };
+    if !cached {
+        return Ok(RuntimeValue::Object(create()));
+    }
+    let key = match &value {
+        RuntimeValue::Long(value) => *value,
+        RuntimeValue::Int(value) => *value as i64,
+        _ => 0,
+    };
+    let object = jvm.boxes.borrow_mut().entry((wrapper.to_string(), key)).or_insert_with(create).clone();
+    return Ok(RuntimeValue::Object(object));
+}
+
+/* the primitive value an object of a wrapper class holds, along with its type. unboxing null
+ * throws a NullPointerException, and anything else that is not a wrapper a ClassCastException
+ */
+fn unbox_value(value: &RuntimeValue, to: &Descriptor) -> Result<(RuntimeValue, Descriptor), JVMError> {
+    let object = match value {
+        RuntimeValue::Object(object) => object,
+        RuntimeValue::Null => return Err(JVMError::null_pointer()),
+        _ => return Err(cannot_cast(value, wrapper_class(to).unwrap_or("java/lang/Object"))),
+    };
+    let class_name = object.borrow().class.clone();
+    match (wrapped_primitive(&class_name), object.borrow().fields.get("value")) {
+        (Some(primitive), Some(value)) => Ok((value.clone(), primitive)),
+        _ => Err(cannot_cast(value, wrapper_class(to).unwrap_or("java/lang/Object"))),
+    }
+}
+
+// the ClassCastException of Class.cast
+fn cannot_cast(value: &RuntimeValue, class_name: &str) -> JVMError {
+    let message = format!("Cannot cast {} to {}", external_class_name(&value.class_name().unwrap_or_default()), external_class_name(class_name));
+    return JVMError::exception("java/lang/ClassCastException", Some(message));
+}
+
+/* a primitive value converted to another primitive type the way a cast does, for the methods of
+ * Number such as intValue
+ */
+fn cast_primitive(value: &RuntimeValue, to: &Descriptor) -> Result<RuntimeValue, JVMError> {
+    let (long, double) = match value {
+        RuntimeValue::Int(value) => (*value as i64, *value as f64),
+        RuntimeValue::Long(value) => (*value, *value as f64),
+        RuntimeValue::Float(value) => (*value as i64, *value as f64),
+        RuntimeValue::Double(value) => (*value as i64, *value),
+        value => return Err(format!("cannot cast {:?} to a primitive", value).into()),
+    };
+    // a floating point value is cast to int directly, which saturates at the range of an int
+    let int = match value {
+        RuntimeValue::Float(value) => *value as i32,
+        RuntimeValue::Double(value) => *value as i32,
+        _ => long as i32,
+    };
+    match to {
+        Descriptor::Byte => Ok(RuntimeValue::Int(int as i8 as i32)),
+        Descriptor::Short => Ok(RuntimeValue::Int(int as i16 as i32)),
+        Descriptor::Char => Ok(RuntimeValue::Int(int as u16 as i32)),
+        Descriptor::Int => Ok(RuntimeValue::Int(int)),
+        Descriptor::Long => Ok(RuntimeValue::Long(long)),
+        Descriptor::Float => Ok(RuntimeValue::Float(match value {
+            RuntimeValue::Double(value) => *value as f32,
+            RuntimeValue::Float(value) => *value,
+            RuntimeValue::Long(value) => *value as f32,
+            _ => int as f32,
+        })),
+        Descriptor::Double => Ok(RuntimeValue::Double(double)),
+        _ => Err(format!("cannot cast {:?} to {}", value, descriptor_external_name(to)).into()),
+    }
+}
+
+/* the methods of a wrapper class: valueOf boxes a value, the methods of Number and the likes of
+ * booleanValue unbox it, and toString, hashCode and equals are those of the value
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/Integer.html
+ */
+fn create_primitive_wrapper(jvm: &RuntimeConst, name: &str, super_class: &str, primitive: &str) -> JVMClass {
+    let mut methods = HashMap::new();
+    let descriptor = class_descriptor(primitive);
+    let value_of: Option<NativeFunction> = match primitive {
+        "boolean" => Some(|jvm: &RuntimeConst, args: &[RuntimeValue]| box_value(jvm, args[0].clone(), &Descriptor::Boolean)),
+        "char" => Some(|jvm: &RuntimeConst, args: &[RuntimeValue]| box_value(jvm, args[0].clone(), &Descriptor::Char)),
+        "byte" => Some(|jvm: &RuntimeConst, args: &[RuntimeValue]| box_value(jvm, args[0].clone(), &Descriptor::Byte)),
+        "short" => Some(|jvm: &RuntimeConst, args: &[RuntimeValue]| box_value(jvm, args[0].clone(), &Descriptor::Short)),
+        "int" => Some(|jvm: &RuntimeConst, args: &[RuntimeValue]| box_value(jvm, args[0].clone(), &Descriptor::Int)),
+        "long" => Some(|jvm: &RuntimeConst, args: &[RuntimeValue]| box_value(jvm, args[0].clone(), &Descriptor::Long)),
+        "float" => Some(|jvm: &RuntimeConst, args: &[RuntimeValue]| box_value(jvm, args[0].clone(), &Descriptor::Float)),
+        "double" => Some(|jvm: &RuntimeConst, args: &[RuntimeValue]| box_value(jvm, args[0].clone(), &Descriptor::Double)),
+        _ => None,
+    };
+    if let Some(value_of) = value_of {
+        methods.insert(method_key("valueOf", &format!("({})L{};", descriptor, name)), JVMMethod::Native(value_of));
+        methods.insert(method_key("toString", "()Ljava/lang/String;"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+            let (value, primitive) = unbox_value(&args[0], &Descriptor::Void)?;
+            return Ok(new_string(&string_value_of(jvm, &value, &primitive)?));
+        }));
+        methods.insert(method_key("hashCode", "()I"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+            let (value, primitive) = unbox_value(&args[0], &Descriptor::Void)?;
+            return Ok(RuntimeValue::Int(hash_code(jvm, &value, &primitive)?));
+        }));
+        methods.insert(method_key("equals", "(Ljava/lang/Object;)Z"), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+            let (value, _) = unbox_value(&args[0], &Descriptor::Void)?;
+            let equal = match &args[1] {
+                RuntimeValue::Object(other) if other.borrow().class == args[0].class_name().unwrap_or_default() => {
+                    values_equal(jvm, &value, &unbox_value(&args[1], &Descriptor::Void)?.0)?
+                },
+                _ => false,
+            };
+            return Ok(RuntimeValue::Int(equal as i32));
+        }));
+    }
+
+    // the methods that give the value, each as the type it is named after
+    let unboxing: &[(&str, &str, NativeFunction)] = &[
+        ("byteValue", "()B", |_jvm: &RuntimeConst, args: &[RuntimeValue]| cast_primitive(&unbox_value(&args[0], &Descriptor::Byte)?.0, &Descriptor::Byte)),
+        ("shortValue", "()S", |_jvm: &RuntimeConst, args: &[RuntimeValue]| cast_primitive(&unbox_value(&args[0], &Descriptor::Short)?.0, &Descriptor::Short)),
+        ("intValue", "()I", |_jvm: &RuntimeConst, args: &[RuntimeValue]| cast_primitive(&unbox_value(&args[0], &Descriptor::Int)?.0, &Descriptor::Int)),
+        ("longValue", "()J", |_jvm: &RuntimeConst, args: &[RuntimeValue]| cast_primitive(&unbox_value(&args[0], &Descriptor::Long)?.0, &Descriptor::Long)),
+        ("floatValue", "()F", |_jvm: &RuntimeConst, args: &[RuntimeValue]| cast_primitive(&unbox_value(&args[0], &Descriptor::Float)?.0, &Descriptor::Float)),
+        ("doubleValue", "()D", |_jvm: &RuntimeConst, args: &[RuntimeValue]| cast_primitive(&unbox_value(&args[0], &Descriptor::Double)?.0, &Descriptor::Double)),
+    ];
+    match primitive {
+        "boolean" | "char" => {
+            let method_name = format!("{}Value", primitive);
+            methods.insert(method_key(&method_name, &format!("(){}", descriptor)), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+                return Ok(unbox_value(&args[0], &Descriptor::Void)?.0);
+            }));
+        },
+        "void" => {
+        },
+        _ => {
+            for (method_name, method_descriptor, method) in unboxing {
+                methods.insert(method_key(method_name, method_descriptor), JVMMethod::Native(*method));
+            }
+        }
+    }
 
-                    frame.push_value(create_new_object(constant_pool, jvm, total)?);
+    let mut wrapper = create_native_class(name, super_class, methods);
+    wrapper.access_flags = ACC_PUBLIC | ACC_FINAL;
+    if primitive != "void" {
+        wrapper.interfaces = vec!["java/io/Serializable".to_string(), "java/lang/Comparable".to_string()];
+    }
+    wrapper.fields.borrow_mut().insert("TYPE".to_string(), jvm.class_object(primitive));
+    // the objects valueOf boxes true and false into
+    if primitive == "boolean" {
+        for (field, value) in [("FALSE", 0), ("TRUE", 1)] {
+            if let Ok(boxed) = box_value(jvm, RuntimeValue::Int(value), &Descriptor::Boolean) {
+                wrapper.fields.borrow_mut().insert(field.to_string(), boxed);
+            }
+        }
+    }
+    return wrapper;
+}
 
-                    pc += 3;
-                },
-                opcodes::GETFIELD => {
-                    let b1 = code[pc+1] as usize;
-                    let b2 = code[pc+2] as usize;
-                    let total = (b1 << 8) | b2;
-                    let objectref = frame.pop_value_force()?;
-                    frame.push_value(getfield(constant_pool, jvm, total, objectref)?);
-                    pc += 3;
-                },
-                opcodes::PUTFIELD => {
-                    let b1 = code[pc+1] as usize;
-                    let b2 = code[pc+2] as usize;
-                    let total = (b1 << 8) | b2;
+// javac checks the receiver of a bound method reference with Objects.requireNonNull
+fn create_java_util_objects() -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("requireNonNull".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match args.first() {
+            Some(RuntimeValue::Null) | None => Err(JVMError::null_pointer()),
+            Some(value) => Ok(value.clone()),
+        }
+    }));
+    return create_native_class("java/util/Objects", "java/lang/Object", methods);
+}
 
-                    let value = frame.pop_value_force()?;
-                    let objectref = frame.pop_value_force()?;
+/* the bootstrap methods javac uses for string concatenation. the call site gets a handle on a
+ * native that builds the string from the recipe, in which \u{1} stands for the next argument and
+ * \u{2} for the next constant
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/invoke/StringConcatFactory.html
+ */
+fn create_java_lang_invoke_string_concat_factory() -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("makeConcatWithConstants".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let (concat_type, recipe, constants) = match args {
+            [_lookup, _name, concat_type, recipe @ RuntimeValue::String(_), constants] => (concat_type, recipe, constants),
+            _ => return Err(format!("invalid arguments to makeConcatWithConstants: {:?}", args).into()),
+        };
+        return create_string_concat_call_site(concat_type, recipe.clone(), constants.clone());
+    }));
+    methods.insert("makeConcat".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let concat_type = match args {
+            [_lookup, _name, concat_type] => concat_type,
+            _ => return Err(format!("invalid arguments to makeConcat: {:?}", args).into()),
+        };
+        let count = parse_method_descriptor(&string_field(concat_type, "descriptor")?)?.parameters.len();
+        return create_string_concat_call_site(concat_type, new_string(&"\u{1}".repeat(count)), new_array("[Ljava/lang/Object;", 0)?);
+    }));
+    methods.insert("concat".to_string(), JVMMethod::Native(string_concat));
 
-                    putfield(constant_pool, jvm, total, objectref, value)?;
+    return create_native_class("java/lang/invoke/StringConcatFactory", "java/lang/Object", methods);
+}
 
-                    pc += 3;
-                },
-                opcodes::INVOKESPECIAL => {
-                    let b1 = code[pc+1] as usize;
-                    let b2 = code[pc+2] as usize;
-                    let total = (b1 << 8) | b2;
-                    invoke_special(constant_pool, frame, jvm, total)?;
-                    pc += 3;
-                },
-                opcodes::INVOKESTATIC => {
-                    let b1 = code[pc+1] as usize;
-                    let b2 = code[pc+2] as usize;
-                    let total = (b1 << 8) | b2;
-
-                    match invoke_static(constant_pool, frame, jvm, total)? {
-                        RuntimeValue::Void => {},
-                        r => {
-                            debug!("got back value {:?}", r);
-                            frame.stack.push(r);
-                        }
-                    }
+fn create_string_concat_call_site(concat_type: &RuntimeValue, recipe: RuntimeValue, constants: RuntimeValue) -> Result<RuntimeValue, JVMError> {
+    let invoked_type = string_field(concat_type, "descriptor")?;
+    let parameters = match (invoked_type.find('('), invoked_type.rfind(')')) {
+        (Some(start), Some(end)) => &invoked_type[start + 1..end],
+        _ => return Err(format!("invalid concatenation type {}", invoked_type).into()),
+    };
 
-                    pc += 3;
-                },
-                opcodes::GETSTATIC => {
-                    debug!("Get static");
-                    let b1 = code[pc+1] as usize;
-                    let b2 = code[pc+2] as usize;
-                    let total = (b1 << 8) | b2;
+    let descriptor = format!("(Ljava/lang/invoke/MethodType;Ljava/lang/String;[Ljava/lang/Object;{})Ljava/lang/String;", parameters);
+    let target = new_method_handle(REF_INVOKE_STATIC, "java/lang/invoke/StringConcatFactory", "concat", &descriptor, false);
+    let target = bind_arguments(target, vec![concat_type.clone(), recipe, constants])?;
 
-                    pc += 2;
+    let mut call_site_fields = HashMap::new();
+    call_site_fields.insert("target".to_string(), target);
+    return Ok(RuntimeValue::Object(rc::Rc::new(cell::RefCell::new(JVMObject{
+        class: "java/lang/invoke/ConstantCallSite".to_string(),
+        fields: call_site_fields,
+    }))));
+}
 
-                    op_getstatic(constant_pool, frame, jvm, total)?;
+// the target of a string concatenation call site, with the type, recipe and constants bound to it
+fn string_concat(jvm: &RuntimeConst, args: &[RuntimeValue]) -> Result<RuntimeValue, JVMError> {
+    let (concat_type, recipe, constants, values) = match args {
+        [concat_type, RuntimeValue::String(recipe), RuntimeValue::Array(constants), values @ ..] => (concat_type, recipe, constants, values),
+        _ => return Err(format!("invalid arguments to string concatenation: {:?}", args).into()),
+    };
+    let types = parse_method_descriptor(&string_field(concat_type, "descriptor")?)?.parameters;
 
-                    pc += 1;
-                },
-                opcodes::INVOKEVIRTUAL => {
-                    let b1 = code[pc+1] as usize;
-                    let b2 = code[pc+2] as usize;
-                    let total = (b1 << 8) | b2;
-
-                    match invoke_virtual(constant_pool, frame, jvm, total)? {
-                        RuntimeValue::Void => {},
-                        r => {
-                            frame.stack.push(r);
-                        }
-                    }
+    /* strings and chars are added as UTF-16, so the halves of a surrogate pair can come from
+     * different values. the result stays Latin-1 for as long as what is added is
+     */
+    let push_value = |result: &mut JVMString, value: &RuntimeValue, kind: &Descriptor| -> Result<(), JVMError> {
+        match (value, kind) {
+            (RuntimeValue::String(string), _) => result.push_str(&string.borrow()),
+            (RuntimeValue::Int(value), Descriptor::Char) => result.push(*value as u16),
+            (value, kind) => result.push_str(&JVMString::from(string_value_of(jvm, value, kind)?.as_str())),
+        }
+        return Ok(());
+    };
 
-                    pc += 3;
-                },
-                opcodes::RETURN => {
-                    return Ok(RuntimeValue::Void);
-                },
-                opcodes::PUSHRUNTIMECONSTANT => {
-                    let index = code[pc+1] as usize;
-                    push_runtime_constant(constant_pool, frame, index)?;
-                    pc += 2;
-                },
-                _ => {
-                    return Err(format!("Unknown opcode pc={} opcode=0x{:x}", pc, code[pc]));
+    let recipe = recipe.borrow().chars().into_owned();
+    let mut result = JVMString::new(Vec::new());
+    let mut next_value = 0;
+    let mut next_constant = 0;
+    for c in &recipe {
+        match c {
+            1 => {
+                match (values.get(next_value), types.get(next_value)) {
+                    (Some(value), Some(kind)) => push_value(&mut result, value, kind)?,
+                    _ => return Err(format!("too few arguments for concatenation recipe {:?}", String::from_utf16_lossy(&recipe)).into()),
                 }
+                next_value += 1;
+            },
+            2 => {
+                let constant = constants.borrow().load(next_constant)?;
+                push_value(&mut result, &constant, &Descriptor::Object("java/lang/Object".to_string()))?;
+                next_constant += 1;
+            },
+            c => {
+                result.push(*c);
             }
         }
-
-    } else {
-        return Err("no code attribute".to_string());
     }
-
-    return Ok(RuntimeValue::Void);
+    return Ok(RuntimeValue::String(rc::Rc::new(cell::RefCell::new(result))));
 }
 
-fn create_stdout_object() -> rc::Rc<cell::RefCell<JVMObject>> {
-    return rc::Rc::new(cell::RefCell::new(JVMObject{
-        class: "java/io/PrintStream".to_string(),
-        fields: HashMap::new()
-    }));
+/* a value as String.valueOf turns it into a string. the type tells apart the primitives that are
+ * all ints on the stack
+ */
+fn string_value_of(jvm: &RuntimeConst, value: &RuntimeValue, kind: &Descriptor) -> Result<String, JVMError> {
+    match (value, kind) {
+        (RuntimeValue::Int(value), Descriptor::Boolean) => Ok(if *value != 0 { "true" } else { "false" }.to_string()),
+        (RuntimeValue::Int(value), Descriptor::Char) => Ok(String::from_utf16_lossy(&[*value as u16])),
+        (RuntimeValue::Int(value), _) => Ok(value.to_string()),
+        (RuntimeValue::Long(value), _) => Ok(value.to_string()),
+        (RuntimeValue::Float(value), _) => Ok(java_float_to_string(*value)),
+        (RuntimeValue::Double(value), _) => Ok(java_double_to_string(*value)),
+        (RuntimeValue::String(value), _) => Ok(value.borrow().to_string()),
+        (RuntimeValue::Null, _) => Ok("null".to_string()),
+        (RuntimeValue::Object(object), _) => {
+            match call_virtual(jvm, value, "toString", "()Ljava/lang/String;", Vec::new())? {
+                Some(RuntimeValue::String(string)) => Ok(string.borrow().to_string()),
+                Some(_) => Ok("null".to_string()),
+                None => Ok(format!("{}@{:x}", external_class_name(&object.borrow().class), value.identity_hash_code())),
+            }
+        },
+        (RuntimeValue::Array(array), _) => Ok(format!("{}@{:x}", external_class_name(&array.borrow().class), value.identity_hash_code())),
+        (value, _) => Err(format!("cannot convert {:?} to a string", value).into()),
+    }
 }
 
-fn create_java_io_print_stream<'a>() -> JVMClass<'a> {
+// the flags of LambdaMetafactory.altMetafactory
+const FLAG_SERIALIZABLE: i32 = 1;
+const FLAG_MARKERS: i32 = 2;
+
+fn create_java_lang_invoke_lambda_metafactory() -> JVMClass {
     let mut methods = HashMap::new();
-    methods.insert("println".to_string(), JVMMethod::Native(|args: &[RuntimeValue]| {
-        for arg in &args[1..] {
-            match arg {
-                RuntimeValue::String(s) => {
-                    println!("{}", s);
-                },
-                RuntimeValue::Int(i) => {
-                    println!("{}", i);
-                },
-                _ => {
-                    println!("Unknown value type for println: {:?}", arg);
-                }
+    methods.insert("metafactory".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return create_lambda_call_site(jvm, args, Vec::new());
+    }));
+
+    /* altMetafactory takes the arguments of metafactory followed by flags and, depending on them,
+     * marker interfaces and bridge method types. the methods of a lambda class are native and
+     * take care of every descriptor of their name, so bridges need nothing
+     */
+    methods.insert("altMetafactory".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        let extra = match args.get(3) {
+            Some(RuntimeValue::Array(extra)) => extra.clone(),
+            value => return Err(format!("altMetafactory expects an array of arguments, not {:?}", value).into()),
+        };
+        let extra = extra.borrow();
+        let mut metafactory_args = args[..3].to_vec();
+        for index in 0..extra.length() {
+            metafactory_args.push(extra.load(index as i32)?);
+        }
+        let args = metafactory_args.as_slice();
+
+        let flags = match args.get(6) {
+            Some(RuntimeValue::Int(flags)) => *flags,
+            _ => 0,
+        };
+
+        let mut interfaces = Vec::new();
+        if flags & FLAG_MARKERS != 0 {
+            let count = match args.get(7) {
+                Some(RuntimeValue::Int(count)) => *count as usize,
+                _ => 0,
+            };
+            for marker in args.iter().skip(8).take(count) {
+                interfaces.push(string_field(marker, "name")?);
             }
         }
+        if flags & FLAG_SERIALIZABLE != 0 {
+            interfaces.push("java/io/Serializable".to_string());
+        }
 
-        return RuntimeValue::Void;
+        return create_lambda_call_site(jvm, args, interfaces);
     }));
 
-    let fields = HashMap::new();
+    return create_native_class("java/lang/invoke/LambdaMetafactory", "java/lang/Object", methods);
+}
 
-    return JVMClass{
-        class: "java/io/PrintStream".to_string(),
+/* the call site of a lambda expression or method reference: a class implementing the functional
+ * interface is made for it, and the target of the call site makes an instance of the class that
+ * holds on to the captured values. the arguments are those of LambdaMetafactory.metafactory: the
+ * lookup, the name of the interface method, the type of the call site, with the captured values
+ * as parameters and the interface as return type, the erased type of the interface method, the
+ * implementation and the type of the interface method once its type variables are filled in
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/invoke/LambdaMetafactory.html
+ */
+fn create_lambda_call_site(jvm: &RuntimeConst, args: &[RuntimeValue], extra_interfaces: Vec<String>) -> Result<RuntimeValue, JVMError> {
+    if args.len() < 6 {
+        return Err(format!("lambda metafactory called with {} arguments", args.len()).into());
+    }
+    let caller = string_field(&args[0], "lookupClass")?;
+    let name = match &args[1] {
+        RuntimeValue::String(name) => name.borrow().to_string(),
+        value => return Err(format!("lambda method name was not a string: {:?}", value).into()),
+    };
+    let invoked_type = string_field(&args[2], "descriptor")?;
+    let invoked = parse_method_descriptor(&invoked_type)?;
+    let interface = match &invoked.return_type {
+        Descriptor::Object(interface) => interface.clone(),
+        _ => return Err(format!("lambda call site {} does not return an interface", invoked_type).into()),
+    };
+
+    // the values the lambda captures are kept in fields named like hotspot names them
+    let mut instance_fields = HashMap::new();
+    for (index, parameter) in invoked.parameters.iter().enumerate() {
+        instance_fields.insert(format!("arg${}", index + 1), default_value(parameter));
+    }
+
+    let mut methods = HashMap::new();
+    methods.insert("<init>".to_string(), JVMMethod::Native(lambda_init));
+    methods.insert(name, JVMMethod::Invoker(lambda_invoke));
+
+    let mut fields = HashMap::new();
+    fields.insert("implementation".to_string(), args[4].clone());
+    fields.insert("instantiatedMethodType".to_string(), args[5].clone());
+
+    let mut interfaces = vec![interface];
+    interfaces.extend(extra_interfaces);
+
+    jvm.lambda_count.set(jvm.lambda_count.get() + 1);
+    let class_name = format!("{}$$Lambda${}", caller, jvm.lambda_count.get());
+    jvm.link_class(JVMClass{
+        class: class_name.clone(),
+        access_flags: ACC_FINAL | ACC_SYNTHETIC,
+        major_version: 0,
+        super_class: Some("java/lang/Object".to_string()),
+        interfaces: interfaces,
+        source_file: None,
+        constant_pool: rc::Rc::new(Vec::new()),
         methods: methods,
-        fields: fields,
+        fields: cell::RefCell::new(fields),
+        instance_fields: instance_fields,
+        object_fields: HashMap::new(),
+        field_owners: HashMap::new(),
+        bootstrap_methods: Vec::new(),
+        call_sites: cell::RefCell::new(HashMap::new()),
+        dynamic_constants: cell::RefCell::new(HashMap::new()),
+        subtype_cache: cell::RefCell::new(HashMap::new()),
+        initialization: cell::RefCell::new(Initialization::NotStarted),
+    })?;
+
+    let parameters = match invoked_type.rfind(')') {
+        Some(end) => &invoked_type[..=end],
+        None => return Err(format!("invalid call site type {}", invoked_type).into()),
+    };
+    let constructor = new_method_handle(REF_NEW_INVOKE_SPECIAL, &class_name, "<init>", &format!("{}V", parameters), false);
+
+    let mut call_site_fields = HashMap::new();
+    call_site_fields.insert("target".to_string(), constructor);
+    return Ok(RuntimeValue::Object(rc::Rc::new(cell::RefCell::new(JVMObject{
+        class: "java/lang/invoke/ConstantCallSite".to_string(),
+        fields: call_site_fields,
+    }))));
+}
+
+// the constructor of a lambda class, which keeps the captured values
+fn lambda_init(_jvm: &RuntimeConst, args: &[RuntimeValue]) -> Result<RuntimeValue, JVMError> {
+    if let Some(RuntimeValue::Object(this)) = args.first() {
+        let mut this = this.borrow_mut();
+        for (index, value) in args.iter().skip(1).enumerate() {
+            this.fields.insert(format!("arg${}", index + 1), value.clone());
+        }
     }
+    return Ok(RuntimeValue::Void);
 }
 
-fn create_java_lang_system<'a>() -> JVMClass<'a> {
-    let mut fields = HashMap::new();
+/* the interface method of a lambda class, which invokes the implementation with the captured
+ * values followed by its own arguments. the implementation runs in place of the interface method
+ * when it returns what that does as it is, so recursion through a lambda uses the frame stack
+ */
+fn lambda_invoke(jvm: &RuntimeConst, args: &[RuntimeValue]) -> Result<Invocation, JVMError> {
+    let this = match args.first() {
+        Some(RuntimeValue::Object(this)) => this.clone(),
+        _ => return Err("lambda method invoked without a receiver".to_string().into()),
+    };
+    let class = jvm.load_class(&this.borrow().class)?;
+    let (implementation, instantiated_type) = {
+        let statics = class.fields.borrow();
+        (statics.get("implementation").cloned().unwrap_or(RuntimeValue::Null), statics.get("instantiatedMethodType").cloned().unwrap_or(RuntimeValue::Null))
+    };
 
-    fields.insert("out".to_string(), RuntimeValue::Object(create_stdout_object()));
+    let mut arguments = Vec::new();
+    for index in 1..=class.instance_fields.len() {
+        arguments.push(this.borrow().fields.get(&format!("arg${}", index)).cloned().unwrap_or(RuntimeValue::Null));
+    }
 
-    let mut methods = HashMap::new();
+    /* the arguments are of the types of the instantiated method type, which the implementation
+     * may take boxed or unboxed, as in a Function<Integer, Integer> made from a method on ints
+     */
+    let instantiated = parse_method_descriptor(&string_field(&instantiated_type, "descriptor")?)?;
+    let implementation_type = parse_method_descriptor(&handle_type(&implementation)?)?;
+    let captured = arguments.len();
+    for (index, argument) in args.iter().skip(1).enumerate() {
+        match (instantiated.parameters.get(index), implementation_type.parameters.get(captured + index)) {
+            (Some(from), Some(to)) => arguments.push(convert_value(jvm, argument.clone(), from, to)?),
+            _ => arguments.push(argument.clone()),
+        }
+    }
 
-    return JVMClass{
-        class: "java/lang/System".to_string(),
-        methods: methods,
-        fields: fields
+    let return_type = instantiated.return_type;
+    let implementation_return = implementation_type.return_type;
+    let unconverted = match (&implementation_return, &return_type) {
+        (Descriptor::Object(_) | Descriptor::Array(_), Descriptor::Object(_) | Descriptor::Array(_)) => true,
+        (from, to) => descriptor_external_name(from) == descriptor_external_name(to),
     };
+    match method_handle_invocation(jvm, &implementation, arguments)? {
+        Invocation::Call(frame) if unconverted => {
+            return Ok(Invocation::Call(frame));
+        },
+        invocation => {
+            let result = run_invocation(jvm, invocation)?;
+            return Ok(Invocation::Completed(convert_value(jvm, result, &implementation_return, &return_type)?));
+        }
+    }
 }
 
-fn create_frame(method: &MethodInfo) -> Result<Frame, String> {
-    if let Some(AttributeKind::Code { max_stack: _, max_locals, code: _, exception_table: _, attributes: _ }) = lookup_code_attribute(method) {
-        let mut locals = Vec::new();
+// the throwable classes provided by the jvm, along with their superclass
+const THROWABLE_CLASSES: &[(&str, &str)] = &[
+    ("java/lang/Exception", "java/lang/Throwable"),
+    ("java/lang/Error", "java/lang/Throwable"),
+    ("java/lang/RuntimeException", "java/lang/Exception"),
+    ("java/lang/IllegalArgumentException", "java/lang/RuntimeException"),
+    ("java/lang/IllegalStateException", "java/lang/RuntimeException"),
+    ("java/lang/MatchException", "java/lang/RuntimeException"),
+    ("java/lang/UnsupportedOperationException", "java/lang/RuntimeException"),
+    ("java/lang/NumberFormatException", "java/lang/IllegalArgumentException"),
+    ("java/lang/ArithmeticException", "java/lang/RuntimeException"),
+    ("java/lang/NullPointerException", "java/lang/RuntimeException"),
+    ("java/lang/ClassCastException", "java/lang/RuntimeException"),
+    ("java/lang/NegativeArraySizeException", "java/lang/RuntimeException"),
+    ("java/lang/ArrayStoreException", "java/lang/RuntimeException"),
+    ("java/lang/IndexOutOfBoundsException", "java/lang/RuntimeException"),
+    ("java/lang/ArrayIndexOutOfBoundsException", "java/lang/IndexOutOfBoundsException"),
+    ("java/lang/StringIndexOutOfBoundsException", "java/lang/IndexOutOfBoundsException"),
+    ("java/io/IOException", "java/lang/Exception"),
+    ("java/lang/ReflectiveOperationException", "java/lang/Exception"),
+    ("java/lang/CloneNotSupportedException", "java/lang/Exception"),
+    ("java/lang/InterruptedException", "java/lang/Exception"),
+    ("java/lang/NoSuchMethodException", "java/lang/ReflectiveOperationException"),
+    ("java/lang/NoSuchFieldException", "java/lang/ReflectiveOperationException"),
+    ("java/lang/IllegalAccessException", "java/lang/ReflectiveOperationException"),
+    ("java/lang/invoke/WrongMethodTypeException", "java/lang/RuntimeException"),
+    ("java/lang/LinkageError", "java/lang/Error"),
+    ("java/lang/VerifyError", "java/lang/LinkageError"),
+    ("java/lang/NoClassDefFoundError", "java/lang/LinkageError"),
+    ("java/lang/ExceptionInInitializerError", "java/lang/LinkageError"),
+    ("java/lang/BootstrapMethodError", "java/lang/LinkageError"),
+    ("java/lang/UnsatisfiedLinkError", "java/lang/LinkageError"),
+    ("java/lang/IncompatibleClassChangeError", "java/lang/LinkageError"),
+    ("java/lang/NoSuchFieldError", "java/lang/IncompatibleClassChangeError"),
+    ("java/lang/NoSuchMethodError", "java/lang/IncompatibleClassChangeError"),
+    ("java/lang/AbstractMethodError", "java/lang/IncompatibleClassChangeError"),
+    ("java/lang/InstantiationError", "java/lang/IncompatibleClassChangeError"),
+    ("java/lang/VirtualMachineError", "java/lang/Error"),
+    ("java/lang/InternalError", "java/lang/VirtualMachineError"),
+    ("java/lang/StackOverflowError", "java/lang/VirtualMachineError"),
+];
 
-        for _i in 0..*max_locals {
-            locals.push(RuntimeValue::Void);
+/* the result of Throwable.toString, the class name followed by the message if there is one */
+fn throwable_to_string(throwable: &JVMObject) -> String {
+    match throwable.fields.get("detailMessage") {
+        Some(RuntimeValue::String(message)) => {
+            return format!("{}: {}", external_class_name(&throwable.class), message.borrow());
+        },
+        _ => {
+            return external_class_name(&throwable.class);
         }
+    }
+}
 
-        return Ok(Frame{
-            stack: Vec::new(),
-            locals,
-        })
+fn new_stack_trace_element(class_name: &str, method_name: &str, file_name: Option<&String>, line_number: i32) -> RuntimeValue {
+    let mut fields = HashMap::new();
+    fields.insert("declaringClass".to_string(), new_string(&external_class_name(class_name)));
+    fields.insert("methodName".to_string(), new_string(method_name));
+    fields.insert("fileName".to_string(), match file_name {
+        Some(file_name) => new_string(file_name),
+        None => RuntimeValue::Null,
+    });
+    fields.insert("lineNumber".to_string(), RuntimeValue::Int(line_number));
+
+    return RuntimeValue::Object(rc::Rc::new(cell::RefCell::new(JVMObject{
+        class: "java/lang/StackTraceElement".to_string(),
+        fields: fields,
+    })));
+}
+
+/* StackTraceElement.toString, such as Main.run(Main.java:12). classes of the application class
+ * loader are shown without a loader or module name
+ */
+fn stack_trace_element_to_string(element: &JVMObject) -> String {
+    let field = |name: &str| element.fields.get(name).cloned().unwrap_or(RuntimeValue::Null);
+    let location = match (field("fileName"), field("lineNumber")) {
+        (_, RuntimeValue::Int(-2)) => "Native Method".to_string(),
+        (RuntimeValue::String(file_name), RuntimeValue::Int(line_number)) if line_number >= 0 => format!("{}:{}", file_name.borrow(), line_number),
+        (RuntimeValue::String(file_name), _) => file_name.borrow().to_string(),
+        _ => "Unknown Source".to_string(),
+    };
+
+    match (field("declaringClass"), field("methodName")) {
+        (RuntimeValue::String(class_name), RuntimeValue::String(method_name)) => format!("{}.{}({})", class_name.borrow(), method_name.borrow(), location),
+        _ => format!("({})", location),
+    }
+}
+
+// hotspot's MaxJavaStackTraceDepth, which keeps the trace of a StackOverflowError manageable
+const MAX_STACK_TRACE_DEPTH: usize = 1024;
+
+/* record the methods currently on the call stack, innermost first. the frames of the throwable's
+ * own constructors are left out, like hotspot does, so the trace starts where it was created
+ */
+fn capture_stack_trace(jvm: &RuntimeConst, throwable_class: &str) -> RuntimeValue {
+    let mut elements = Vec::new();
+    let mut in_constructor = true;
+    for call in jvm.call_stack.borrow().iter().rev() {
+        if in_constructor && call.method == "<init>" && jvm.is_assignable(throwable_class, &call.class) {
+            continue;
+        }
+        in_constructor = false;
+        if elements.len() == MAX_STACK_TRACE_DEPTH {
+            break;
+        }
+
+        let (source_file, line_number) = match jvm.lookup_class(&call.class) {
+            Some(class) => (class.source_file.clone(), line_number(&call.method_info, call.pc)),
+            None => (None, line_number(&call.method_info, call.pc)),
+        };
+        elements.push(new_stack_trace_element(&call.class, &call.method, source_file.as_ref(), line_number));
     }
 
-    return Err("no code attribute".to_string());
+    return RuntimeValue::Array(rc::Rc::new(cell::RefCell::new(JVMArray{
+        class: "[Ljava/lang/StackTraceElement;".to_string(),
+        elements: ArrayElements::Reference(elements),
+    })));
 }
 
-fn create_java_lang_object<'a>() -> JVMClass<'a> {
+/* what a class whose initializer threw remembers of the exception: an ExceptionInInitializerError
+ * describing it, with the stack trace of where it was thrown
+ */
+fn initialization_failure(exception: &rc::Rc<cell::RefCell<JVMObject>>) -> rc::Rc<cell::RefCell<JVMObject>> {
+    let exception = exception.borrow();
+    let message = format!("Exception {} [in thread \"main\"]", throwable_to_string(&exception));
     let mut fields = HashMap::new();
-    let mut methods = HashMap::new();
+    fields.insert("detailMessage".to_string(), new_string(&message));
+    fields.insert("cause".to_string(), RuntimeValue::Null);
+    if let Some(trace) = exception.fields.get("stackTrace") {
+        fields.insert("stackTrace".to_string(), trace.clone());
+    }
 
-    methods.insert("<init>".to_string(), JVMMethod::Native(|_args: &[RuntimeValue]| {
-        return RuntimeValue::Void;
+    return rc::Rc::new(cell::RefCell::new(JVMObject{
+        class: "java/lang/ExceptionInInitializerError".to_string(),
+        fields: fields,
     }));
+}
 
-    return JVMClass{
-        class: "java/lang/Object".to_string(),
-        methods: methods,
-        fields: fields,
+/* exceptions raised by the jvm itself get their stack trace when they first reach a method
+ * on the call stack, java code creates them with one already
+ */
+fn ensure_stack_trace(jvm: &RuntimeConst, throwable: &rc::Rc<cell::RefCell<JVMObject>>) {
+    let has_trace = match throwable.borrow().fields.get("stackTrace") {
+        Some(RuntimeValue::Array(_)) => true,
+        _ => false,
     };
+
+    if !has_trace {
+        let class = throwable.borrow().class.clone();
+        let trace = capture_stack_trace(jvm, &class);
+        throwable.borrow_mut().fields.insert("stackTrace".to_string(), trace);
+    }
 }
 
-fn create_runtime_const<'a>() -> RuntimeConst<'a> {
-    let mut classes = HashMap::new();
+// the elements of a reference array held in a field of a throwable
+fn throwable_references(throwable: &JVMObject, field: &str) -> Vec<rc::Rc<cell::RefCell<JVMObject>>> {
+    let mut references = Vec::new();
+    if let Some(RuntimeValue::Array(array)) = throwable.fields.get(field) {
+        if let ArrayElements::Reference(elements) = &array.borrow().elements {
+            for element in elements {
+                if let RuntimeValue::Object(object) = element {
+                    references.push(object.clone());
+                }
+            }
+        }
+    }
 
-    classes.insert("java/lang/System".to_string(), create_java_lang_system());
-    classes.insert("java/io/PrintStream".to_string(), create_java_io_print_stream());
-    classes.insert("java/lang/Object".to_string(), create_java_lang_object());
+    return references;
+}
 
-    return RuntimeConst{
-        classes: classes,
+fn throwable_cause(throwable: &JVMObject) -> Option<rc::Rc<cell::RefCell<JVMObject>>> {
+    match throwable.fields.get("cause") {
+        Some(RuntimeValue::Object(cause)) => Some(cause.clone()),
+        _ => None,
     }
 }
 
-pub fn execute_method(jvm: &JVMClassFile, name: &str) -> Result<RuntimeValue, String> {
-    // find method named 'name'
-    // start executing byte code at that method
+/* the output of Throwable.printStackTrace: the throwable and its stack trace, followed by its
+ * suppressed exceptions and its cause. frames an enclosed trace shares with the one enclosing it
+ * are abbreviated to "... n more"
+ */
+fn format_stack_trace(throwable: &rc::Rc<cell::RefCell<JVMObject>>) -> String {
+    let mut out = String::new();
+    let mut seen = vec![throwable.clone()];
+    format_enclosed_stack_trace(&mut out, throwable, &[], "", "", &mut seen);
+    return out;
+}
 
-    for i in 0..jvm.methods.len() {
-        /*
-        match lookup_utf8_constant(jvm, jvm.methods[i].descriptor_index as usize) {
-            Some(descriptor_name) => {
-                println!("Method {} descriptor {}", i, descriptor_name);
-            },
-            None => {
-                println!("Error: method {} descriptor index {} is invalid", i, jvm.methods[i].descriptor_index);
-            }
+fn format_enclosed_stack_trace(out: &mut String, throwable: &rc::Rc<cell::RefCell<JVMObject>>, enclosing_trace: &[String],
+                               caption: &str, prefix: &str, seen: &mut Vec<rc::Rc<cell::RefCell<JVMObject>>>) {
+    let object = throwable.borrow();
+    if !caption.is_empty() {
+        if seen.iter().any(|other| rc::Rc::ptr_eq(other, throwable)) {
+            out.push_str(&format!("{}{}[CIRCULAR REFERENCE: {}]\n", prefix, caption, throwable_to_string(&object)));
+            return;
+        }
+        seen.push(throwable.clone());
+    }
+
+    let trace: Vec<String> = throwable_references(&object, "stackTrace").iter().map(|element| stack_trace_element_to_string(&element.borrow())).collect();
+    let mut unique = trace.len();
+    let mut enclosing = enclosing_trace.len();
+    while unique > 0 && enclosing > 0 && trace[unique - 1] == enclosing_trace[enclosing - 1] {
+        unique -= 1;
+        enclosing -= 1;
+    }
+
+    out.push_str(&format!("{}{}{}\n", prefix, caption, throwable_to_string(&object)));
+    for element in &trace[..unique] {
+        out.push_str(&format!("{}\tat {}\n", prefix, element));
+    }
+    if unique != trace.len() {
+        out.push_str(&format!("{}\t... {} more\n", prefix, trace.len() - unique));
+    }
+
+    for suppressed in throwable_references(&object, "suppressedExceptions") {
+        format_enclosed_stack_trace(out, &suppressed, &trace, "Suppressed: ", &format!("{}\t", prefix), seen);
+    }
+
+    if let Some(cause) = throwable_cause(&object) {
+        format_enclosed_stack_trace(out, &cause, &trace, "Caused by: ", prefix, seen);
+    }
+}
+
+/* the constructors Throwable(), Throwable(String), Throwable(Throwable) and Throwable(String, Throwable).
+ * methods are only looked up by name, so which one was called is decided by the arguments
+ */
+fn throwable_init(jvm: &RuntimeConst, args: &[RuntimeValue]) -> Result<RuntimeValue, JVMError> {
+    if let Some(RuntimeValue::Object(this)) = args.first() {
+        let (message, cause) = match &args[1..] {
+            // a throwable made from a cause takes the cause's description as its message
+            [RuntimeValue::Object(cause)] => (new_string(&throwable_to_string(&cause.borrow())), RuntimeValue::Object(cause.clone())),
+            [message] => (message.clone(), RuntimeValue::Null),
+            [message, cause] => (message.clone(), cause.clone()),
+            _ => (RuntimeValue::Null, RuntimeValue::Null),
+        };
+
+        let class = this.borrow().class.clone();
+        let trace = capture_stack_trace(jvm, &class);
+        let mut this = this.borrow_mut();
+        this.fields.insert("detailMessage".to_string(), message);
+        this.fields.insert("cause".to_string(), cause);
+        this.fields.insert("stackTrace".to_string(), trace);
+        this.fields.insert("suppressedExceptions".to_string(), new_array("[Ljava/lang/Throwable;", 0)?);
+    }
+
+    return Ok(RuntimeValue::Void);
+}
+
+// a field of the object a native method was called on
+fn receiver_field(args: &[RuntimeValue], name: &str) -> RuntimeValue {
+    match args.first() {
+        Some(RuntimeValue::Object(this)) => this.borrow().fields.get(name).cloned().unwrap_or(RuntimeValue::Null),
+        _ => RuntimeValue::Null,
+    }
+}
+
+// getStackTrace and getSuppressed hand out copies, so changing them does not affect the throwable
+fn copy_reference_array(value: RuntimeValue, class: &str) -> Result<RuntimeValue, JVMError> {
+    match value {
+        RuntimeValue::Array(array) => {
+            let array = array.borrow();
+            return Ok(RuntimeValue::Array(rc::Rc::new(cell::RefCell::new(JVMArray{
+                class: array.class.clone(),
+                elements: match &array.elements {
+                    ArrayElements::Reference(elements) => ArrayElements::Reference(elements.clone()),
+                    _ => return Err(format!("{} is not a reference array", array.class).into()),
+                },
+            }))));
+        },
+        _ => {
+            return new_array(class, 0);
         }
-        */
-        match lookup_utf8_constant(&jvm.constant_pool, jvm.methods[i].name_index as usize) {
-            Some(method_name) => {
-                debug!("Check method index={} name='{}' vs '{}'", i, method_name, name);
-                if method_name == name {
+    }
+}
 
-                    let mut frame = create_frame(&jvm.methods[i])?;
+fn create_throwable_class(name: &str, super_class: &str) -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("<init>".to_string(), JVMMethod::Native(throwable_init));
 
-                    let mut runtime = create_runtime_const();
-                    runtime.add_class(create_jvm_class(jvm)?);
+    return JVMClass{
+        class: name.to_string(),
+        access_flags: ACC_PUBLIC,
+        major_version: 0,
+        super_class: Some(super_class.to_string()),
+        interfaces: Vec::new(),
+        source_file: None,
+        constant_pool: rc::Rc::new(Vec::new()),
+        methods: methods,
+        fields: cell::RefCell::new(HashMap::new()),
+        instance_fields: HashMap::new(),
+        object_fields: HashMap::new(),
+        field_owners: HashMap::new(),
+        bootstrap_methods: Vec::new(),
+        call_sites: cell::RefCell::new(HashMap::new()),
+        dynamic_constants: cell::RefCell::new(HashMap::new()),
+        subtype_cache: cell::RefCell::new(HashMap::new()),
+        initialization: cell::RefCell::new(Initialization::NotStarted),
+    };
+}
 
-                    return do_execute_method(&jvm.methods[i], &jvm.constant_pool, &mut frame, &runtime);
+fn create_java_lang_throwable() -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("<init>".to_string(), JVMMethod::Native(throwable_init));
+    methods.insert("getMessage".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(receiver_field(args, "detailMessage"));
+    }));
+    methods.insert("getLocalizedMessage".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(receiver_field(args, "detailMessage"));
+    }));
+    methods.insert("getCause".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(receiver_field(args, "cause"));
+    }));
+    methods.insert("initCause".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match args {
+            [RuntimeValue::Object(this), cause] => {
+                if let RuntimeValue::Object(cause) = cause {
+                    if rc::Rc::ptr_eq(this, cause) {
+                        return Err(JVMError::exception("java/lang/IllegalArgumentException", Some("Self-causation not permitted".to_string())));
+                    }
                 }
+                this.borrow_mut().fields.insert("cause".to_string(), cause.clone());
+                return Ok(RuntimeValue::Object(this.clone()));
             },
-            None => {
-                return Err("Error: method name index is invalid".to_string());
+            _ => {
+                return Err(format!("invalid arguments to initCause: {:?}", args).into());
+            }
+        }
+    }));
+    methods.insert("toString".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match args.first() {
+            Some(RuntimeValue::Object(this)) => Ok(new_string(&throwable_to_string(&this.borrow()))),
+            _ => Ok(RuntimeValue::Null),
+        }
+    }));
+    methods.insert("fillInStackTrace".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match args.first() {
+            Some(RuntimeValue::Object(this)) => {
+                let class = this.borrow().class.clone();
+                let trace = capture_stack_trace(jvm, &class);
+                this.borrow_mut().fields.insert("stackTrace".to_string(), trace);
+                return Ok(RuntimeValue::Object(this.clone()));
+            },
+            _ => {
+                return Ok(RuntimeValue::Null);
+            }
+        }
+    }));
+    methods.insert("getStackTrace".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return copy_reference_array(receiver_field(args, "stackTrace"), "[Ljava/lang/StackTraceElement;");
+    }));
+    methods.insert("getSuppressed".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return copy_reference_array(receiver_field(args, "suppressedExceptions"), "[Ljava/lang/Throwable;");
+    }));
+    methods.insert("addSuppressed".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match args {
+            [RuntimeValue::Object(this), RuntimeValue::Object(exception)] => {
+                if rc::Rc::ptr_eq(this, exception) {
+                    return Err(JVMError::exception("java/lang/IllegalArgumentException", Some("Self-suppression not permitted".to_string())));
+                }
+
+                let suppressed = copy_reference_array(receiver_field(args, "suppressedExceptions"), "[Ljava/lang/Throwable;")?;
+                if let RuntimeValue::Array(array) = &suppressed {
+                    if let ArrayElements::Reference(elements) = &mut array.borrow_mut().elements {
+                        elements.push(RuntimeValue::Object(exception.clone()));
+                    }
+                }
+                this.borrow_mut().fields.insert("suppressedExceptions".to_string(), suppressed);
+                return Ok(RuntimeValue::Void);
+            },
+            [RuntimeValue::Object(_), RuntimeValue::Null] => {
+                return Err(JVMError::exception("java/lang/NullPointerException", Some("Cannot suppress a null exception.".to_string())));
+            },
+            _ => {
+                return Err(format!("invalid arguments to addSuppressed: {:?}", args).into());
+            }
+        }
+    }));
+    methods.insert("printStackTrace".to_string(), JVMMethod::Native(|jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match args {
+            [RuntimeValue::Object(this)] => {
+                print_to_stream(&jvm.system_stream("err")?, &format_stack_trace(this))?;
+            },
+            [RuntimeValue::Object(this), stream] => {
+                print_to_stream(stream, &format_stack_trace(this))?;
+            },
+            _ => {
+                return Err(format!("invalid arguments to printStackTrace: {:?}", args).into());
             }
         }
+
+        return Ok(RuntimeValue::Void);
+    }));
+
+    let mut instance_fields = HashMap::new();
+    instance_fields.insert("detailMessage".to_string(), RuntimeValue::Null);
+    instance_fields.insert("cause".to_string(), RuntimeValue::Null);
+    instance_fields.insert("stackTrace".to_string(), RuntimeValue::Null);
+    instance_fields.insert("suppressedExceptions".to_string(), RuntimeValue::Null);
+
+    return JVMClass{
+        class: "java/lang/Throwable".to_string(),
+        access_flags: ACC_PUBLIC,
+        major_version: 0,
+        super_class: Some("java/lang/Object".to_string()),
+        interfaces: vec!["java/io/Serializable".to_string()],
+        source_file: None,
+        constant_pool: rc::Rc::new(Vec::new()),
+        methods: methods,
+        fields: cell::RefCell::new(HashMap::new()),
+        instance_fields: instance_fields,
+        object_fields: HashMap::new(),
+        field_owners: HashMap::new(),
+        bootstrap_methods: Vec::new(),
+        call_sites: cell::RefCell::new(HashMap::new()),
+        dynamic_constants: cell::RefCell::new(HashMap::new()),
+        subtype_cache: cell::RefCell::new(HashMap::new()),
+        initialization: cell::RefCell::new(Initialization::NotStarted),
+    };
+}
+
+fn stack_trace_element_field(args: &[RuntimeValue], name: &str) -> RuntimeValue {
+    match args.first() {
+        Some(RuntimeValue::Object(this)) => this.borrow().fields.get(name).cloned().unwrap_or(RuntimeValue::Null),
+        _ => RuntimeValue::Null,
+    }
+}
+
+fn create_java_lang_stack_trace_element() -> JVMClass {
+    let mut methods = HashMap::new();
+    methods.insert("getClassName".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(stack_trace_element_field(args, "declaringClass"));
+    }));
+    methods.insert("getMethodName".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(stack_trace_element_field(args, "methodName"));
+    }));
+    methods.insert("getFileName".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(stack_trace_element_field(args, "fileName"));
+    }));
+    methods.insert("getLineNumber".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        return Ok(stack_trace_element_field(args, "lineNumber"));
+    }));
+    methods.insert("isNativeMethod".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match stack_trace_element_field(args, "lineNumber") {
+            RuntimeValue::Int(-2) => Ok(RuntimeValue::Int(1)),
+            _ => Ok(RuntimeValue::Int(0)),
+        }
+    }));
+    methods.insert("toString".to_string(), JVMMethod::Native(|_jvm: &RuntimeConst, args: &[RuntimeValue]| {
+        match args.first() {
+            Some(RuntimeValue::Object(this)) => Ok(new_string(&stack_trace_element_to_string(&this.borrow()))),
+            _ => Ok(RuntimeValue::Null),
+        }
+    }));
+
+    return JVMClass{
+        class: "java/lang/StackTraceElement".to_string(),
+        access_flags: ACC_PUBLIC,
+        major_version: 0,
+        super_class: Some("java/lang/Object".to_string()),
+        interfaces: vec!["java/io/Serializable".to_string()],
+        source_file: None,
+        constant_pool: rc::Rc::new(Vec::new()),
+        methods: methods,
+        fields: cell::RefCell::new(HashMap::new()),
+        instance_fields: HashMap::new(),
+        object_fields: HashMap::new(),
+        field_owners: HashMap::new(),
+        bootstrap_methods: Vec::new(),
+        call_sites: cell::RefCell::new(HashMap::new()),
+        dynamic_constants: cell::RefCell::new(HashMap::new()),
+        subtype_cache: cell::RefCell::new(HashMap::new()),
+        initialization: cell::RefCell::new(Initialization::NotStarted),
+    };
+}
+
+fn create_runtime_const<'a>(options: &'a JVMOptions) -> RuntimeConst<'a> {
+    let runtime = RuntimeConst{
+        classes: cell::RefCell::new(HashMap::new()),
+        class_path: &options.class_path,
+        call_stack: cell::RefCell::new(Vec::new()),
+        stack_size: options.stack_size,
+        stack_used: cell::Cell::new(0),
+        nested_runs: cell::Cell::new(0),
+        class_objects: cell::RefCell::new(HashMap::new()),
+        lambda_count: cell::Cell::new(0),
+        strings: cell::RefCell::new(HashMap::new()),
+        boxes: cell::RefCell::new(HashMap::new()),
+    };
+
+    runtime.add_class(create_java_lang_object());
+    for (name, interfaces, methods) in INTERFACES {
+        runtime.add_class(create_interface(name, interfaces, methods));
     }
+    runtime.add_class(create_java_lang_system());
+    runtime.add_class(create_java_io_print_stream());
+    runtime.add_class(create_java_lang_string());
+    runtime.add_class(create_string_builder("java/lang/StringBuilder"));
+    runtime.add_class(create_string_builder("java/lang/StringBuffer"));
+    runtime.add_class(create_java_lang_throwable());
+    runtime.add_class(create_java_lang_stack_trace_element());
+    runtime.add_class(create_java_lang_class());
+    runtime.add_class(create_java_lang_invoke_method_handle());
+    for handle_class in ["java/lang/invoke/DirectMethodHandle", "java/lang/invoke/BoundMethodHandle", "java/lang/invoke/DroppingMethodHandle", "java/lang/invoke/ConvertingMethodHandle"] {
+        runtime.add_class(create_native_class(handle_class, "java/lang/invoke/MethodHandle", HashMap::new()));
+    }
+    runtime.add_class(create_java_lang_invoke_method_type());
+    runtime.add_class(create_java_lang_invoke_method_handles());
+    runtime.add_class(create_java_lang_invoke_method_handles_lookup());
+    let mut number = create_native_class("java/lang/Number", "java/lang/Object", HashMap::new());
+    number.access_flags = ACC_PUBLIC | ACC_ABSTRACT;
+    runtime.add_class(number);
+    for (name, super_class, primitive) in PRIMITIVE_WRAPPERS {
+        runtime.add_class(create_primitive_wrapper(&runtime, name, super_class, primitive));
+    }
+    runtime.add_class(create_native_class("java/lang/invoke/CallSite", "java/lang/Object", HashMap::new()));
+    runtime.add_class(create_native_class("java/lang/invoke/ConstantCallSite", "java/lang/invoke/CallSite", HashMap::new()));
+    runtime.add_class(create_java_lang_invoke_lambda_metafactory());
+    runtime.add_class(create_java_lang_invoke_string_concat_factory());
+    runtime.add_class(create_java_lang_invoke_constant_bootstraps());
+    runtime.add_class(create_java_util_objects());
+    runtime.add_class(create_java_lang_runtime_switch_bootstraps());
+    runtime.add_class(create_java_lang_runtime_object_methods());
+    runtime.add_class(create_java_lang_enum());
+    runtime.add_class(create_java_lang_record());
+    for (name, super_class) in THROWABLE_CLASSES {
+        runtime.add_class(create_throwable_class(name, super_class));
+    }
+
+    return runtime;
+}
+
+/* run the method called `name` in a class file. the classes it uses are loaded from the class path
+ * given in `options`
+ */
+pub fn execute_method(jvm: JVMClassFile, name: &str, descriptor: &str, arguments: Vec<RuntimeValue>, options: &JVMOptions) -> Result<RuntimeValue, JVMError> {
+    // find method named 'name'
+    // start executing byte code at that method
 
-    return Err("no such method found".to_string());
+    let runtime = create_runtime_const(options);
+    let class = runtime.link_class(create_jvm_class(&runtime, jvm)?)?;
+    runtime.initialize_class(&class)?;
+    match class.declared_method(name, descriptor) {
+        Some(JVMMethod::Bytecode(info)) => {
+            debug!("Found method {} in class {}", name, class.class);
+            let frame = create_frame_with_arguments(class.clone(), info.clone(), arguments)?;
+            return run_method(&runtime, frame);
+        },
+        _ => {
+            return Err("no such method found".to_string().into());
+        }
+    }
 }
 
@@ -0,0 +1,267 @@
+/* decompression of deflate streams, the compression used by zip archives and so by jar files.
+ * huffman codes are decoded a bit at a time from their canonical form, which is slow next to
+ * table driven decoders but plenty for loading classes
+ * https://www.rfc-editor.org/rfc/rfc1951
+ */
+
+use std::io;
+
+// the base length and number of extra bits for the length symbols 257 to 285
+const LENGTH_BASE: [u16; 29] = [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+
+// the base distance and number of extra bits for the distance symbols 0 to 29
+const DISTANCE_BASE: [u16; 30] = [1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577];
+const DISTANCE_EXTRA: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+
+// the order the code lengths of the code length alphabet are sent in by a dynamic block
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+const MAX_BITS: usize = 15;
+
+fn corrupt(message: &str) -> io::Error {
+    return io::Error::new(io::ErrorKind::InvalidData, format!("invalid deflate data: {}", message));
+}
+
+// reads the input least significant bit first, the way deflate packs it
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    buffer: u32,
+    count: u32,
+}
+
+impl <'a>BitReader<'a> {
+    fn bits(self: &mut BitReader<'a>, count: u32) -> Result<u32, io::Error> {
+        while self.count < count {
+            let byte = match self.data.get(self.position) {
+                Some(byte) => *byte,
+                None => return Err(corrupt("unexpected end of data")),
+            };
+            self.position += 1;
+            self.buffer |= (byte as u32) << self.count;
+            self.count += 8;
+        }
+
+        let value = self.buffer & ((1u32 << count) - 1);
+        self.buffer >>= count;
+        self.count -= count;
+        return Ok(value);
+    }
+
+    // stored blocks start on a byte boundary
+    fn align(self: &mut BitReader<'a>) {
+        self.buffer = 0;
+        self.count = 0;
+    }
+}
+
+/* a canonical huffman code, described by how many codes there are of each length and the symbols
+ * in code order
+ */
+struct Huffman {
+    counts: [u16; MAX_BITS + 1],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Huffman, io::Error> {
+        let mut counts = [0u16; MAX_BITS + 1];
+        for length in lengths {
+            counts[*length as usize] += 1;
+        }
+
+        // a code with more codes of some length than there is room for cannot be decoded
+        let mut left: i32 = 1;
+        for count in counts.iter().skip(1) {
+            left = (left << 1) - *count as i32;
+            if left < 0 {
+                return Err(corrupt("over-subscribed huffman code"));
+            }
+        }
+
+        let mut offsets = [0u16; MAX_BITS + 1];
+        for length in 1..MAX_BITS {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, length) in lengths.iter().enumerate() {
+            if *length != 0 {
+                symbols[offsets[*length as usize] as usize] = symbol as u16;
+                offsets[*length as usize] += 1;
+            }
+        }
+
+        return Ok(Huffman{
+            counts: counts,
+            symbols: symbols,
+        });
+    }
+
+    fn decode(self: &Huffman, input: &mut BitReader) -> Result<u16, io::Error> {
+        // codes of each length are consecutive, starting at `first`
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+        for length in 1..=MAX_BITS {
+            code |= input.bits(1)? as i32;
+            let count = self.counts[length] as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        return Err(corrupt("invalid huffman code"));
+    }
+}
+
+// the codes of a block compressed with the fixed huffman codes of section 3.2.6
+fn fixed_codes() -> Result<(Huffman, Huffman), io::Error> {
+    let mut lengths = [0u8; 288];
+    for (symbol, length) in lengths.iter_mut().enumerate() {
+        *length = match symbol {
+            0..=143 => 8,
+            144..=255 => 9,
+            256..=279 => 7,
+            _ => 8,
+        };
+    }
+
+    return Ok((Huffman::new(&lengths)?, Huffman::new(&[5u8; 30])?));
+}
+
+// the codes of a block that sends its own huffman codes, section 3.2.7
+fn dynamic_codes(input: &mut BitReader) -> Result<(Huffman, Huffman), io::Error> {
+    let literal_count = input.bits(5)? as usize + 257;
+    let distance_count = input.bits(5)? as usize + 1;
+    let code_length_count = input.bits(4)? as usize + 4;
+    if literal_count > 286 || distance_count > 30 {
+        return Err(corrupt("too many length or distance codes"));
+    }
+
+    let mut code_lengths = [0u8; 19];
+    for index in CODE_LENGTH_ORDER.iter().take(code_length_count) {
+        code_lengths[*index] = input.bits(3)? as u8;
+    }
+    let code_length_code = Huffman::new(&code_lengths)?;
+
+    // the literal/length and distance code lengths are sent as one sequence
+    let mut lengths = Vec::new();
+    while lengths.len() < literal_count + distance_count {
+        let symbol = code_length_code.decode(input)?;
+        let (length, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => match lengths.last() {
+                Some(previous) => (*previous, 3 + input.bits(2)?),
+                None => return Err(corrupt("repeated length with no previous length")),
+            },
+            17 => (0, 3 + input.bits(3)?),
+            _ => (0, 11 + input.bits(7)?),
+        };
+        for _i in 0..repeat {
+            lengths.push(length);
+        }
+    }
+
+    if lengths.len() > literal_count + distance_count {
+        return Err(corrupt("too many code lengths"));
+    }
+    if lengths[256] == 0 {
+        return Err(corrupt("no end of block code"));
+    }
+
+    let literal_code = Huffman::new(&lengths[..literal_count])?;
+    let distance_code = Huffman::new(&lengths[literal_count..])?;
+    return Ok((literal_code, distance_code));
+}
+
+// the literals and back references of a compressed block, up to its end of block symbol
+fn inflate_codes(input: &mut BitReader, output: &mut Vec<u8>, literal_code: &Huffman, distance_code: &Huffman) -> Result<(), io::Error> {
+    loop {
+        let symbol = literal_code.decode(input)? as usize;
+        if symbol < 256 {
+            output.push(symbol as u8);
+        } else if symbol == 256 {
+            return Ok(());
+        } else {
+            let symbol = symbol - 257;
+            if symbol >= LENGTH_BASE.len() {
+                return Err(corrupt("invalid length symbol"));
+            }
+            let length = LENGTH_BASE[symbol] as usize + input.bits(LENGTH_EXTRA[symbol] as u32)? as usize;
+
+            let symbol = distance_code.decode(input)? as usize;
+            if symbol >= DISTANCE_BASE.len() {
+                return Err(corrupt("invalid distance symbol"));
+            }
+            let distance = DISTANCE_BASE[symbol] as usize + input.bits(DISTANCE_EXTRA[symbol] as u32)? as usize;
+            if distance > output.len() {
+                return Err(corrupt("distance too far back"));
+            }
+
+            // the copy may overlap what it is copying, so it goes a byte at a time
+            let start = output.len() - distance;
+            for i in 0..length {
+                let byte = output[start + i];
+                output.push(byte);
+            }
+        }
+    }
+}
+
+/* decompress raw deflate data, as stored in a zip entry. `size` is the expected size of the
+ * output, used to size the buffer
+ */
+pub fn inflate(data: &[u8], size: usize) -> Result<Vec<u8>, io::Error> {
+    let mut input = BitReader{
+        data: data,
+        position: 0,
+        buffer: 0,
+        count: 0,
+    };
+    let mut output = Vec::with_capacity(size);
+
+    loop {
+        let last = input.bits(1)? == 1;
+        match input.bits(2)? {
+            0 => {
+                input.align();
+                let header = match data.get(input.position..input.position + 4) {
+                    Some(header) => header,
+                    None => return Err(corrupt("unexpected end of data")),
+                };
+                let length = u16::from_le_bytes([header[0], header[1]]);
+                let complement = u16::from_le_bytes([header[2], header[3]]);
+                if length != !complement {
+                    return Err(corrupt("stored block length does not match its complement"));
+                }
+
+                let start = input.position + 4;
+                match data.get(start..start + length as usize) {
+                    Some(bytes) => output.extend_from_slice(bytes),
+                    None => return Err(corrupt("unexpected end of data")),
+                }
+                input.position = start + length as usize;
+            },
+            1 => {
+                let (literal_code, distance_code) = fixed_codes()?;
+                inflate_codes(&mut input, &mut output, &literal_code, &distance_code)?;
+            },
+            2 => {
+                let (literal_code, distance_code) = dynamic_codes(&mut input)?;
+                inflate_codes(&mut input, &mut output, &literal_code, &distance_code)?;
+            },
+            _ => {
+                return Err(corrupt("invalid block type"));
+            }
+        }
+
+        if last {
+            return Ok(output);
+        }
+    }
+}
@@ -0,0 +1,639 @@
+/* helpful NullPointerException messages (JEP 358). the message says which action failed and,
+ * when the bytecode shows where the null came from, what was null:
+ *   Cannot invoke "Main.get(int)" because "<local1>" is null
+ * the source of the null reference is found by simulating the operand stack of the method up to
+ * the faulting instruction, remembering which instruction pushed each word.
+ * https://openjdk.org/jeps/358
+ */
+
+use super::data::*;
+use super::exec::opcodes;
+
+// how deeply an expression is described before giving up on it, the same limit hotspot uses
+const MAX_DETAIL: usize = 5;
+
+// the pc of the instruction that pushed each word of the operand stack, None where it is not
+// known because the paths leading to an instruction disagree
+type SimulatedStack = Vec<Option<usize>>;
+
+struct Analysis<'a> {
+    code: &'a [u8],
+    constant_pool: &'a ConstantPool,
+    is_static: bool,
+    descriptor: MethodDescriptor,
+    local_variables: Vec<&'a LocalVariableTableEntry>,
+    // the operand stack before each instruction, None for instructions never reached
+    stacks: Vec<Option<SimulatedStack>>,
+    // instructions whose stack changed since they were last simulated
+    pending: Vec<bool>,
+    // local variable slots that are stored to somewhere in the method. a slot that never is
+    // still holds the parameter passed in
+    written: Vec<bool>,
+}
+
+/* the message for a NullPointerException thrown by the instruction at `pc`, or None if the
+ * instruction is not one that throws on null
+ */
+pub fn null_pointer_message(method: &MethodInfo, constant_pool: &ConstantPool, pc: usize) -> Option<String> {
+    let (code, exception_table, attributes) = match method.attributes.iter().find(|attribute| matches!(attribute, AttributeKind::Code{..})) {
+        Some(AttributeKind::Code{code, exception_table, attributes, ..}) => (code, exception_table, attributes),
+        _ => return None,
+    };
+
+    let mut local_variables = Vec::new();
+    for attribute in attributes {
+        if let AttributeKind::LocalVariableTable{local_variable_table} = attribute {
+            local_variables.extend(local_variable_table.iter());
+        }
+    }
+
+    let descriptor = parse_method_descriptor(lookup_utf8_constant(constant_pool, method.descriptor_index as usize)?).ok()?;
+    let mut analysis = Analysis{
+        code: code,
+        constant_pool: constant_pool,
+        is_static: method.access_flags & ACC_STATIC != 0,
+        descriptor: descriptor,
+        local_variables: local_variables,
+        stacks: vec![None; code.len()],
+        pending: vec![false; code.len()],
+        written: Vec::new(),
+    };
+
+    let (action, slot) = analysis.failed_action(pc)?;
+    if analysis.simulate(exception_table, pc).is_none() {
+        return Some(action);
+    }
+
+    match analysis.source(pc, slot) {
+        Some(source) if is_invoke(code[source]) => {
+            return Some(format!("{} because the return value of \"{}\" is null", action, analysis.method_name(source)?));
+        },
+        Some(source) => {
+            match analysis.describe(source, MAX_DETAIL) {
+                Some(expression) => Some(format!("{} because \"{}\" is null", action, expression)),
+                None => Some(action),
+            }
+        },
+        None => Some(action),
+    }
+}
+
+fn is_invoke(opcode: u8) -> bool {
+    return opcode == opcodes::INVOKEVIRTUAL || opcode == opcodes::INVOKESPECIAL || opcode == opcodes::INVOKESTATIC || opcode == opcodes::INVOKEINTERFACE;
+}
+
+fn descriptor_size(descriptor: &Descriptor) -> usize {
+    match descriptor {
+        Descriptor::Long | Descriptor::Double => 2,
+        Descriptor::Void => 0,
+        _ => 1,
+    }
+}
+
+// the words taken by the arguments of a method descriptor and the words its result takes
+fn method_sizes(descriptor: &str) -> Option<(usize, usize)> {
+    let descriptor = parse_method_descriptor(descriptor).ok()?;
+    let arguments = descriptor.parameters.iter().map(descriptor_size).sum();
+    return Some((arguments, descriptor_size(&descriptor.return_type)));
+}
+
+// java.lang.Object and java.lang.String are common enough that hotspot leaves out their package
+fn class_name(class_name: &str) -> String {
+    match class_name {
+        "java/lang/Object" => "Object".to_string(),
+        "java/lang/String" => "String".to_string(),
+        _ => class_name.replace('/', "."),
+    }
+}
+
+fn type_name(descriptor: &Descriptor) -> String {
+    match descriptor {
+        Descriptor::Byte => "byte".to_string(),
+        Descriptor::Char => "char".to_string(),
+        Descriptor::Double => "double".to_string(),
+        Descriptor::Float => "float".to_string(),
+        Descriptor::Int => "int".to_string(),
+        Descriptor::Long => "long".to_string(),
+        Descriptor::Short => "short".to_string(),
+        Descriptor::Boolean => "boolean".to_string(),
+        Descriptor::Void => "void".to_string(),
+        Descriptor::Object(name) => class_name(name),
+        Descriptor::Array(component) => format!("{}[]", type_name(component)),
+    }
+}
+
+fn read_u16(code: &[u8], pc: usize) -> usize {
+    return ((code[pc] as usize) << 8) | code[pc + 1] as usize;
+}
+
+fn read_i32(code: &[u8], pc: usize) -> i32 {
+    return i32::from_be_bytes([code[pc], code[pc + 1], code[pc + 2], code[pc + 3]]);
+}
+
+fn jump(pc: usize, offset: i32) -> usize {
+    return (pc as i64 + offset as i64) as usize;
+}
+
+// the number of bytes taken by the instruction at `pc`, including its operands
+fn instruction_length(code: &[u8], pc: usize) -> Option<usize> {
+    let length = match code[pc] {
+        opcodes::PUSHBYTE | opcodes::PUSHRUNTIMECONSTANT | opcodes::NEWARRAY => 2,
+        opcodes::ILOAD..=opcodes::ALOAD | opcodes::ISTORE..=opcodes::ASTORE | opcodes::RET => 2,
+        opcodes::PUSHSHORT | opcodes::PUSHRUNTIMECONSTANTWIDE | opcodes::PUSHRUNTIMECONSTANT2WIDE | opcodes::IINC => 3,
+        opcodes::IFEQUAL..=opcodes::JSR | opcodes::IFNULL | opcodes::IFNONNULL => 3,
+        opcodes::GETSTATIC..=opcodes::INVOKESTATIC | opcodes::NEW | opcodes::ANEWARRAY | opcodes::CHECKCAST | opcodes::INSTANCEOF => 3,
+        opcodes::MULTIANEWARRAY => 4,
+        opcodes::INVOKEINTERFACE | opcodes::INVOKEDYNAMIC | opcodes::GOTOW | opcodes::JSRW => 5,
+        opcodes::WIDE => if code.get(pc + 1)? == &opcodes::IINC { 6 } else { 4 },
+        opcodes::TABLESWITCH => {
+            let operands = (pc + 4) & !3;
+            let low = read_i32(code, operands + 4) as i64;
+            let high = read_i32(code, operands + 8) as i64;
+            operands + 12 + (high - low + 1) as usize * 4 - pc
+        },
+        opcodes::LOOKUPSWITCH => {
+            let operands = (pc + 4) & !3;
+            operands + 8 + read_i32(code, operands + 4) as usize * 8 - pc
+        },
+        0xca..=0xff => return None,
+        _ => 1,
+    };
+    return Some(length);
+}
+
+// the common case: pop some words, push some words and carry on with the next instruction
+fn simple(stack: &mut SimulatedStack, pc: usize, pop: usize, push: usize, length: usize) -> Option<Vec<usize>> {
+    if stack.len() < pop {
+        return None;
+    }
+    stack.truncate(stack.len() - pop);
+    for _i in 0..push {
+        stack.push(Some(pc));
+    }
+    return Some(vec![pc + length]);
+}
+
+impl <'a>Analysis<'a> {
+    // the class, name and descriptor of the field or method an instruction refers to
+    fn member(self: &Analysis<'a>, pc: usize) -> Option<(&'a str, &'a str, &'a str)> {
+        let (class_index, name_and_type_index) = match constant_pool_lookup(self.constant_pool, read_u16(self.code, pc + 1))? {
+            ConstantPoolEntry::Fieldref{class_index, name_and_type_index} => (*class_index, *name_and_type_index),
+            ConstantPoolEntry::Methodref(class_index, name_and_type_index) => (*class_index, *name_and_type_index),
+            ConstantPoolEntry::InterfaceMethodref(class_index, name_and_type_index) => (*class_index, *name_and_type_index),
+            _ => return None,
+        };
+
+        let class = match constant_pool_lookup(self.constant_pool, class_index as usize)? {
+            ConstantPoolEntry::Classref(name_index) => lookup_utf8_constant(self.constant_pool, *name_index as usize)?,
+            _ => return None,
+        };
+
+        match constant_pool_lookup(self.constant_pool, name_and_type_index as usize)? {
+            ConstantPoolEntry::NameAndType{name_index, descriptor_index} => {
+                let name = lookup_utf8_constant(self.constant_pool, *name_index as usize)?;
+                let descriptor = lookup_utf8_constant(self.constant_pool, *descriptor_index as usize)?;
+                return Some((class, name, descriptor));
+            },
+            _ => {
+                return None;
+            }
+        }
+    }
+
+    fn field_size(self: &Analysis<'a>, pc: usize) -> Option<usize> {
+        let (_, _, descriptor) = self.member(pc)?;
+        return Some(descriptor_size(&parse_type_descriptor(descriptor).ok()?));
+    }
+
+    // the words taken by the arguments of an invoke and the words its result takes
+    fn invoke_sizes(self: &Analysis<'a>, pc: usize) -> Option<(usize, usize)> {
+        let (_, _, descriptor) = self.member(pc)?;
+        return method_sizes(descriptor);
+    }
+
+    // the same for invokedynamic, whose call site has a name and descriptor but no class
+    fn dynamic_invoke_sizes(self: &Analysis<'a>, pc: usize) -> Option<(usize, usize)> {
+        let name_and_type_index = match constant_pool_lookup(self.constant_pool, read_u16(self.code, pc + 1))? {
+            ConstantPoolEntry::InvokeDynamic{name_and_type_index, ..} => *name_and_type_index,
+            _ => return None,
+        };
+
+        match constant_pool_lookup(self.constant_pool, name_and_type_index as usize)? {
+            ConstantPoolEntry::NameAndType{descriptor_index, ..} => {
+                return method_sizes(lookup_utf8_constant(self.constant_pool, *descriptor_index as usize)?);
+            },
+            _ => {
+                return None;
+            }
+        }
+    }
+
+    // a method the way the message shows it, such as Main.get(int)
+    fn method_name(self: &Analysis<'a>, pc: usize) -> Option<String> {
+        let (class, name, descriptor) = self.member(pc)?;
+        let descriptor = parse_method_descriptor(descriptor).ok()?;
+        let parameters: Vec<String> = descriptor.parameters.iter().map(type_name).collect();
+        return Some(format!("{}.{}({})", class_name(class), name, parameters.join(", ")));
+    }
+
+    /* what the instruction at `pc` was trying to do with the null reference, and which word of
+     * the operand stack, counting from the top, holds that reference
+     */
+    fn failed_action(self: &Analysis<'a>, pc: usize) -> Option<(String, usize)> {
+        let array_load = |kind: &str| Some((format!("Cannot load from {} array", kind), 1));
+        let array_store = |kind: &str, value_size: usize| Some((format!("Cannot store to {} array", kind), value_size + 1));
+        match self.code[pc] {
+            opcodes::IALOAD => array_load("int"),
+            opcodes::LALOAD => array_load("long"),
+            opcodes::FALOAD => array_load("float"),
+            opcodes::DALOAD => array_load("double"),
+            opcodes::AALOAD => array_load("object"),
+            opcodes::BALOAD => array_load("byte/boolean"),
+            opcodes::CALOAD => array_load("char"),
+            opcodes::SALOAD => array_load("short"),
+            opcodes::IASTORE => array_store("int", 1),
+            opcodes::LASTORE => array_store("long", 2),
+            opcodes::FASTORE => array_store("float", 1),
+            opcodes::DASTORE => array_store("double", 2),
+            opcodes::AASTORE => array_store("object", 1),
+            opcodes::BASTORE => array_store("byte/boolean", 1),
+            opcodes::CASTORE => array_store("char", 1),
+            opcodes::SASTORE => array_store("short", 1),
+            opcodes::ARRAYLENGTH => Some(("Cannot read the array length".to_string(), 0)),
+            opcodes::ATHROW => Some(("Cannot throw exception".to_string(), 0)),
+            opcodes::MONITORENTER => Some(("Cannot enter synchronized block".to_string(), 0)),
+            opcodes::MONITOREXIT => Some(("Cannot exit synchronized block".to_string(), 0)),
+            opcodes::GETFIELD => {
+                let (_, name, _) = self.member(pc)?;
+                Some((format!("Cannot read field \"{}\"", name), 0))
+            },
+            opcodes::PUTFIELD => {
+                let (_, name, _) = self.member(pc)?;
+                Some((format!("Cannot assign field \"{}\"", name), self.field_size(pc)?))
+            },
+            opcodes::INVOKEVIRTUAL | opcodes::INVOKESPECIAL | opcodes::INVOKEINTERFACE => {
+                let (arguments, _) = self.invoke_sizes(pc)?;
+                Some((format!("Cannot invoke \"{}\"", self.method_name(pc)?), arguments))
+            },
+            _ => None,
+        }
+    }
+
+    // the instruction that pushed the word `slot` words below the top of the stack at `pc`
+    fn source(self: &Analysis<'a>, pc: usize, slot: usize) -> Option<usize> {
+        let stack = self.stacks[pc].as_ref()?;
+        if slot >= stack.len() {
+            return None;
+        }
+        return stack[stack.len() - 1 - slot];
+    }
+
+    /* the expression computed by the instruction at `pc`, such as <local1>.next or ms[2].
+     * `detail` limits how deeply nested expressions are followed
+     */
+    fn describe(self: &Analysis<'a>, pc: usize, detail: usize) -> Option<String> {
+        if detail == 0 {
+            return None;
+        }
+
+        let code = self.code;
+        match code[pc] {
+            opcodes::ACONSTNULL => Some("null".to_string()),
+            opcodes::ICONSTM1..=opcodes::ICONST5 => Some((code[pc] as i32 - opcodes::ICONST0 as i32).to_string()),
+            opcodes::PUSHBYTE => Some((code[pc + 1] as i8).to_string()),
+            opcodes::PUSHSHORT => Some((read_u16(code, pc + 1) as i16).to_string()),
+            opcodes::ILOAD | opcodes::ALOAD => Some(self.local_name(pc, code[pc + 1] as usize)),
+            opcodes::ILOAD0..=opcodes::ILOAD3 => Some(self.local_name(pc, (code[pc] - opcodes::ILOAD0) as usize)),
+            opcodes::ALOAD0..=opcodes::ALOAD3 => Some(self.local_name(pc, (code[pc] - opcodes::ALOAD0) as usize)),
+            opcodes::WIDE if code[pc + 1] == opcodes::ILOAD || code[pc + 1] == opcodes::ALOAD => {
+                Some(self.local_name(pc, read_u16(code, pc + 2)))
+            },
+            opcodes::IALOAD..=opcodes::SALOAD => {
+                let array = self.source(pc, 1).and_then(|source| self.describe(source, detail - 1)).unwrap_or("<array>".to_string());
+                let index = self.source(pc, 0).and_then(|source| self.describe(source, detail - 1)).unwrap_or("...".to_string());
+                Some(format!("{}[{}]", array, index))
+            },
+            opcodes::GETSTATIC => {
+                let (class, name, _) = self.member(pc)?;
+                Some(format!("{}.{}", class_name(class), name))
+            },
+            opcodes::GETFIELD => {
+                let (_, name, _) = self.member(pc)?;
+                match self.source(pc, 0).and_then(|source| self.describe(source, detail - 1)) {
+                    Some(object) => Some(format!("{}.{}", object, name)),
+                    None => Some(name.to_string()),
+                }
+            },
+            opcode if is_invoke(opcode) => self.method_name(pc),
+            _ => None,
+        }
+    }
+
+    /* the name of a local variable from the LocalVariableTable, or failing that this, <parameterN>
+     * for parameters that are never overwritten, or <localN>
+     */
+    fn local_name(self: &Analysis<'a>, pc: usize, slot: usize) -> String {
+        for entry in self.local_variables.iter() {
+            let start = entry.start_pc as usize;
+            if entry.index as usize == slot && pc >= start && pc < start + entry.length as usize {
+                if let Some(name) = lookup_utf8_constant(self.constant_pool, entry.name_index as usize) {
+                    return name.to_string();
+                }
+            }
+        }
+
+        let is_parameter = !self.written.get(slot).cloned().unwrap_or(false);
+        if !self.is_static && slot == 0 && is_parameter {
+            return "this".to_string();
+        }
+
+        let mut current = if self.is_static { 0 } else { 1 };
+        for (index, parameter) in self.descriptor.parameters.iter().enumerate() {
+            let size = descriptor_size(parameter);
+            if slot >= current && slot < current + size {
+                if is_parameter {
+                    return format!("<parameter{}>", index + 1);
+                }
+                break;
+            }
+            current += size;
+        }
+
+        return format!("<local{}>", slot);
+    }
+
+    fn merge(self: &mut Analysis<'a>, pc: usize, stack: &SimulatedStack) {
+        if pc >= self.code.len() {
+            return;
+        }
+
+        match &mut self.stacks[pc] {
+            Some(existing) => {
+                if existing.len() != stack.len() {
+                    return;
+                }
+                let mut changed = false;
+                for (word, other) in existing.iter_mut().zip(stack.iter()) {
+                    if word.is_some() && word != other {
+                        *word = None;
+                        changed = true;
+                    }
+                }
+                if changed {
+                    self.pending[pc] = true;
+                }
+            },
+            None => {
+                self.stacks[pc] = Some(stack.clone());
+                self.pending[pc] = true;
+            }
+        }
+    }
+
+    fn write_local(self: &mut Analysis<'a>, slot: usize, size: usize) {
+        if self.written.len() < slot + size {
+            self.written.resize(slot + size, false);
+        }
+        for i in slot..slot + size {
+            self.written[i] = true;
+        }
+    }
+
+    /* pass over the method in code order until the operand stacks stop changing, recording the
+     * stack before each instruction. like hotspot, stop as soon as the instruction just before
+     * `target` has been handled and `target` has a stack, so stores further down the method are
+     * not counted. returns None if the code uses an instruction the analysis does not understand
+     */
+    fn simulate(self: &mut Analysis<'a>, exception_table: &[ExceptionTableEntry], target: usize) -> Option<()> {
+        self.merge(0, &Vec::new());
+        for entry in exception_table {
+            // a handler starts with the exception, which was not pushed by any instruction
+            self.merge(entry.handler_pc as usize, &vec![None]);
+        }
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            let mut pc = 0;
+            while pc < self.code.len() {
+                let length = instruction_length(self.code, pc)?;
+                if self.pending[pc] {
+                    self.pending[pc] = false;
+                    changed = true;
+                    let mut stack = self.stacks[pc].clone()?;
+                    for successor in self.step(pc, &mut stack)? {
+                        self.merge(successor, &stack);
+                    }
+                }
+                pc += length;
+                if pc == target && self.stacks[target].is_some() {
+                    return Some(());
+                }
+            }
+        }
+
+        return Some(());
+    }
+
+    /* apply the instruction at `pc` to the simulated stack and return the instructions that can
+     * run next with that stack
+     */
+    fn step(self: &mut Analysis<'a>, pc: usize, stack: &mut SimulatedStack) -> Option<Vec<usize>> {
+        let code = self.code;
+        let opcode = code[pc];
+
+        match opcode {
+            0x00 => simple(stack, pc, 0, 0, 1),
+            opcodes::ACONSTNULL..=opcodes::ICONST5 | opcodes::FCONST0..=opcodes::FCONST2 => simple(stack, pc, 0, 1, 1),
+            opcodes::LCONST0 | opcodes::LCONST1 | opcodes::DCONST0 | opcodes::DCONST1 => simple(stack, pc, 0, 2, 1),
+            opcodes::PUSHBYTE => simple(stack, pc, 0, 1, 2),
+            opcodes::PUSHSHORT => simple(stack, pc, 0, 1, 3),
+            opcodes::PUSHRUNTIMECONSTANT | opcodes::PUSHRUNTIMECONSTANTWIDE => {
+                let (index, length) = if opcode == opcodes::PUSHRUNTIMECONSTANT { (code[pc + 1] as usize, 2) } else { (read_u16(code, pc + 1), 3) };
+                match constant_pool_lookup(self.constant_pool, index)? {
+                    ConstantPoolEntry::Long(_) | ConstantPoolEntry::Double(_) => simple(stack, pc, 0, 2, length),
+                    _ => simple(stack, pc, 0, 1, length),
+                }
+            },
+            opcodes::PUSHRUNTIMECONSTANT2WIDE => simple(stack, pc, 0, 2, 3),
+            opcodes::ILOAD | opcodes::FLOAD | opcodes::ALOAD => simple(stack, pc, 0, 1, 2),
+            opcodes::LLOAD | opcodes::DLOAD => simple(stack, pc, 0, 2, 2),
+            opcodes::ILOAD0..=opcodes::ILOAD3 | opcodes::FLOAD0..=opcodes::FLOAD3 | opcodes::ALOAD0..=opcodes::ALOAD3 => simple(stack, pc, 0, 1, 1),
+            opcodes::LLOAD0..=opcodes::LLOAD3 | opcodes::DLOAD0..=opcodes::DLOAD3 => simple(stack, pc, 0, 2, 1),
+            opcodes::LALOAD | opcodes::DALOAD => simple(stack, pc, 2, 2, 1),
+            opcodes::IALOAD..=opcodes::SALOAD => simple(stack, pc, 2, 1, 1),
+            opcodes::ISTORE | opcodes::FSTORE | opcodes::ASTORE => {
+                self.write_local(code[pc + 1] as usize, 1);
+                simple(stack, pc, 1, 0, 2)
+            },
+            opcodes::LSTORE | opcodes::DSTORE => {
+                self.write_local(code[pc + 1] as usize, 2);
+                simple(stack, pc, 2, 0, 2)
+            },
+            opcodes::ISTORE0..=opcodes::ISTORE3 | opcodes::FSTORE0..=opcodes::FSTORE3 | opcodes::ASTORE0..=opcodes::ASTORE3 => {
+                self.write_local(((opcode - opcodes::ISTORE0) % 4) as usize, 1);
+                simple(stack, pc, 1, 0, 1)
+            },
+            opcodes::LSTORE0..=opcodes::LSTORE3 | opcodes::DSTORE0..=opcodes::DSTORE3 => {
+                self.write_local(((opcode - opcodes::ISTORE0) % 4) as usize, 2);
+                simple(stack, pc, 2, 0, 1)
+            },
+            opcodes::LASTORE | opcodes::DASTORE => simple(stack, pc, 4, 0, 1),
+            opcodes::IASTORE..=opcodes::SASTORE => simple(stack, pc, 3, 0, 1),
+            opcodes::POP => simple(stack, pc, 1, 0, 1),
+            opcodes::POP2 => simple(stack, pc, 2, 0, 1),
+            opcodes::DUP..=opcodes::DUP2X2 => {
+                // the number of words copied and the number of words the copy goes below
+                let (words, skip) = match opcode {
+                    opcodes::DUP => (1, 0),
+                    opcodes::DUPX1 => (1, 1),
+                    opcodes::DUPX2 => (1, 2),
+                    opcodes::DUP2 => (2, 0),
+                    opcodes::DUP2X1 => (2, 1),
+                    _ => (2, 2),
+                };
+                if stack.len() < words + skip {
+                    return None;
+                }
+                let top: Vec<Option<usize>> = stack[stack.len() - words..].to_vec();
+                let at = stack.len() - words - skip;
+                for (i, word) in top.into_iter().enumerate() {
+                    stack.insert(at + i, word);
+                }
+                Some(vec![pc + 1])
+            },
+            opcodes::SWAP => {
+                let length = stack.len();
+                if length < 2 {
+                    return None;
+                }
+                stack.swap(length - 1, length - 2);
+                Some(vec![pc + 1])
+            },
+            // binary operations on ints and floats, then on longs and doubles
+            opcodes::IADD | opcodes::FADD | opcodes::ISUB | opcodes::FSUB | opcodes::IMUL | opcodes::FMUL |
+            opcodes::IDIV | opcodes::FDIV | opcodes::IREM | opcodes::FREM | opcodes::ISHL | opcodes::ISHR |
+            opcodes::IUSHR | opcodes::IAND | opcodes::IOR | opcodes::IXOR => simple(stack, pc, 2, 1, 1),
+            opcodes::LADD | opcodes::DADD | opcodes::LSUB | opcodes::DSUB | opcodes::LMUL | opcodes::DMUL |
+            opcodes::LDIV | opcodes::DDIV | opcodes::LREM | opcodes::DREM | opcodes::LAND | opcodes::LOR |
+            opcodes::LXOR => simple(stack, pc, 4, 2, 1),
+            opcodes::LSHL | opcodes::LSHR | opcodes::LUSHR => simple(stack, pc, 3, 2, 1),
+            opcodes::INEG | opcodes::FNEG => simple(stack, pc, 1, 1, 1),
+            opcodes::LNEG | opcodes::DNEG => simple(stack, pc, 2, 2, 1),
+            opcodes::IINC => {
+                self.write_local(code[pc + 1] as usize, 1);
+                simple(stack, pc, 0, 0, 3)
+            },
+            opcodes::I2L | opcodes::I2D | opcodes::F2L | opcodes::F2D => simple(stack, pc, 1, 2, 1),
+            opcodes::I2F | opcodes::F2I | opcodes::I2B | opcodes::I2C | opcodes::I2S => simple(stack, pc, 1, 1, 1),
+            opcodes::L2I | opcodes::L2F | opcodes::D2I | opcodes::D2F => simple(stack, pc, 2, 1, 1),
+            opcodes::L2D | opcodes::D2L => simple(stack, pc, 2, 2, 1),
+            opcodes::LCMP | opcodes::DCMPL | opcodes::DCMPG => simple(stack, pc, 4, 1, 1),
+            opcodes::FCMPL | opcodes::FCMPG => simple(stack, pc, 2, 1, 1),
+            opcodes::IFEQUAL..=opcodes::IFLESSEQUAL | opcodes::IFNULL | opcodes::IFNONNULL => {
+                let mut successors = simple(stack, pc, 1, 0, 3)?;
+                successors.push(jump(pc, read_u16(code, pc + 1) as i16 as i32));
+                Some(successors)
+            },
+            opcodes::IFICOMPAREEQUAL..=opcodes::IFACOMPARENOTEQUAL => {
+                let mut successors = simple(stack, pc, 2, 0, 3)?;
+                successors.push(jump(pc, read_u16(code, pc + 1) as i16 as i32));
+                Some(successors)
+            },
+            opcodes::GOTO => Some(vec![jump(pc, read_u16(code, pc + 1) as i16 as i32)]),
+            opcodes::GOTOW => Some(vec![jump(pc, read_i32(code, pc + 1))]),
+            opcodes::JSR | opcodes::JSRW => {
+                // the subroutine is entered with the return address pushed and is assumed to return
+                // to the next instruction with the stack as it was
+                let (offset, length) = if opcode == opcodes::JSR { (read_u16(code, pc + 1) as i16 as i32, 3) } else { (read_i32(code, pc + 1), 5) };
+                let mut subroutine_stack = stack.clone();
+                subroutine_stack.push(Some(pc));
+                self.merge(jump(pc, offset), &subroutine_stack);
+                Some(vec![pc + length])
+            },
+            opcodes::RET => Some(Vec::new()),
+            opcodes::TABLESWITCH | opcodes::LOOKUPSWITCH => {
+                stack.pop()?;
+                // the operands start at the next multiple of four
+                let operands = (pc + 4) & !3;
+                let mut successors = vec![jump(pc, read_i32(code, operands))];
+                if opcode == opcodes::TABLESWITCH {
+                    let low = read_i32(code, operands + 4);
+                    let high = read_i32(code, operands + 8);
+                    for i in 0..(high as i64 - low as i64 + 1) as usize {
+                        successors.push(jump(pc, read_i32(code, operands + 12 + i * 4)));
+                    }
+                } else {
+                    let pairs = read_i32(code, operands + 4) as usize;
+                    for i in 0..pairs {
+                        successors.push(jump(pc, read_i32(code, operands + 12 + i * 8)));
+                    }
+                }
+                Some(successors)
+            },
+            opcodes::IRETURN..=opcodes::RETURN | opcodes::ATHROW => Some(Vec::new()),
+            opcodes::GETSTATIC => {
+                let size = self.field_size(pc)?;
+                simple(stack, pc, 0, size, 3)
+            },
+            opcodes::PUTSTATIC => {
+                let size = self.field_size(pc)?;
+                simple(stack, pc, size, 0, 3)
+            },
+            opcodes::GETFIELD => {
+                let size = self.field_size(pc)?;
+                simple(stack, pc, 1, size, 3)
+            },
+            opcodes::PUTFIELD => {
+                let size = self.field_size(pc)?;
+                simple(stack, pc, size + 1, 0, 3)
+            },
+            opcodes::INVOKEVIRTUAL | opcodes::INVOKESPECIAL => {
+                let (arguments, result) = self.invoke_sizes(pc)?;
+                simple(stack, pc, arguments + 1, result, 3)
+            },
+            opcodes::INVOKESTATIC => {
+                let (arguments, result) = self.invoke_sizes(pc)?;
+                simple(stack, pc, arguments, result, 3)
+            },
+            opcodes::INVOKEINTERFACE => {
+                let (arguments, result) = self.invoke_sizes(pc)?;
+                simple(stack, pc, arguments + 1, result, 5)
+            },
+            opcodes::INVOKEDYNAMIC => {
+                let (arguments, result) = self.dynamic_invoke_sizes(pc)?;
+                simple(stack, pc, arguments, result, 5)
+            },
+            opcodes::NEW => simple(stack, pc, 0, 1, 3),
+            opcodes::NEWARRAY => simple(stack, pc, 1, 1, 2),
+            opcodes::ANEWARRAY | opcodes::INSTANCEOF => simple(stack, pc, 1, 1, 3),
+            opcodes::ARRAYLENGTH => simple(stack, pc, 1, 1, 1),
+            // a cast leaves the reference, and where it came from, alone
+            opcodes::CHECKCAST => simple(stack, pc, 0, 0, 3),
+            opcodes::MONITORENTER | opcodes::MONITOREXIT => simple(stack, pc, 1, 0, 1),
+            opcodes::MULTIANEWARRAY => simple(stack, pc, code[pc + 3] as usize, 1, 4),
+            opcodes::WIDE => {
+                let index = read_u16(code, pc + 2);
+                match code[pc + 1] {
+                    opcodes::ILOAD | opcodes::FLOAD | opcodes::ALOAD => simple(stack, pc, 0, 1, 4),
+                    opcodes::LLOAD | opcodes::DLOAD => simple(stack, pc, 0, 2, 4),
+                    opcodes::ISTORE | opcodes::FSTORE | opcodes::ASTORE => {
+                        self.write_local(index, 1);
+                        simple(stack, pc, 1, 0, 4)
+                    },
+                    opcodes::LSTORE | opcodes::DSTORE => {
+                        self.write_local(index, 2);
+                        simple(stack, pc, 2, 0, 4)
+                    },
+                    opcodes::IINC => {
+                        self.write_local(index, 1);
+                        simple(stack, pc, 0, 0, 6)
+                    },
+                    opcodes::RET => Some(Vec::new()),
+                    _ => None,
+                }
+            },
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use super::exec::JVMError;
+
+/* how the chars of a string are stored, which is the coder of hotspot's compact strings. a string
+ * whose chars all fit in a byte is stored as Latin-1, one byte per char, and any other string as
+ * UTF-16. a string is only ever UTF-16 if it has to be, so equal strings are stored the same way
+ * https://openjdk.org/jeps/254
+ */
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Value {
+    Latin1(Vec<u8>),
+    Utf16(Vec<u16>),
+}
+
+/* the contents of a java/lang/String. java strings are sequences of UTF-16 code units, so
+ * lengths and indexes count chars, and a supplementary character takes up two of them
+ * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/String.html
+ */
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct JVMString {
+    value: Value,
+}
+
+// Latin-1 bytes are the first 256 chars of UTF-16
+fn latin1(chars: &[u16]) -> Option<Vec<u8>> {
+    return chars.iter().map(|c| u8::try_from(*c).ok()).collect();
+}
+
+// the index of the first occurrence of needle in haystack at or after from
+fn find<T: PartialEq>(haystack: &[T], needle: &[T], from: usize) -> Option<usize> {
+    if needle.len() > haystack.len() || from > haystack.len() - needle.len() {
+        return None;
+    }
+    return (from..=haystack.len() - needle.len()).find(|start| haystack[*start..].starts_with(needle));
+}
+
+// the index of the last occurrence of needle in haystack at or before from
+fn rfind<T: PartialEq>(haystack: &[T], needle: &[T], from: usize) -> Option<usize> {
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    let last = from.min(haystack.len() - needle.len());
+    return (0..=last).rev().find(|start| haystack[*start..].starts_with(needle));
+}
+
+fn index_or_minus_one(index: Option<usize>) -> i32 {
+    match index {
+        Some(index) => index as i32,
+        None => -1,
+    }
+}
+
+impl JVMString {
+    pub fn new(chars: Vec<u16>) -> JVMString {
+        let value = match latin1(&chars) {
+            Some(bytes) => Value::Latin1(bytes),
+            None => Value::Utf16(chars),
+        };
+        return JVMString{
+            value: value,
+        };
+    }
+
+    // the chars of the string, which a Latin-1 string has to widen to UTF-16 first
+    pub fn chars(self: &JVMString) -> Cow<'_, [u16]> {
+        match &self.value {
+            Value::Latin1(bytes) => Cow::Owned(bytes.iter().map(|b| *b as u16).collect()),
+            Value::Utf16(chars) => Cow::Borrowed(chars),
+        }
+    }
+
+    /* give a string made by new its contents. strings are immutable to java code, only the
+     * constructors of String set them
+     */
+    pub fn set(self: &mut JVMString, chars: Vec<u16>) {
+        *self = JVMString::new(chars);
+    }
+
+    pub fn length(self: &JVMString) -> usize {
+        match &self.value {
+            Value::Latin1(bytes) => bytes.len(),
+            Value::Utf16(chars) => chars.len(),
+        }
+    }
+
+    // StringLatin1 and StringUTF16 word the index being out of range differently
+    pub fn char_at(self: &JVMString, index: i32) -> Result<u16, JVMError> {
+        let length = self.length();
+        if index < 0 || index as usize >= length {
+            let message = match self.value {
+                Value::Latin1(_) => format!("String index out of range: {}", index),
+                Value::Utf16(_) => format!("index {}, length {}", index, length),
+            };
+            return Err(JVMError::exception("java/lang/StringIndexOutOfBoundsException", Some(message)));
+        }
+        match &self.value {
+            Value::Latin1(bytes) => Ok(bytes[index as usize] as u16),
+            Value::Utf16(chars) => Ok(chars[index as usize]),
+        }
+    }
+
+    // part of a UTF-16 string may only hold Latin-1 chars, so it is compressed again
+    pub fn substring(self: &JVMString, begin: i32, end: i32) -> Result<JVMString, JVMError> {
+        let length = self.length();
+        if begin < 0 || begin > end || end as usize > length {
+            let message = format!("begin {}, end {}, length {}", begin, end, length);
+            return Err(JVMError::exception("java/lang/StringIndexOutOfBoundsException", Some(message)));
+        }
+        let (begin, end) = (begin as usize, end as usize);
+        match &self.value {
+            Value::Latin1(bytes) => Ok(JVMString{
+                value: Value::Latin1(bytes[begin..end].to_vec()),
+            }),
+            Value::Utf16(chars) => Ok(JVMString::new(chars[begin..end].to_vec())),
+        }
+    }
+
+    /* append a char, which turns a Latin-1 string into UTF-16 if it does not fit in a byte. the
+     * chars are added one by one, so the halves of a surrogate pair can be appended separately
+     */
+    pub fn push(self: &mut JVMString, c: u16) {
+        match (&mut self.value, u8::try_from(c)) {
+            (Value::Latin1(bytes), Ok(byte)) => bytes.push(byte),
+            (Value::Latin1(bytes), Err(_)) => {
+                let mut chars: Vec<u16> = bytes.iter().map(|b| *b as u16).collect();
+                chars.push(c);
+                self.value = Value::Utf16(chars);
+            },
+            (Value::Utf16(chars), _) => chars.push(c),
+        }
+    }
+
+    pub fn push_str(self: &mut JVMString, other: &JVMString) {
+        match (&mut self.value, &other.value) {
+            (Value::Latin1(bytes), Value::Latin1(other)) => bytes.extend_from_slice(other),
+            (Value::Utf16(chars), Value::Latin1(other)) => chars.extend(other.iter().map(|b| *b as u16)),
+            (Value::Utf16(chars), Value::Utf16(other)) => chars.extend_from_slice(other),
+            (Value::Latin1(bytes), Value::Utf16(other)) => {
+                let mut chars: Vec<u16> = bytes.iter().map(|b| *b as u16).collect();
+                chars.extend_from_slice(other);
+                self.value = Value::Utf16(chars);
+            },
+        }
+    }
+
+    /* the operations of StringBuilder that are not appends. they leave a UTF-16 string Latin-1 if
+     * that is all it holds now, for equal strings to be stored the same way
+     */
+    pub fn insert(self: &mut JVMString, offset: usize, other: &JVMString) {
+        if let (Value::Latin1(bytes), Value::Latin1(other)) = (&mut self.value, &other.value) {
+            bytes.splice(offset..offset, other.iter().cloned());
+            return;
+        }
+        let mut chars = self.chars().into_owned();
+        chars.splice(offset..offset, other.chars().iter().cloned());
+        self.value = Value::Utf16(chars);
+    }
+
+    pub fn remove(self: &mut JVMString, index: usize) {
+        match &mut self.value {
+            Value::Latin1(bytes) => {
+                bytes.remove(index);
+            },
+            Value::Utf16(chars) => {
+                chars.remove(index);
+                *self = JVMString::new(std::mem::take(chars));
+            },
+        }
+    }
+
+    // cut the string short, or pad it with null chars
+    pub fn set_length(self: &mut JVMString, length: usize) {
+        match &mut self.value {
+            Value::Latin1(bytes) => bytes.resize(length, 0),
+            Value::Utf16(chars) => {
+                chars.resize(length, 0);
+                *self = JVMString::new(std::mem::take(chars));
+            },
+        }
+    }
+
+    /* reverse the chars, except that the halves of a surrogate pair stay in order
+     * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/StringBuilder.html#reverse()
+     */
+    pub fn reverse(self: &mut JVMString) {
+        match &mut self.value {
+            Value::Latin1(bytes) => bytes.reverse(),
+            Value::Utf16(chars) => {
+                chars.reverse();
+                let mut i = 0;
+                while i + 1 < chars.len() {
+                    if (0xdc00..0xe000).contains(&chars[i]) && (0xd800..0xdc00).contains(&chars[i + 1]) {
+                        chars.swap(i, i + 1);
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            },
+        }
+    }
+
+    // s[0]*31^(n-1) + s[1]*31^(n-2) + ... + s[n-1], wrapping around like int arithmetic does
+    pub fn hash_code(self: &JVMString) -> i32 {
+        match &self.value {
+            Value::Latin1(bytes) => bytes.iter().fold(0i32, |hash, b| hash.wrapping_mul(31).wrapping_add(*b as i32)),
+            Value::Utf16(chars) => chars.iter().fold(0i32, |hash, c| hash.wrapping_mul(31).wrapping_add(*c as i32)),
+        }
+    }
+
+    /* the index of the first occurrence of target at or after from, or -1. a from beyond either
+     * end of the string is clamped to it
+     */
+    pub fn index_of(self: &JVMString, target: &[u16], from: i32) -> i32 {
+        let from = (from.max(0) as usize).min(self.length());
+        match &self.value {
+            // a target with a char outside Latin-1 cannot be in a Latin-1 string
+            Value::Latin1(bytes) => index_or_minus_one(latin1(target).and_then(|target| find(bytes, &target, from))),
+            Value::Utf16(chars) => index_or_minus_one(find(chars, target, from)),
+        }
+    }
+
+    // the index of the last occurrence of target at or before from, or -1
+    pub fn last_index_of(self: &JVMString, target: &[u16], from: i32) -> i32 {
+        if from < 0 {
+            return -1;
+        }
+        match &self.value {
+            Value::Latin1(bytes) => index_or_minus_one(latin1(target).and_then(|target| rfind(bytes, &target, from as usize))),
+            Value::Utf16(chars) => index_or_minus_one(rfind(chars, target, from as usize)),
+        }
+    }
+
+    /* compare strings by their chars, and failing that by their length
+     * https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/String.html#compareTo(java.lang.String)
+     */
+    pub fn compare_to(self: &JVMString, other: &JVMString) -> i32 {
+        if let (Value::Latin1(a), Value::Latin1(b)) = (&self.value, &other.value) {
+            for (a, b) in a.iter().zip(b.iter()) {
+                if a != b {
+                    return *a as i32 - *b as i32;
+                }
+            }
+            return a.len() as i32 - b.len() as i32;
+        }
+        let (a, b) = (self.chars(), other.chars());
+        for (a, b) in a.iter().zip(b.iter()) {
+            if a != b {
+                return *a as i32 - *b as i32;
+            }
+        }
+        return a.len() as i32 - b.len() as i32;
+    }
+}
+
+impl From<&str> for JVMString {
+    fn from(value: &str) -> JVMString {
+        if value.chars().all(|c| (c as u32) < 0x100) {
+            return JVMString{
+                value: Value::Latin1(value.chars().map(|c| c as u8).collect()),
+            };
+        }
+        return JVMString{
+            value: Value::Utf16(value.encode_utf16().collect()),
+        };
+    }
+}
+
+// lone surrogates have no UTF-8 encoding, java writes them out as '?'
+impl fmt::Display for JVMString {
+    fn fmt(self: &JVMString, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Value::Latin1(bytes) => {
+                for b in bytes {
+                    write!(f, "{}", *b as char)?;
+                }
+            },
+            Value::Utf16(chars) => {
+                for c in char::decode_utf16(chars.iter().cloned()) {
+                    write!(f, "{}", c.unwrap_or('?'))?;
+                }
+            },
+        }
+        return Ok(());
+    }
+}
@@ -0,0 +1,170 @@
+/* reading zip archives, which is what jar files are. entries are found through the central
+ * directory at the end of the archive, and only the stored and deflate compression methods that
+ * jar tools write are supported
+ * https://pkware.cachefly.net/webdocs/casestudies/APPNOTE.TXT
+ */
+
+use std::collections::HashMap;
+use std::io;
+use std::path;
+
+use crate::jvm::inflate::inflate;
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x04034b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06054b50;
+
+const LOCAL_HEADER_SIZE: usize = 30;
+const CENTRAL_HEADER_SIZE: usize = 46;
+const END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+
+const ENCRYPTED_FLAG: u16 = 1;
+
+fn invalid(message: String) -> io::Error {
+    return io::Error::new(io::ErrorKind::InvalidData, message);
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, io::Error> {
+    return match data.get(offset..offset + 2) {
+        Some(bytes) => Ok(u16::from_le_bytes([bytes[0], bytes[1]])),
+        None => Err(invalid(format!("zip structure at {} is past the end of the file", offset))),
+    };
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, io::Error> {
+    return match data.get(offset..offset + 4) {
+        Some(bytes) => Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+        None => Err(invalid(format!("zip structure at {} is past the end of the file", offset))),
+    };
+}
+
+// the crc-32 zip stores for each entry, computed bitwise
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffffffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _i in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb88320 & mask);
+        }
+    }
+    return !crc;
+}
+
+// what the central directory says about an entry
+struct ZipEntry {
+    flags: u16,
+    method: u16,
+    crc32: u32,
+    compressed_size: usize,
+    size: usize,
+    local_header_offset: usize,
+}
+
+pub struct ZipArchive {
+    data: Vec<u8>,
+    entries: HashMap<String, ZipEntry>,
+}
+
+impl ZipArchive {
+    pub fn open(path: &path::Path) -> Result<ZipArchive, io::Error> {
+        return ZipArchive::from_bytes(std::fs::read(path)?);
+    }
+
+    pub fn from_bytes(data: Vec<u8>) -> Result<ZipArchive, io::Error> {
+        // the end of central directory record is followed by a comment of up to 65535 bytes
+        if data.len() < END_OF_CENTRAL_DIRECTORY_SIZE {
+            return Err(invalid(String::from("zip file is too short")));
+        }
+        let last = data.len() - END_OF_CENTRAL_DIRECTORY_SIZE;
+        let first = last.saturating_sub(0xffff);
+        let end = match (first..=last).rev().find(|offset| read_u32(&data, *offset).ok() == Some(END_OF_CENTRAL_DIRECTORY_SIGNATURE)) {
+            Some(end) => end,
+            None => return Err(invalid(String::from("zip end of central directory not found"))),
+        };
+
+        let count = read_u16(&data, end + 10)?;
+        let directory_offset = read_u32(&data, end + 16)?;
+        if count == 0xffff || directory_offset == 0xffffffff {
+            return Err(invalid(String::from("zip64 archives are not supported")));
+        }
+
+        let mut entries = HashMap::new();
+        let mut offset = directory_offset as usize;
+        for _i in 0..count {
+            if read_u32(&data, offset)? != CENTRAL_HEADER_SIGNATURE {
+                return Err(invalid(format!("bad zip central directory header at {}", offset)));
+            }
+
+            let name_length = read_u16(&data, offset + 28)? as usize;
+            let extra_length = read_u16(&data, offset + 30)? as usize;
+            let comment_length = read_u16(&data, offset + 32)? as usize;
+            let name_start = offset + CENTRAL_HEADER_SIZE;
+            let name = match data.get(name_start..name_start + name_length) {
+                Some(name) => String::from_utf8_lossy(name).into_owned(),
+                None => return Err(invalid(format!("zip entry name at {} is past the end of the file", name_start))),
+            };
+
+            let entry = ZipEntry{
+                flags: read_u16(&data, offset + 8)?,
+                method: read_u16(&data, offset + 10)?,
+                crc32: read_u32(&data, offset + 16)?,
+                compressed_size: read_u32(&data, offset + 20)? as usize,
+                size: read_u32(&data, offset + 24)? as usize,
+                local_header_offset: read_u32(&data, offset + 42)? as usize,
+            };
+            // like java, the first entry of a name wins
+            entries.entry(name).or_insert(entry);
+
+            offset = name_start + name_length + extra_length + comment_length;
+        }
+
+        return Ok(ZipArchive{
+            data: data,
+            entries: entries,
+        });
+    }
+
+    pub fn contains(self: &ZipArchive, name: &str) -> bool {
+        return self.entries.contains_key(name);
+    }
+
+    // the uncompressed contents of an entry, or None if the archive has no such entry
+    pub fn read(self: &ZipArchive, name: &str) -> Result<Option<Vec<u8>>, io::Error> {
+        let entry = match self.entries.get(name) {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+
+        if entry.flags & ENCRYPTED_FLAG != 0 {
+            return Err(invalid(format!("zip entry {} is encrypted", name)));
+        }
+
+        // the local header repeats the name, but its extra field may differ from the central one
+        let offset = entry.local_header_offset;
+        if read_u32(&self.data, offset)? != LOCAL_HEADER_SIGNATURE {
+            return Err(invalid(format!("bad zip local header for {}", name)));
+        }
+        let name_length = read_u16(&self.data, offset + 26)? as usize;
+        let extra_length = read_u16(&self.data, offset + 28)? as usize;
+        let start = offset + LOCAL_HEADER_SIZE + name_length + extra_length;
+        let compressed = match self.data.get(start..start + entry.compressed_size) {
+            Some(compressed) => compressed,
+            None => return Err(invalid(format!("zip entry {} is past the end of the file", name))),
+        };
+
+        let contents = match entry.method {
+            STORED => compressed.to_vec(),
+            DEFLATED => inflate(compressed, entry.size)?,
+            method => return Err(invalid(format!("zip entry {} uses unsupported compression method {}", name, method))),
+        };
+
+        if contents.len() != entry.size || crc32(&contents) != entry.crc32 {
+            return Err(invalid(format!("zip entry {} is corrupt", name)));
+        }
+        return Ok(Some(contents));
+    }
+}
@@ -1,15 +1,164 @@
 use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
 
 mod jvm;
 
 use jvm::data::*;
 use jvm::exec::*;
+use jvm::classpath::{ClassPath, Jar};
 
 mod debug;
 
+/* a size such as 512k or 2m, as used by -Xss. without a suffix the size is in bytes */
+fn parse_size(text: &str) -> Option<usize> {
+    let (digits, multiplier) = match text.chars().last() {
+        Some('k') | Some('K') => (&text[..text.len() - 1], 1024),
+        Some('m') | Some('M') => (&text[..text.len() - 1], 1024 * 1024),
+        Some('g') | Some('G') => (&text[..text.len() - 1], 1024 * 1024 * 1024),
+        _ => (text, 1),
+    };
+
+    return digits.parse::<usize>().ok().and_then(|size| size.checked_mul(multiplier));
+}
+
+// the jvm refuses to start, the way the java launcher does
+fn fail_to_start(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("Error: Could not create the Java Virtual Machine.");
+    eprintln!("Error: A fatal exception has occurred. Program will exit.");
+    process::exit(1);
+}
+
+/* the class path entry a class file sits under: its directory, less one directory for each part
+ * of the package of the class
+ */
+fn class_path_root(path: &Path, class_name: &str) -> PathBuf {
+    let mut root = path.parent().map(|parent| parent.to_path_buf()).unwrap_or_default();
+    for _i in 0..class_name.matches('/').count() {
+        root.pop();
+    }
+
+    if root.as_os_str().is_empty() {
+        return PathBuf::from(".");
+    }
+    return root;
+}
+
+// a class such as com.acme.Main from the class path
+fn find_main_class(main_class: &str, class_path: &ClassPath) -> Result<JVMClassFile, std::io::Error> {
+    match class_path.read_class(&main_class.replace('.', "/"))? {
+        Some(contents) => {
+            return read_class_file(&mut &contents[..]);
+        },
+        None => {
+            eprintln!("Error: Could not find or load main class {}", main_class);
+            eprintln!("Caused by: java.lang.ClassNotFoundException: {}", main_class);
+            process::exit(1);
+        }
+    }
+}
+
+/* the main class is either a class file, whose directory then goes on the class path so the
+ * classes next to it can be loaded, or a class name found on the class path
+ */
+fn load_main_class(main_class: &str, mut class_path: Vec<PathBuf>) -> Result<(JVMClassFile, ClassPath), std::io::Error> {
+    if main_class.ends_with(".class") {
+        let class_file = parse_class_file(main_class)?;
+        if let Some(class_name) = class_file.class_name() {
+            class_path.insert(0, class_path_root(Path::new(main_class), class_name));
+        }
+        return Ok((class_file, ClassPath::new(&class_path)));
+    }
+
+    let class_path = ClassPath::new(&class_path);
+    return Ok((find_main_class(main_class, &class_path)?, class_path));
+}
+
+/* with -jar the jar is the whole class path, along with whatever its manifest adds, and the
+ * manifest names the main class
+ */
+fn load_jar_main_class(jar_file: &str) -> Result<(JVMClassFile, ClassPath), std::io::Error> {
+    let path = Path::new(jar_file);
+    if !path.is_file() {
+        eprintln!("Error: Unable to access jarfile {}", jar_file);
+        process::exit(1);
+    }
+    let jar = match Jar::open(path) {
+        Ok(jar) => jar,
+        Err(_) => {
+            eprintln!("Error: Invalid or corrupt jarfile {}", jar_file);
+            process::exit(1);
+        }
+    };
+    let main_class = match jar.main_class() {
+        Some(main_class) => main_class,
+        None => {
+            eprintln!("no main manifest attribute, in {}", jar_file);
+            process::exit(1);
+        }
+    };
+
+    let class_path = ClassPath::new(&[path.to_path_buf()]);
+    return Ok((find_main_class(&main_class.replace('/', "."), &class_path)?, class_path));
+}
+
+/* the interpreter runs on a thread of its own, whose native stack is large enough for natives to
+ * call back into java code as deeply as the jvm allows
+ */
 fn main() {
+    let interpreter = thread::Builder::new().stack_size(NATIVE_STACK_SIZE).spawn(run);
+    match interpreter.map(|thread| thread.join()) {
+        Ok(Ok(())) => {
+        },
+        // the panic has been reported by the thread already
+        Ok(Err(_)) => process::exit(101),
+        Err(err) => {
+            eprintln!("could not start the interpreter thread: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+fn run() {
     let args: Vec<String> = env::args().collect();
 
+    // options come before the main class, everything after it is passed to main
+    let mut stack_size = DEFAULT_STACK_SIZE;
+    let mut class_path = None;
+    let mut jar = false;
+    let mut index = 1;
+    while index < args.len() && args[index].starts_with('-') {
+        let option = args[index].as_str();
+        if option == "-cp" || option == "-classpath" || option == "--class-path" {
+            index += 1;
+            match args.get(index) {
+                Some(paths) => class_path = Some(paths.split(':').map(PathBuf::from).collect()),
+                None => fail_to_start(&format!("Error: {} requires class path specification", option)),
+            }
+        } else if option == "-jar" {
+            // the jar file takes the place of the main class
+            if index + 1 >= args.len() {
+                fail_to_start("Error: -jar requires jar file specification");
+            }
+            jar = true;
+            index += 1;
+            break;
+        } else if let Some(size) = option.strip_prefix("-Xss") {
+            match parse_size(size) {
+                Some(size) => stack_size = size,
+                None => fail_to_start(&format!("Invalid thread stack size: {}", option)),
+            }
+        } else {
+            fail_to_start(&format!("Unrecognized option: {}", option));
+        }
+        index += 1;
+    }
+
+    // like java, classes are looked for in the current directory unless told otherwise
+    let class_path = class_path.unwrap_or_else(|| vec![PathBuf::from(".")]);
+
     // iterate through arguments and print each one out
     /*
     for arg in args.iter() {
@@ -17,12 +166,25 @@ fn main() {
     }
     */
     // print just the first argument out, but only if there is at least one argument
-    if args.len() > 1 {
-        match parse_class_file(args[1].as_str()) {
-            Ok(class_file) => {
-                match execute_method(&class_file, "main") {
+    if args.len() > index {
+        let main_class = if jar {
+            load_jar_main_class(&args[index])
+        } else {
+            load_main_class(&args[index], class_path)
+        };
+        match main_class {
+            Ok((class_file, class_path)) => {
+                let options = JVMOptions{
+                    class_path: class_path,
+                    stack_size: stack_size,
+                };
+                match execute_method(class_file, "main", "([Ljava/lang/String;)V", vec![make_string_array(&args[index + 1..])], &options) {
                     Ok(_) => {
                     },
+                    Err(err @ JVMError::Exception(_)) => {
+                        eprint!("Exception in thread \"main\" {0}", err.stack_trace());
+                        process::exit(1);
+                    },
                     Err(err) => {
                         println!("Error: {0}", err);
                     }
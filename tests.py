@@ -27,17 +27,43 @@ def compile_java_files(path):
     if need_compile:
         subprocess.call(['javac'] + java_files, cwd=path)
 
+def generate_class_files(path):
+    """Tests that need bytecode javac will not emit provide a gen.py that
+    writes the class files itself using tests/classgen.py, or that packs
+    them into an app.jar"""
+    generator = os.path.join(path, 'gen.py')
+    outputs = [os.path.join(path, name) for name in ['Main.class', 'app.jar']]
+    outputs = [output for output in outputs if os.path.exists(output)]
+    if not outputs or any(last_modification(output) < last_modification(generator) for output in outputs):
+        env = dict(os.environ, PYTHONPATH=os.path.abspath('tests'), PYTHONDONTWRITEBYTECODE='1')
+        subprocess.call([sys.executable, 'gen.py'], cwd=path, env=env)
+
+def without_debug_output(stderr):
+    """Debug builds of the jvm log to stderr, each line starting with the
+    source location such as [src/jvm/exec.rs:100]"""
+    lines = stderr.splitlines(keepends=True)
+    return b''.join(line for line in lines if not line.startswith(b'[src/'))
+
+def jar_file(path):
+    """Tests with an app.jar are run with -jar"""
+    jar = os.path.join(path, 'app.jar')
+    if os.path.exists(jar):
+        return jar
+    return None
+
+# tests that end with an uncaught exception exit with a non-zero status and
+# print a stack trace, so the exit status and stderr are compared as well
 def run_jvm(path):
-    process = subprocess.run(['./jvm', os.path.join(path, 'Main.class')], capture_output=True)
-    if process.returncode != 0:
-        return b'failed to run: ' + process.stderr
-    return process.stdout
+    jar = jar_file(path)
+    arguments = ['-jar', jar] if jar else [os.path.join(path, 'Main.class')]
+    process = subprocess.run(['./jvm'] + arguments, capture_output=True)
+    return (process.returncode, process.stdout, without_debug_output(process.stderr))
 
 def run_java(path):
-    process = subprocess.run(['java', '-classpath', path, 'Main'], capture_output=True)
-    if process.returncode != 0:
-        return b'java failed to run: ' + process.stderr
-    return process.stdout
+    jar = jar_file(path)
+    arguments = ['-jar', jar] if jar else ['-classpath', path, 'Main']
+    process = subprocess.run(['java'] + arguments, capture_output=True)
+    return (process.returncode, process.stdout, process.stderr)
 
 def xterm_color_start(color):
     return '\033[%sm' % color
@@ -55,7 +81,8 @@ def colorize(s, color):
 
 def do_test(path):
     """`path` should contain a set of java files.
-        1. javac to compile them (if the .class files don't already exist)
+        1. javac to compile them (if the .class files don't already exist),
+           or run gen.py if the test assembles its own class files
         2. run `jvm` on the class files to get the actual output
         3. run `java` on the class files to get the expected output
         4. compare expected output to actual
@@ -64,7 +91,10 @@ def do_test(path):
     print(path + ': ', end='')
     sys.stdout.flush()
 
-    compile_java_files(path)
+    if os.path.exists(os.path.join(path, 'gen.py')):
+        generate_class_files(path)
+    else:
+        compile_java_files(path)
     actual = run_jvm(path)
     expected = run_java(path)
 
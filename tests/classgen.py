@@ -0,0 +1,228 @@
+"""Tiny class file assembler for tests that javac can no longer produce
+(old class file versions, jsr/ret, etc).
+
+A test directory may contain a `gen.py` that imports this module and writes
+its own Main.class; tests.py runs it instead of javac.
+"""
+
+import struct
+
+OPCODES = {
+    'aconst_null': 0x01,
+    'iconst_m1': 0x02, 'iconst_0': 0x03, 'iconst_1': 0x04, 'iconst_2': 0x05,
+    'bipush': 0x10, 'sipush': 0x11,
+    'ldc': 0x12, 'ldc_w': 0x13, 'ldc2_w': 0x14,
+    'iload': 0x15, 'aload': 0x19,
+    'iload_0': 0x1a, 'iload_1': 0x1b, 'iload_2': 0x1c, 'iload_3': 0x1d,
+    'aload_0': 0x2a, 'aload_1': 0x2b, 'aload_2': 0x2c, 'aload_3': 0x2d,
+    'istore': 0x36, 'astore': 0x3a,
+    'istore_0': 0x3b, 'istore_1': 0x3c, 'istore_2': 0x3d, 'istore_3': 0x3e,
+    'astore_0': 0x4b, 'astore_1': 0x4c, 'astore_2': 0x4d, 'astore_3': 0x4e,
+    'pop': 0x57, 'pop2': 0x58, 'dup': 0x59,
+    'iadd': 0x60,
+    'iinc': 0x84,
+    'if_icmpge': 0xa2,
+    'goto': 0xa7, 'jsr': 0xa8, 'ret': 0xa9,
+    'ireturn': 0xac, 'lreturn': 0xad, 'areturn': 0xb0, 'return': 0xb1,
+    'getstatic': 0xb2, 'putstatic': 0xb3, 'getfield': 0xb4, 'putfield': 0xb5,
+    'invokevirtual': 0xb6, 'invokespecial': 0xb7, 'invokestatic': 0xb8,
+    'invokeinterface': 0xb9, 'invokedynamic': 0xba,
+    'new': 0xbb, 'athrow': 0xbf, 'checkcast': 0xc0,
+    'goto_w': 0xc8, 'jsr_w': 0xc9,
+}
+
+BRANCH16 = {'if_icmpge', 'goto', 'jsr'}
+BRANCH32 = {'goto_w', 'jsr_w'}
+
+
+class Label:
+    pass
+
+
+class Code:
+    def __init__(self):
+        self.items = []
+        self.handlers = []
+        self.offsets = {}
+
+    def label(self):
+        return Label()
+
+    def mark(self, label):
+        self.items.append(label)
+
+    def op(self, name, *operands):
+        self.items.append((name, operands))
+
+    def handler(self, start, end, handler, catch_type=0):
+        """Add an exception table entry; catch_type is a class constant
+        index, or 0 to catch everything"""
+        self.handlers.append((start, end, handler, catch_type))
+
+    def exception_table(self):
+        return [(self.offsets[start], self.offsets[end], self.offsets[handler], catch_type)
+                for start, end, handler, catch_type in self.handlers]
+
+    def assemble(self):
+        # two passes: first find label offsets, then encode
+        offsets = {}
+        for resolve in (False, True):
+            code = bytearray()
+            for item in self.items:
+                if isinstance(item, Label):
+                    offsets[item] = len(code)
+                    continue
+                name, operands = item
+                start = len(code)
+                code.append(OPCODES[name])
+                if name in BRANCH16:
+                    target = offsets.get(operands[0], start) if resolve else start
+                    code += struct.pack('>h', target - start)
+                elif name in BRANCH32:
+                    target = offsets.get(operands[0], start) if resolve else start
+                    code += struct.pack('>i', target - start)
+                elif name in ('bipush', 'ldc', 'iload', 'aload', 'istore', 'astore', 'ret'):
+                    code += struct.pack('>B', operands[0] & 0xff)
+                elif name == 'iinc':
+                    code += struct.pack('>Bb', operands[0], operands[1])
+                elif name == 'invokeinterface':
+                    code += struct.pack('>HBB', operands[0], operands[1], 0)
+                elif name == 'invokedynamic':
+                    code += struct.pack('>HH', operands[0], 0)
+                else:
+                    for operand in operands:
+                        code += struct.pack('>H', operand)
+        self.offsets = offsets
+        return bytes(code)
+
+
+class ClassFile:
+    def __init__(self, name, super_name='java/lang/Object', major=49, minor=0):
+        self.major = major
+        self.minor = minor
+        self.pool = []
+        self.cache = {}
+        self.methods = []
+        self.fields = []
+        self.attributes = []
+        self.bootstraps = []
+        self.pending = {}
+        self.this_class = self.klass(name)
+        self.super_class = self.klass(super_name)
+
+    def _add(self, key, data, slots=1):
+        if key in self.cache:
+            return self.cache[key]
+        index = len(self.pool) + 1
+        self.pool.append(data)
+        for _ in range(slots - 1):
+            self.pool.append(None)
+        self.cache[key] = index
+        return index
+
+    def utf8(self, s):
+        raw = s.encode('utf-8')
+        return self._add(('utf8', s), struct.pack('>BH', 1, len(raw)) + raw)
+
+    def integer(self, value):
+        return self._add(('int', value), struct.pack('>Bi', 3, value))
+
+    def long(self, value):
+        return self._add(('long', value), struct.pack('>Bq', 5, value), 2)
+
+    def klass(self, name):
+        return self._add(('class', name), struct.pack('>BH', 7, self.utf8(name)))
+
+    def string(self, s):
+        return self._add(('string', s), struct.pack('>BH', 8, self.utf8(s)))
+
+    def name_and_type(self, name, descriptor):
+        return self._add(('nat', name, descriptor),
+                         struct.pack('>BHH', 12, self.utf8(name), self.utf8(descriptor)))
+
+    def fieldref(self, owner, name, descriptor):
+        return self._add(('field', owner, name, descriptor),
+                         struct.pack('>BHH', 9, self.klass(owner), self.name_and_type(name, descriptor)))
+
+    def methodref(self, owner, name, descriptor):
+        return self._add(('method', owner, name, descriptor),
+                         struct.pack('>BHH', 10, self.klass(owner), self.name_and_type(name, descriptor)))
+
+    def method_handle(self, kind, reference):
+        """kind is a reference kind such as 6 for REF_invokeStatic and
+        reference the index of the field or method it refers to"""
+        return self._add(('handle', kind, reference), struct.pack('>BBH', 15, kind, reference))
+
+    def method_type(self, descriptor):
+        return self._add(('type', descriptor), struct.pack('>BH', 16, self.utf8(descriptor)))
+
+    def bootstrap_method(self, handle, arguments=()):
+        """an entry of the BootstrapMethods attribute, which is written along
+        with the class once there is one"""
+        self.bootstraps.append((handle, tuple(arguments)))
+        return len(self.bootstraps) - 1
+
+    def dynamic(self, bootstrap, name, descriptor):
+        return self._add(('dynamic', bootstrap, name, descriptor),
+                         struct.pack('>BHH', 17, bootstrap, self.name_and_type(name, descriptor)))
+
+    def reserve_dynamic(self, name, descriptor):
+        """a dynamic constant whose bootstrap method is set later with
+        set_dynamic, for constants that are their own bootstrap arguments"""
+        index = len(self.pool) + 1
+        self.pool.append(None)
+        self.pending[index] = self.name_and_type(name, descriptor)
+        return index
+
+    def set_dynamic(self, index, bootstrap):
+        self.pool[index - 1] = struct.pack('>BHH', 17, bootstrap, self.pending.pop(index))
+
+    def add_field(self, access, name, descriptor):
+        self.fields.append((access, self.utf8(name), self.utf8(descriptor)))
+
+    def add_method(self, access, name, descriptor, code, max_stack, max_locals):
+        """code may be None for abstract and native methods"""
+        if code is None:
+            self.methods.append((access, self.utf8(name), self.utf8(descriptor), None))
+            return
+        assembled = code.assemble()
+        self.methods.append((access, self.utf8(name), self.utf8(descriptor),
+                             (self.utf8('Code'), assembled, code.exception_table(), max_stack, max_locals)))
+
+    def write(self, path):
+        if self.bootstraps:
+            self.utf8('BootstrapMethods')
+        out = bytearray()
+        out += struct.pack('>IHHH', 0xcafebabe, self.minor, self.major, len(self.pool) + 1)
+        for entry in self.pool:
+            if entry is not None:
+                out += entry
+        # ACC_PUBLIC | ACC_SUPER
+        out += struct.pack('>HHHH', 0x21, self.this_class, self.super_class, 0)
+        out += struct.pack('>H', len(self.fields))
+        for access, name, descriptor in self.fields:
+            out += struct.pack('>HHHH', access, name, descriptor, 0)
+        out += struct.pack('>H', len(self.methods))
+        for access, name, descriptor, code in self.methods:
+            if code is None:
+                out += struct.pack('>HHHH', access, name, descriptor, 0)
+                continue
+            code_name, assembled, exception_table, max_stack, max_locals = code
+            out += struct.pack('>HHHH', access, name, descriptor, 1)
+            body = struct.pack('>HHI', max_stack, max_locals, len(assembled)) + assembled
+            body += struct.pack('>H', len(exception_table))
+            for entry in exception_table:
+                body += struct.pack('>HHHH', *entry)
+            body += struct.pack('>H', 0)
+            out += struct.pack('>HI', code_name, len(body)) + body
+        if self.bootstraps:
+            body = struct.pack('>H', len(self.bootstraps))
+            for handle, arguments in self.bootstraps:
+                body += struct.pack('>HH', handle, len(arguments))
+                for argument in arguments:
+                    body += struct.pack('>H', argument)
+            out += struct.pack('>HHI', 1, self.utf8('BootstrapMethods'), len(body)) + body
+        else:
+            out += struct.pack('>H', 0)
+        with open(path, 'wb') as f:
+            f.write(out)
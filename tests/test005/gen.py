@@ -0,0 +1,64 @@
+# Java 5 style finally blocks compiled with jsr/ret subroutines
+from classgen import ClassFile, Code
+
+ACC_PUBLIC = 0x1
+ACC_STATIC = 0x8
+
+main = ClassFile('Main', major=49)
+
+out = main.fieldref('java/lang/System', 'out', 'Ljava/io/PrintStream;')
+println_string = main.methodref('java/io/PrintStream', 'println', '(Ljava/lang/String;)V')
+println_int = main.methodref('java/io/PrintStream', 'println', '(I)V')
+
+def say(code, text):
+    code.op('getstatic', out)
+    code.op('ldc', main.string(text))
+    code.op('invokevirtual', println_string)
+
+init = Code()
+init.op('aload_0')
+init.op('invokespecial', main.methodref('java/lang/Object', '<init>', '()V'))
+init.op('return')
+main.add_method(ACC_PUBLIC, '<init>', '()V', init, 1, 1)
+
+# static int count(int n) { int i = 0; try { while (i < n) i++; } finally { print } return i; }
+count = Code()
+loop = count.label()
+done = count.label()
+finally_block = count.label()
+count.op('iconst_0')
+count.op('istore_1')
+count.mark(loop)
+count.op('iload_1')
+count.op('iload_0')
+count.op('if_icmpge', done)
+count.op('iinc', 1, 1)
+count.op('goto', loop)
+count.mark(done)
+count.op('jsr_w', finally_block)
+count.op('iload_1')
+count.op('ireturn')
+count.mark(finally_block)
+count.op('astore_2')
+say(count, "finally in count")
+count.op('ret', 2)
+main.add_method(ACC_PUBLIC | ACC_STATIC, 'count', '(I)I', count, 2, 3)
+
+code = Code()
+subroutine = code.label()
+say(code, "before")
+code.op('jsr', subroutine)
+say(code, "between")
+code.op('jsr', subroutine)
+code.op('getstatic', out)
+code.op('bipush', 7)
+code.op('invokestatic', main.methodref('Main', 'count', '(I)I'))
+code.op('invokevirtual', println_int)
+code.op('return')
+code.mark(subroutine)
+code.op('astore_1')
+say(code, "in subroutine")
+code.op('ret', 1)
+main.add_method(ACC_PUBLIC | ACC_STATIC, 'main', '([Ljava/lang/String;)V', code, 2, 2)
+
+main.write('Main.class')
@@ -0,0 +1,92 @@
+public class Main{
+    private int count;
+    private long total;
+
+    public Main(){
+        this.count = 0;
+        this.total = 0L;
+    }
+
+    public static long factorial(int n){
+        long result = 1;
+        for (int i = 2; i <= n; i++){
+            result *= i;
+        }
+        return result;
+    }
+
+    public static long sum(long a, int b, long c){
+        return a + b + c;
+    }
+
+    public static double average(double a, double b){
+        return (a + b) / 2;
+    }
+
+    public int next(){
+        return this.count++;
+    }
+
+    public long add(long amount){
+        return this.total += amount;
+    }
+
+    public static void main(String... args){
+        Main m = new Main();
+        m.next();
+        m.next();
+        int before = m.next();
+        System.out.println(before);
+        System.out.println(m.count);
+
+        m.add(5);
+        System.out.println(m.add(1L << 40));
+        m.total++;
+        long old = m.total++;
+        System.out.println(old);
+        System.out.println(m.total);
+
+        System.out.println(factorial(20));
+        System.out.println(factorial(21));
+        System.out.println(sum(1L << 33, -7, 100L));
+        factorial(5);
+
+        System.out.println(average(3, 4));
+        System.out.println(1.0 / 3);
+        System.out.println(1e10);
+        System.out.println(0.001);
+        System.out.println(1.5f * 3);
+        System.out.println(100.0f / 3);
+        double d = 0.1 + 0.2;
+        System.out.println(d);
+        if (d > 0.3){
+            System.out.println(1);
+        }
+
+        int x = 17;
+        x *= 3;
+        x -= 4;
+        x /= 2;
+        x %= 7;
+        x <<= 3;
+        x >>= 1;
+        x ^= 0xff;
+        System.out.println(x);
+        System.out.println(-x >>> 28);
+        long big = -17L;
+        System.out.println(big >>> 60);
+        System.out.println(big * 1000000007L);
+        if (big < factorial(3)){
+            System.out.println(2);
+        }
+
+        double huge = 3.99e10;
+        System.out.println((int) huge);
+        System.out.println((long) -2.5);
+        int wide = 200;
+        System.out.println((byte) wide);
+        System.out.println((short) (wide * 350));
+        int max = 2147483647;
+        System.out.println(max + 1);
+    }
+}
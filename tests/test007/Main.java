@@ -0,0 +1,50 @@
+public class Main{
+    private Main next;
+    private String name;
+    private int count;
+    private long total;
+    private double ratio;
+
+    public Main(){
+    }
+
+    public static String describe(Main m){
+        if (m == null){
+            return "nothing";
+        }
+        if (m.next == null){
+            return "last";
+        }
+        return "more";
+    }
+
+    public static void main(String... args){
+        Main first = new Main();
+        System.out.println(first.next);
+        System.out.println(first.name);
+        System.out.println(first.count);
+        System.out.println(first.total);
+        System.out.println(first.ratio);
+
+        Main second = new Main();
+        first.next = second;
+        System.out.println(describe(null));
+        System.out.println(describe(first));
+        System.out.println(describe(second));
+
+        if (first.next == second){
+            System.out.println("same");
+        }
+        if (first != second){
+            System.out.println("different");
+        }
+        if (second.next != null){
+            System.out.println("wrong");
+        }
+
+        String nothing = null;
+        System.out.println(nothing);
+        second.name = "second";
+        System.out.println(first.next.name);
+    }
+}
@@ -0,0 +1,81 @@
+public class Main{
+    public static int sum(int[] values){
+        int total = 0;
+        for (int i = 0; i < values.length; i++){
+            total += values[i];
+        }
+        return total;
+    }
+
+    public static void main(String... args){
+        System.out.println(args.length);
+
+        int[] numbers = new int[5];
+        for (int i = 0; i < numbers.length; i++){
+            numbers[i] = i * i;
+        }
+        numbers[2] += 10;
+        System.out.println(sum(numbers));
+        System.out.println(numbers[2]);
+
+        long[] counters = new long[3];
+        counters[1] = 1L << 40;
+        long old = counters[1]++;
+        System.out.println(old);
+        System.out.println(counters[1]);
+        System.out.println(counters[0]);
+
+        double[] ratios = {0.5, 0.25, 0.125};
+        System.out.println(ratios[0] + ratios[1] + ratios[2]);
+        float[] small = new float[2];
+        small[1] = 2.5f;
+        System.out.println(small[1] * small[1]);
+
+        byte[] bytes = new byte[2];
+        bytes[0] = (byte) 200;
+        bytes[1]++;
+        System.out.println(bytes[0]);
+        System.out.println(bytes[1]);
+
+        char[] letters = new char[3];
+        letters[0] = 'a';
+        letters[1] = (char) (letters[0] + 1);
+        System.out.println((int) letters[1]);
+
+        short[] shorts = {(short) 40000, 7};
+        System.out.println(shorts[0]);
+
+        boolean[] flags = new boolean[4];
+        flags[2] = true;
+        if (flags[2] && !flags[1]){
+            System.out.println("flags");
+        }
+
+        int[][] grid = new int[3][4];
+        grid[1][2] = 7;
+        grid[2][3] = grid[1][2] * 2;
+        System.out.println(grid.length);
+        System.out.println(grid[2].length);
+        System.out.println(grid[2][3]);
+
+        int[][][] cube = new int[2][3][];
+        System.out.println(cube[1][2]);
+        cube[1][2] = new int[]{1, 2, 3};
+        System.out.println(sum(cube[1][2]));
+
+        String[] names = new String[2];
+        System.out.println(names[0]);
+        names[1] = "second";
+        Object[] objects = names;
+        objects[0] = "first";
+        System.out.println(names[0]);
+        System.out.println(names[1]);
+
+        Object[][] nested = new String[2][2];
+        nested[0] = new String[]{"x"};
+        System.out.println(nested[0][0]);
+        Object anything = grid;
+        Object[] rows = grid;
+        System.out.println(rows.length);
+    }
+}
@@ -0,0 +1,66 @@
+public class Main implements Runnable, java.io.Serializable{
+    public void run(){
+    }
+
+    public static void check(boolean result){
+        if (result){
+            System.out.println("yes");
+        } else {
+            System.out.println("no");
+        }
+    }
+
+    public static void main(String... args){
+        Object main = new Main();
+        check(main instanceof Main);
+        check(main instanceof Runnable);
+        check(main instanceof java.io.Serializable);
+        check(main instanceof Cloneable);
+        check(main instanceof String);
+        Main back = (Main) main;
+        Runnable runnable = (Runnable) main;
+        check(back == runnable);
+
+        Object text = "text";
+        check(text instanceof String);
+        check(text instanceof Comparable);
+        check(text instanceof CharSequence);
+        check(text instanceof Runnable);
+        String string = (String) text;
+        System.out.println(string);
+
+        Object nothing = null;
+        check(nothing instanceof Object);
+        String stillNothing = (String) nothing;
+        System.out.println(stillNothing);
+
+        Object ints = new int[3];
+        check(ints instanceof int[]);
+        check(ints instanceof long[]);
+        check(ints instanceof Object[]);
+        check(ints instanceof Cloneable);
+        check(ints instanceof java.io.Serializable);
+        int[] back2 = (int[]) ints;
+        System.out.println(back2.length);
+
+        Object strings = new String[2];
+        check(strings instanceof String[]);
+        check(strings instanceof Object[]);
+        check(strings instanceof Comparable[]);
+        check(strings instanceof Main[]);
+        Object[] objects = (Object[]) strings;
+        System.out.println(objects.length);
+
+        Object mains = new Main[1][1];
+        check(mains instanceof Runnable[][]);
+        check(mains instanceof Object[]);
+        check(mains instanceof java.io.Serializable[]);
+        check(mains instanceof Cloneable[]);
+        check(mains instanceof Runnable[]);
+
+        Object grid = new int[2][2];
+        check(grid instanceof Object[]);
+        check(grid instanceof int[][]);
+        check(grid instanceof long[][]);
+    }
+}
@@ -0,0 +1,95 @@
+public class Main {
+    static int depth(int n) {
+        if (n == 0) {
+            throw new IllegalStateException("bottom");
+        }
+        return depth(n - 1) + 1;
+    }
+
+    static int withFinally(int n) {
+        try {
+            if (n > 0) {
+                throw new IllegalArgumentException("positive");
+            }
+            return n;
+        } finally {
+            System.out.println("finally");
+            System.out.println(n);
+        }
+    }
+
+    static void rethrow() throws Exception {
+        try {
+            throw new Exception("first");
+        } catch (Exception e) {
+            System.out.println("caught");
+            System.out.println(e.getMessage());
+            throw new RuntimeException("second", e);
+        }
+    }
+
+    public static void main(String[] args) {
+        try {
+            throw new RuntimeException("simple");
+        } catch (RuntimeException e) {
+            System.out.println(e.getMessage());
+        }
+
+        // caught by a superclass handler
+        try {
+            throw new ArithmeticException("arithmetic");
+        } catch (Exception e) {
+            System.out.println(e.toString());
+        }
+
+        // unwinds through several frames
+        try {
+            depth(5);
+        } catch (IllegalStateException e) {
+            System.out.println("unwound");
+            System.out.println(e.getMessage());
+        }
+
+        System.out.println(withFinally(0));
+        try {
+            withFinally(3);
+        } catch (IllegalArgumentException e) {
+            System.out.println("after finally");
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            rethrow();
+        } catch (Exception e) {
+            System.out.println(e.getMessage());
+            System.out.println(e.getCause().getMessage());
+        }
+
+        // the first matching handler wins
+        try {
+            throw new UnsupportedOperationException();
+        } catch (IllegalStateException | UnsupportedOperationException e) {
+            System.out.println("multi");
+            System.out.println(e.getMessage());
+        } catch (RuntimeException e) {
+            System.out.println("wrong handler");
+        }
+
+        // nested handlers
+        try {
+            try {
+                throw new Error("inner");
+            } catch (RuntimeException e) {
+                System.out.println("wrong handler");
+            } finally {
+                System.out.println("inner finally");
+            }
+        } catch (Error e) {
+            System.out.println("outer");
+            System.out.println(e.getMessage());
+        }
+
+        System.out.println("uncaught next");
+        throw new IllegalStateException("not caught");
+    }
+}
@@ -0,0 +1,91 @@
+public class Main {
+    int value;
+
+    static int divide(int a, int b) {
+        return a / b;
+    }
+
+    public static void main(String[] args) {
+        try {
+            divide(1, 0);
+        } catch (ArithmeticException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            long zero = 0;
+            System.out.println(5L % zero);
+        } catch (ArithmeticException e) {
+            System.out.println(e.toString());
+        }
+
+        Main main = null;
+        try {
+            System.out.println(main.value);
+        } catch (NullPointerException e) {
+            System.out.println("getfield on null");
+        }
+
+        try {
+            main.value = 1;
+        } catch (NullPointerException e) {
+            System.out.println("putfield on null");
+        }
+
+        int[] numbers = null;
+        try {
+            System.out.println(numbers.length);
+        } catch (NullPointerException e) {
+            System.out.println("arraylength on null");
+        }
+
+        RuntimeException exception = null;
+        try {
+            throw exception;
+        } catch (NullPointerException e) {
+            System.out.println("athrow on null");
+        }
+
+        numbers = new int[3];
+        try {
+            numbers[3] = 1;
+        } catch (ArrayIndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            System.out.println(numbers[-1]);
+        } catch (IndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            numbers = new int[-2];
+        } catch (NegativeArraySizeException e) {
+            System.out.println(e.getMessage());
+        }
+
+        Object object = new int[0];
+        try {
+            String string = (String) object;
+        } catch (ClassCastException e) {
+            System.out.println(e.getMessage());
+        }
+
+        Object[] strings = new String[1];
+        try {
+            strings[0] = new int[0];
+        } catch (ArrayStoreException e) {
+            System.out.println(e.getMessage());
+        }
+
+        // errors the jvm raises can be caught as any of their superclasses
+        try {
+            divide(2, 0);
+        } catch (RuntimeException e) {
+            System.out.println(e.getMessage());
+        }
+
+        System.out.println(divide(6, 0));
+    }
+}
@@ -0,0 +1,97 @@
+# linkage errors that javac will not compile: missing classes, fields and
+# methods, and fields and methods used with the wrong kind of instruction
+from classgen import ClassFile, Code
+
+ACC_PUBLIC = 0x1
+ACC_STATIC = 0x8
+ACC_NATIVE = 0x100
+
+main = ClassFile('Main')
+
+out = main.fieldref('java/lang/System', 'out', 'Ljava/io/PrintStream;')
+println_string = main.methodref('java/io/PrintStream', 'println', '(Ljava/lang/String;)V')
+get_message = main.methodref('java/lang/Throwable', 'getMessage', '()Ljava/lang/String;')
+throwable = main.klass('java/lang/Throwable')
+
+init = Code()
+init.op('aload_0')
+init.op('invokespecial', main.methodref('java/lang/Object', '<init>', '()V'))
+init.op('return')
+main.add_method(ACC_PUBLIC, '<init>', '()V', init, 1, 1)
+
+empty = Code()
+empty.op('return')
+main.add_method(ACC_PUBLIC, 'instanceMethod', '()V', empty, 0, 1)
+main.add_method(ACC_PUBLIC | ACC_STATIC, 'staticMethod', '()V', empty, 0, 0)
+main.add_method(ACC_PUBLIC | ACC_NATIVE, 'nativeMethod', '()V', None, 0, 0)
+main.add_field(ACC_PUBLIC, 'instanceField', 'I')
+main.add_field(ACC_PUBLIC | ACC_STATIC, 'staticField', 'I')
+
+code = Code()
+
+def new_main():
+    code.op('new', main.klass('Main'))
+    code.op('dup')
+    code.op('invokespecial', main.methodref('Main', '<init>', '()V'))
+
+def expect_error(body):
+    """run body and print the message of the throwable it raises"""
+    start, end, handler, done = code.label(), code.label(), code.label(), code.label()
+    code.mark(start)
+    body()
+    code.mark(end)
+    code.op('goto', done)
+    code.mark(handler)
+    code.op('astore_1')
+    code.op('getstatic', out)
+    code.op('aload_1')
+    code.op('invokevirtual', get_message)
+    code.op('invokevirtual', println_string)
+    code.mark(done)
+    code.handler(start, end, handler, throwable)
+
+def missing_method():
+    code.op('invokestatic', main.methodref('Main', 'missing', '()V'))
+
+def missing_method_with_arguments():
+    code.op('iconst_0')
+    code.op('aconst_null')
+    code.op('invokestatic', main.methodref('Main', 'missing', '(I[Ljava/lang/String;)J'))
+    code.op('pop2')
+
+def missing_field():
+    code.op('getstatic', main.fieldref('Main', 'missing', 'I'))
+    code.op('pop')
+
+def missing_class():
+    code.op('new', main.klass('Missing'))
+    code.op('pop')
+
+def static_call_of_instance_method():
+    code.op('invokestatic', main.methodref('Main', 'instanceMethod', '()V'))
+
+def virtual_call_of_static_method():
+    new_main()
+    code.op('invokevirtual', main.methodref('Main', 'staticMethod', '()V'))
+
+def getstatic_of_instance_field():
+    code.op('getstatic', main.fieldref('Main', 'instanceField', 'I'))
+    code.op('pop')
+
+def getfield_of_static_field():
+    new_main()
+    code.op('getfield', main.fieldref('Main', 'staticField', 'I'))
+    code.op('pop')
+
+def unimplemented_native_method():
+    new_main()
+    code.op('invokevirtual', main.methodref('Main', 'nativeMethod', '()V'))
+
+for body in (missing_method, missing_method_with_arguments, missing_field, missing_class,
+             static_call_of_instance_method, virtual_call_of_static_method,
+             getstatic_of_instance_field, getfield_of_static_field, unimplemented_native_method):
+    expect_error(body)
+code.op('return')
+main.add_method(ACC_PUBLIC | ACC_STATIC, 'main', '([Ljava/lang/String;)V', code, 3, 2)
+
+main.write('Main.class')
@@ -0,0 +1,62 @@
+public class Main implements AutoCloseable {
+    static RuntimeException create() {
+        return new RuntimeException("created");
+    }
+
+    static void fail(int depth) {
+        if (depth == 0) {
+            int[] numbers = new int[1];
+            numbers[2] = 1;
+        }
+        fail(depth - 1);
+    }
+
+    static void wrap() {
+        try {
+            fail(2);
+        } catch (ArrayIndexOutOfBoundsException e) {
+            throw new IllegalStateException("wrapped", e);
+        }
+    }
+
+    public void close() {
+        throw new IllegalArgumentException("close failed");
+    }
+
+    public static void main(String[] args) {
+        RuntimeException created = create();
+        StackTraceElement[] trace = created.getStackTrace();
+        System.out.println(trace.length);
+        System.out.println(trace[0].getClassName());
+        System.out.println(trace[0].getMethodName());
+        System.out.println(trace[0].getFileName());
+        System.out.println(trace[0].getLineNumber());
+        System.out.println(trace[1].toString());
+        created.printStackTrace(System.out);
+
+        try {
+            wrap();
+        } catch (IllegalStateException e) {
+            e.printStackTrace(System.out);
+            System.out.println(e.getCause().getMessage());
+        }
+
+        try (Main main = new Main()) {
+            throw new UnsupportedOperationException("body failed");
+        } catch (UnsupportedOperationException e) {
+            System.out.println(e.getSuppressed().length);
+            System.out.println(e.getSuppressed()[0].getMessage());
+            e.printStackTrace(System.out);
+        }
+
+        Exception refilled = new Exception("refilled");
+        refilled.fillInStackTrace();
+        refilled.printStackTrace();
+
+        Error error = new Error();
+        error.initCause(new Exception("cause"));
+        error.printStackTrace();
+
+        wrap();
+    }
+}
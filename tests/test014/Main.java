@@ -0,0 +1,217 @@
+public class Main {
+    Main next;
+    int value;
+    long total;
+    int[] numbers;
+    static Main shared;
+    static Main[] all;
+
+    Main get(int i) {
+        return null;
+    }
+
+    Main find(String key, Object o, long l, int[] a, Main[][] b) {
+        return null;
+    }
+
+    static Main make() {
+        return null;
+    }
+
+    static void parameters(int count, long wide, Main first, Main[] rest) {
+        try {
+            System.out.println(first.value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            System.out.println(rest[count].next);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        first = new Main();
+        try {
+            System.out.println(first.next.value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+    }
+
+    void fields(long wide, Main other) {
+        try {
+            System.out.println(this.next.value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            System.out.println(other.value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        Main local = null;
+        try {
+            System.out.println(local.value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            local.total = 7;
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            System.out.println(next.next.next.next.next.next.next.value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            System.out.println(shared.value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+    }
+
+    void invokes() {
+        try {
+            System.out.println(get(1).next.value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            find("a", null, 1, null, null).value = 1;
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            System.out.println(get(0).get(1).value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            make().fields(1, null);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            next.find("b", this, 2, numbers, null);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+    }
+
+    void arrays(long wide) {
+        try {
+            System.out.println(all[0].value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            System.out.println(all[value + 1]);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            System.out.println(numbers.length);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            numbers[2] = 5;
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            long[] longs = null;
+            longs[1] = 3L;
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            boolean[] flags = null;
+            System.out.println(flags[0]);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            char[][] chars = new char[2][];
+            System.out.println(chars[1][0]);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            Main[] ms = new Main[3];
+            System.out.println(ms[value * 2].value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            Main[] ms = new Main[3];
+            System.out.println(ms[(int) wide].value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            Main[] ms = new Main[300];
+            System.out.println(ms[200].value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            Main[] ms = new Main[70000];
+            System.out.println(ms[65000].value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+    }
+
+    void merges(Main other) {
+        Main x = value > 0 ? next : other;
+        try {
+            System.out.println(x.value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            System.out.println((value > 0 ? next : other).value);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+    }
+
+    void statements() {
+        try {
+            RuntimeException exception = null;
+            throw exception;
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            synchronized (next) {
+                System.out.println("unreachable");
+            }
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            throw new NullPointerException();
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            throw new NullPointerException("explicit");
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+    }
+
+    public static void main(String[] args) {
+        Main main = new Main();
+        parameters(1, 2, null, null);
+        main.fields(1, null);
+        main.invokes();
+        main.arrays(1);
+        main.merges(null);
+        main.statements();
+        System.out.println(main.next.value);
+    }
+}
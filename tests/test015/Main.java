@@ -0,0 +1,69 @@
+public class Main {
+    static void recurse(int[] depth) {
+        depth[0]++;
+        recurse(depth);
+    }
+
+    static int sum(int n) {
+        if (n == 0) {
+            return 0;
+        }
+        return n + sum(n - 1);
+    }
+
+    static long fill(long a, long b, double c, int d) {
+        long x = a + b;
+        double y = c * d;
+        return x + (long) y + fill(a, b, c, d);
+    }
+
+    static void recurseAndCatch(int n, int[] caughtAt) {
+        try {
+            recurseAndCatch(n + 1, caughtAt);
+        } catch (StackOverflowError e) {
+            if (caughtAt[0] == -1) {
+                caughtAt[0] = n;
+            }
+        }
+    }
+
+    int countdown(int n) {
+        if (n == 0) {
+            return 0;
+        }
+        return 1 + countdown(n - 1);
+    }
+
+    public static void main(String[] args) {
+        int[] depth = new int[1];
+        try {
+            recurse(depth);
+        } catch (StackOverflowError e) {
+            System.out.println("caught StackOverflowError");
+            System.out.println(e.getMessage());
+            System.out.println(depth[0] > 1000 ? "deep" : "shallow");
+            System.out.println(e.getStackTrace().length);
+            System.out.println(e.getStackTrace()[0].getMethodName());
+        }
+
+        try {
+            fill(1, 2, 3.0, 4);
+        } catch (VirtualMachineError e) {
+            System.out.println("caught VirtualMachineError");
+        }
+
+        // the stack is usable again once the error has been caught
+        System.out.println(sum(2000));
+        System.out.println(new Main().countdown(2000));
+
+        int[] caughtAt = { -1 };
+        recurseAndCatch(0, caughtAt);
+        System.out.println(caughtAt[0] > 1000 ? "deep" : "shallow");
+
+        try {
+            recurse(depth);
+        } catch (Error e) {
+            System.out.println(e instanceof StackOverflowError ? "StackOverflowError" : "other");
+        }
+    }
+}
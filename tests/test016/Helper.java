@@ -0,0 +1,20 @@
+public class Helper {
+    int count;
+
+    Helper(int count) {
+        this.count = count;
+    }
+
+    int next() {
+        count = count + 1;
+        return count;
+    }
+
+    static int twice(int value) {
+        return Main.add(value, value);
+    }
+
+    static void fail(String message) {
+        throw new IllegalStateException(message);
+    }
+}
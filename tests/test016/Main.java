@@ -0,0 +1,33 @@
+import com.acme.Counter;
+
+public class Main {
+    static int add(int a, int b) {
+        return a + b;
+    }
+
+    public static void main(String[] args) {
+        Helper helper = new Helper(40);
+        helper.next();
+        System.out.println(helper.next());
+        System.out.println(helper.count);
+        System.out.println(Helper.twice(21));
+
+        Counter counter = new Counter();
+        counter.add(5);
+        counter.add(7);
+        System.out.println(counter.total);
+        System.out.println(Counter.square(12));
+
+        try {
+            Counter.check(-1);
+        } catch (IllegalArgumentException e) {
+            System.out.println(e.getMessage());
+            StackTraceElement top = e.getStackTrace()[0];
+            System.out.println(top.getClassName());
+            System.out.println(top.getFileName());
+            System.out.println(top.getLineNumber());
+        }
+
+        Helper.fail("from another class");
+    }
+}
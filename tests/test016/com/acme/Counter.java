@@ -0,0 +1,19 @@
+package com.acme;
+
+public class Counter {
+    public int total;
+
+    public void add(int amount) {
+        total = total + amount;
+    }
+
+    public static long square(long value) {
+        return value * value;
+    }
+
+    public static void check(int value) {
+        if (value < 0) {
+            throw new IllegalArgumentException("negative");
+        }
+    }
+}
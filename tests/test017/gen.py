@@ -0,0 +1,39 @@
+# runs with -jar: a multi-release jar whose manifest names the main class and
+# puts another jar on the class path. entries are a mix of stored and deflated
+import os
+import subprocess
+import tempfile
+import zipfile
+
+def compile_classes(sources, build, class_path=None):
+    command = ['javac', '--release', '9', '-d', build]
+    if class_path:
+        command += ['-cp', class_path]
+    subprocess.check_call(command + sources)
+
+with tempfile.TemporaryDirectory() as build:
+    base = os.path.join(build, 'base')
+    lib = os.path.join(build, 'lib')
+    compile_classes(['lib/Greeter.java'], lib)
+    compile_classes(['src/Main.java', 'src/Version.java'], base, lib)
+    for version in ['9', '11', '21']:
+        compile_classes([f'versions/{version}/Version.java'], os.path.join(build, version))
+
+    os.makedirs('lib', exist_ok=True)
+    with zipfile.ZipFile('lib/util.jar', 'w', zipfile.ZIP_DEFLATED) as jar:
+        jar.write(os.path.join(lib, 'Greeter.class'), 'Greeter.class')
+
+    manifest = ('Manifest-Version: 1.0\r\n'
+                'Main-Class: Main\r\n'
+                'Multi-Release: true\r\n'
+                # long attributes are continued on the next line after a space
+                'Class-Path: lib/missing.jar\r\n'
+                '  lib/util.jar\r\n'
+                '\r\n')
+    with zipfile.ZipFile('app.jar', 'w') as jar:
+        jar.writestr('META-INF/MANIFEST.MF', manifest, zipfile.ZIP_DEFLATED)
+        jar.write(os.path.join(base, 'Main.class'), 'Main.class', zipfile.ZIP_DEFLATED)
+        jar.write(os.path.join(base, 'Version.class'), 'Version.class', zipfile.ZIP_STORED)
+        for version in ['9', '11', '21']:
+            path = os.path.join(build, version, 'Version.class')
+            jar.write(path, f'META-INF/versions/{version}/Version.class', zipfile.ZIP_DEFLATED, compresslevel=1)
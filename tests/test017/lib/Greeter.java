@@ -0,0 +1,15 @@
+public class Greeter {
+    public static String greeting() {
+        return "hello from util.jar";
+    }
+
+    public static int twice(int value) {
+        return value * 2;
+    }
+
+    public static void fail(int code) {
+        if (code > 0) {
+            throw new IllegalStateException("failed in a jar");
+        }
+    }
+}
@@ -0,0 +1,14 @@
+public class Main {
+    public static void main(String[] args) {
+        // from lib/util.jar, which the manifest puts on the class path
+        System.out.println(Greeter.greeting());
+        System.out.println(Greeter.twice(21));
+
+        // the multi-release jar has versions for java 9, 11 and 21
+        System.out.println(Version.feature());
+        System.out.println(Version.name());
+
+        System.out.println(args.length);
+        Greeter.fail(7);
+    }
+}
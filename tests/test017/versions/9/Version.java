@@ -0,0 +1,9 @@
+public class Version {
+    public static int feature() {
+        return 9;
+    }
+
+    public static String name() {
+        return "compiled for java 9";
+    }
+}
@@ -0,0 +1,45 @@
+public class Main {
+    int value;
+
+    Main(int value) {
+        this.value = value;
+    }
+
+    static String label() {
+        return "main label";
+    }
+
+    static int callback(int depth) {
+        if (depth == 0) {
+            return 1;
+        }
+        return 2 * Shapes.bounce(depth - 1);
+    }
+
+    int scaled(int factor) {
+        return value * factor;
+    }
+
+    public static void main(String[] args) {
+        // each class resolves its own constants, even at the same constant pool index
+        System.out.println(label());
+        System.out.println(Shapes.label());
+        System.out.println(Shapes.area(3, 4));
+        System.out.println(1234567890123L);
+        System.out.println(Shapes.huge());
+
+        // calls back and forth between the classes
+        System.out.println(callback(10));
+
+        Main main = new Main(6);
+        Shapes shapes = new Shapes(2.5);
+        System.out.println(shapes.scale(main));
+        System.out.println(shapes.describe());
+
+        try {
+            Shapes.fail();
+        } catch (IllegalStateException e) {
+            System.out.println(e.getMessage());
+        }
+    }
+}
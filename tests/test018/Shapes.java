@@ -0,0 +1,38 @@
+public class Shapes {
+    double size;
+
+    Shapes(double size) {
+        this.size = size;
+    }
+
+    static String label() {
+        return "shapes label";
+    }
+
+    static long huge() {
+        return 9876543210987L;
+    }
+
+    static int area(int width, int height) {
+        return width * height;
+    }
+
+    static int bounce(int depth) {
+        return Main.callback(depth) + 1;
+    }
+
+    int scale(Main main) {
+        return main.scaled(7) + (int) size;
+    }
+
+    String describe() {
+        if (size > 2.0) {
+            return "large";
+        }
+        return "small";
+    }
+
+    static void fail() {
+        throw new IllegalStateException("thrown by " + "Shapes");
+    }
+}
@@ -0,0 +1,142 @@
+public class Main {
+    static int counter;
+    static int[] squares = new int[5];
+
+    static {
+        System.out.println("Main initialized");
+        for (int i = 0; i < squares.length; i++) {
+            squares[i] = i * i;
+        }
+    }
+
+    static int next() {
+        counter++;
+        return counter;
+    }
+
+    public static void main(String[] args) {
+        System.out.println(next());
+        System.out.println(next());
+        System.out.println(squares[4]);
+
+        // classes are initialized on first use, superclasses first
+        System.out.println("before Child");
+        System.out.println(Child.childValue);
+        System.out.println(Child.childValue);
+        new Child();
+
+        // a static field declared by a superclass only initializes that superclass
+        System.out.println(Other.baseValue);
+
+        System.out.println(Constants.NAME);
+        System.out.println(Constants.BIG);
+        System.out.println(Constants.RATIO);
+        System.out.println(Constants.computed);
+
+        Registry.add(3);
+        Registry.add(4);
+        System.out.println(Registry.total);
+        System.out.println(Registry.count());
+
+        try {
+            System.out.println(Broken.value);
+        } catch (ExceptionInInitializerError e) {
+            System.out.println("ExceptionInInitializerError");
+            System.out.println(e.getMessage());
+            System.out.println(e.getCause().getMessage());
+        }
+        try {
+            System.out.println(Broken.value);
+        } catch (NoClassDefFoundError e) {
+            System.out.println(e.getMessage());
+            System.out.println(e.getCause().getMessage());
+        }
+        try {
+            Broken.touch();
+        } catch (NoClassDefFoundError e) {
+            System.out.println(e.getMessage());
+        }
+
+        // errors thrown by an initializer are not wrapped
+        try {
+            new Failing();
+        } catch (InternalError e) {
+            System.out.println(e.getMessage());
+        }
+
+        System.out.println(Fatal.value);
+    }
+}
+
+class Base {
+    static int baseValue = init("Base");
+
+    static int init(String name) {
+        System.out.println(name);
+        return 7;
+    }
+}
+
+class Child extends Base {
+    static int childValue = init("Child") * 6;
+
+    Child() {
+        System.out.println("new Child");
+    }
+}
+
+class Other extends Base {
+    static {
+        System.out.println("Other initialized");
+    }
+}
+
+class Constants {
+    static final String NAME = "constant name";
+    static final long BIG = 1L << 40;
+    static final double RATIO = 0.25;
+    static final int computed = Integer.MAX_VALUE - 1;
+}
+
+class Registry {
+    static int total;
+    static int added;
+
+    static void add(int value) {
+        total += value;
+        added++;
+    }
+
+    static int count() {
+        return added;
+    }
+}
+
+class Broken {
+    static int value = 10 / zero();
+
+    static int zero() {
+        return 0;
+    }
+
+    static void touch() {
+    }
+}
+
+class Failing {
+    static {
+        if (Registry.total > 0) {
+            throw new InternalError("failing initializer");
+        }
+    }
+}
+
+class Fatal {
+    static int value;
+
+    static {
+        if (Registry.total > 0) {
+            throw new IllegalStateException("fatal initializer");
+        }
+    }
+}
@@ -0,0 +1,179 @@
+abstract class Animal {
+    int legs;
+    String name = "animal";
+
+    Animal(int legs) {
+        this.legs = legs;
+    }
+
+    abstract String sound();
+
+    String describe() {
+        return sound();
+    }
+
+    int legs() {
+        return legs;
+    }
+
+    private String secret() {
+        return "animal secret";
+    }
+
+    String revealSecret() {
+        return secret();
+    }
+
+    String name() {
+        return name;
+    }
+}
+
+class Dog extends Animal {
+    String name = "dog";
+
+    Dog() {
+        super(4);
+    }
+
+    String sound() {
+        return "woof";
+    }
+
+    String describe() {
+        return "dog";
+    }
+
+    // not an override of Animal.secret, which is private
+    String secret() {
+        return "dog secret";
+    }
+
+    String superDescribe() {
+        return super.describe();
+    }
+
+    String names() {
+        return super.name;
+    }
+
+    String name() {
+        return name;
+    }
+}
+
+class Puppy extends Dog {
+    String sound() {
+        return "yip";
+    }
+
+    int legs() {
+        return super.legs() - 4 + legs;
+    }
+}
+
+class Bird extends Animal {
+    Bird() {
+        super(2);
+    }
+
+    String sound() {
+        return "tweet";
+    }
+}
+
+class Counter {
+    int count;
+
+    void add(int amount) {
+        count += amount;
+    }
+
+    void add(int amount, int times) {
+        for (int i = 0; i < times; i++) {
+            add(amount);
+        }
+    }
+
+    void add(long amount) {
+        count += (int) amount * 100;
+    }
+}
+
+class CountingCounter extends Counter {
+    int calls;
+
+    void add(int amount) {
+        calls++;
+        super.add(amount);
+    }
+}
+
+class AppException extends RuntimeException {
+    int code;
+
+    AppException(String message, int code) {
+        super(message);
+        this.code = code;
+    }
+}
+
+abstract class Shape {
+    abstract int area();
+}
+
+class Square extends Shape {
+    int side;
+
+    Square(int side) {
+        this.side = side;
+    }
+
+    int area() {
+        return side * side;
+    }
+}
+
+public class Main {
+    static void show(Animal animal) {
+        System.out.println(animal.sound());
+        System.out.println(animal.legs());
+        System.out.println(animal.revealSecret());
+        System.out.println(animal.name());
+        System.out.println(animal.name);
+    }
+
+    public static void main(String[] args) {
+        show(new Dog());
+        show(new Puppy());
+        show(new Bird());
+
+        Dog dog = new Puppy();
+        System.out.println(dog.superDescribe());
+        System.out.println(dog.secret());
+        System.out.println(dog.names());
+        System.out.println(dog.name);
+
+        Counter counter = new CountingCounter();
+        counter.add(2, 3);
+        counter.add(1L);
+        System.out.println(counter.count);
+        System.out.println(((CountingCounter) counter).calls);
+
+        Shape[] shapes = { new Square(2), new Square(5) };
+        int total = 0;
+        for (Shape shape : shapes) {
+            total += shape.area();
+        }
+        System.out.println(total);
+
+        try {
+            throw new AppException("failed", 42);
+        } catch (AppException e) {
+            System.out.println(e.getMessage());
+            System.out.println(e.code);
+        }
+
+        throw new AppException("uncaught", 7);
+    }
+}
@@ -0,0 +1,132 @@
+interface Shape {
+    int SIDES_UNKNOWN = -1;
+
+    int area();
+
+    default int sides() {
+        return SIDES_UNKNOWN;
+    }
+
+    default String describe() {
+        return kind();
+    }
+
+    private String kind() {
+        return "shape";
+    }
+
+    static Shape unit() {
+        return new Square(1);
+    }
+}
+
+interface Polygon extends Shape {
+    default int sides() {
+        return 3;
+    }
+}
+
+interface Named {
+    String name();
+
+    default String describe() {
+        return "named";
+    }
+}
+
+class Square implements Polygon {
+    int side;
+
+    Square(int side) {
+        this.side = side;
+    }
+
+    public int area() {
+        return side * side;
+    }
+
+    public int sides() {
+        return 4;
+    }
+}
+
+class Triangle implements Polygon, Shape {
+    public int area() {
+        return 6;
+    }
+}
+
+// both Shape and Named have describe(), so the class has to pick
+class Label implements Shape, Named {
+    public int area() {
+        return 0;
+    }
+
+    public String name() {
+        return "label";
+    }
+
+    public String describe() {
+        return Named.super.describe();
+    }
+}
+
+abstract class Base implements Named {
+    public String name() {
+        return "base";
+    }
+}
+
+class Derived extends Base implements Shape {
+    public int area() {
+        return 10;
+    }
+
+    public String describe() {
+        return Shape.super.describe();
+    }
+}
+
+interface Counter {
+    int next();
+}
+
+class Sequence implements Counter {
+    int value;
+
+    public int next() {
+        return ++value;
+    }
+}
+
+public class Main {
+    static int total(Shape[] shapes) {
+        int total = 0;
+        for (Shape shape : shapes) {
+            total += shape.area();
+        }
+        return total;
+    }
+
+    public static void main(String[] args) {
+        Shape[] shapes = { new Square(3), new Triangle(), Shape.unit(), new Label(), new Derived() };
+        System.out.println(total(shapes));
+        for (Shape shape : shapes) {
+            System.out.println(shape.sides());
+            System.out.println(shape.describe());
+        }
+
+        Named named = new Derived();
+        System.out.println(named.name());
+        System.out.println(named.describe());
+
+        Counter counter = new Sequence();
+        counter.next();
+        counter.next();
+        System.out.println(counter.next());
+        System.out.println(Shape.SIDES_UNKNOWN);
+
+        Named nothing = null;
+        System.out.println(nothing.name());
+    }
+}
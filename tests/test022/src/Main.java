@@ -0,0 +1,26 @@
+public class Main {
+    public static void main(String[] args) {
+        try {
+            System.out.println(new Both().side());
+        } catch (IncompatibleClassChangeError e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            System.out.println(Tool.use());
+        } catch (IncompatibleClassChangeError e) {
+            System.out.println(e.getMessage());
+        }
+
+        Task partial = Helper.partial();
+        System.out.println(partial.run());
+        try {
+            System.out.println(partial.undo());
+        } catch (AbstractMethodError e) {
+            System.out.println(e.getMessage());
+        }
+
+        Task job = Helper.job();
+        System.out.println(job.run());
+    }
+}
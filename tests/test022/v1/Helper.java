@@ -0,0 +1,9 @@
+public class Helper {
+    static Task partial() {
+        return new Partial();
+    }
+
+    static Task job() {
+        return new Job();
+    }
+}
@@ -0,0 +1,5 @@
+public class Job implements Task {
+    public String run() {
+        return "job ran";
+    }
+}
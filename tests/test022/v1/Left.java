@@ -0,0 +1,5 @@
+public interface Left {
+    default String side() {
+        return "left";
+    }
+}
@@ -0,0 +1,5 @@
+public class Partial implements Task {
+    public String run() {
+        return "partial ran";
+    }
+}
@@ -0,0 +1,3 @@
+public interface Task {
+    String run();
+}
@@ -0,0 +1,5 @@
+public class Tool {
+    public static String use() {
+        return "tool used";
+    }
+}
@@ -0,0 +1,5 @@
+public class Job {
+    public String run() {
+        return "job ran";
+    }
+}
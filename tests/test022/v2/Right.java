@@ -0,0 +1,5 @@
+public interface Right {
+    default String side() {
+        return "right";
+    }
+}
@@ -0,0 +1,5 @@
+public interface Task {
+    String run();
+
+    String undo();
+}
@@ -0,0 +1,5 @@
+public interface Tool {
+    static String use() {
+        return "tool used";
+    }
+}
@@ -0,0 +1,150 @@
+import java.io.Serializable;
+
+interface IntOp {
+    int apply(int a, int b);
+}
+
+interface Transformer {
+    String transform(String s);
+}
+
+interface Maker {
+    Point make(int x, int y);
+}
+
+interface LongFunction {
+    long compute(int x);
+}
+
+interface Visitor<T> {
+    T visit(T value);
+}
+
+interface Check {
+    boolean test(int value);
+
+    default Check negate() {
+        return value -> !test(value);
+    }
+}
+
+interface Marker {
+}
+
+class Point {
+    int x;
+    int y;
+
+    Point(int x, int y) {
+        this.x = x;
+        this.y = y;
+    }
+
+    int sum() {
+        return x + y;
+    }
+}
+
+public class Main {
+    int base;
+
+    Main(int base) {
+        this.base = base;
+    }
+
+    static int multiply(int a, int b) {
+        return a * b;
+    }
+
+    int addBase(int a, int b) {
+        return base + a + b;
+    }
+
+    static String pick(String s) {
+        if (s == null) {
+            return "none";
+        }
+        return s;
+    }
+
+    static void run(Runnable runnable) {
+        runnable.run();
+    }
+
+    static int fail(int value) {
+        if (value < 0) {
+            throw new IllegalArgumentException("negative");
+        }
+        return value;
+    }
+
+    public static void main(String[] args) {
+        IntOp add = (a, b) -> a + b;
+        System.out.println(add.apply(3, 4));
+
+        int offset = 100;
+        IntOp addOffset = (a, b) -> a + b + offset;
+        System.out.println(addOffset.apply(1, 2));
+
+        IntOp mul = Main::multiply;
+        System.out.println(mul.apply(6, 7));
+
+        Main main = new Main(1000);
+        IntOp bound = main::addBase;
+        System.out.println(bound.apply(20, 3));
+
+        Transformer picker = Main::pick;
+        System.out.println(picker.transform("hello"));
+        System.out.println(picker.transform(null));
+
+        Maker maker = Point::new;
+        Point point = maker.make(5, 8);
+        System.out.println(point.sum());
+
+        LongFunction square = x -> (long) x * x;
+        System.out.println(square.compute(100000));
+
+        Visitor<Point> shift = p -> new Point(p.y, p.x + 1);
+        System.out.println(shift.visit(point).x);
+        System.out.println(shift.visit(shift.visit(point)).sum());
+
+        Check even = value -> value % 2 == 0;
+        Check odd = even.negate();
+        if (even.test(4) && !odd.test(4) && odd.test(7)) {
+            System.out.println("even and odd");
+        }
+
+        for (int i = 0; i < 3; i++) {
+            IntOp op = (a, b) -> a - b;
+            System.out.println(op.apply(10, i));
+        }
+
+        String message = "from a runnable";
+        run(() -> System.out.println(message));
+
+        Runnable serializable = (Runnable & Serializable) () -> System.out.println("serializable");
+        serializable.run();
+        if (serializable instanceof Serializable) {
+            System.out.println("is serializable");
+        }
+        Runnable marked = (Runnable & Marker) () -> System.out.println("marked");
+        marked.run();
+        if (marked instanceof Marker && !(marked instanceof Serializable)) {
+            System.out.println("is marked");
+        }
+        if (!(add instanceof Serializable)) {
+            System.out.println("is not serializable");
+        }
+
+        IntOp failing = (a, b) -> fail(a - b);
+        System.out.println(failing.apply(5, 2));
+        try {
+            failing.apply(2, 5);
+        } catch (IllegalArgumentException e) {
+            System.out.println(e.getMessage());
+        }
+
+        IntOp unchecked = (a, b) -> a / b;
+        System.out.println(unchecked.apply(1, 0));
+    }
+}
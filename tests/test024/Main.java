@@ -0,0 +1,87 @@
+class Temperature {
+    double degrees;
+
+    Temperature(double degrees) {
+        this.degrees = degrees;
+    }
+
+    public String toString() {
+        return degrees + " degrees";
+    }
+}
+
+class Pair {
+    Object first;
+    Object second;
+
+    Pair(Object first, Object second) {
+        this.first = first;
+        this.second = second;
+    }
+
+    public String toString() {
+        return "(" + first + ", " + second + ")";
+    }
+}
+
+public class Main {
+    static final String PREFIX = "prefix";
+
+    static String describe(int count) {
+        return "count=" + count;
+    }
+
+    public static void main(String[] args) {
+        int i = 42;
+        long l = 1234567890123L;
+        char c = 'x';
+        boolean yes = true;
+        boolean no = false;
+        byte b = -7;
+        short s = 300;
+        float f = 1.5f;
+        double d = 0.1 + 0.2;
+        String text = "text";
+        String nothing = null;
+        Object object = null;
+
+        System.out.println("i=" + i);
+        System.out.println("l=" + l + ", c=" + c);
+        System.out.println("yes=" + yes + " no=" + no);
+        System.out.println("b=" + b + " s=" + s);
+        System.out.println("f=" + f + " d=" + d);
+        System.out.println(text + nothing + object);
+        System.out.println(i + l + "|" + i + l);
+        System.out.println(c + "" + (char) (c + 1));
+        System.out.println("" + Float.NaN + " " + (1.0 / 0) + " " + (-1.0f / 0) + " " + 1e10 + " " + 100.0f);
+        System.out.println(PREFIX + ":" + describe(3));
+        System.out.println("tag\u0001" + i + "\u0002end");
+
+        Temperature warm = new Temperature(21.5);
+        System.out.println("it is " + warm);
+        Pair pair = new Pair(warm, new Pair(null, "inner"));
+        System.out.println("pair " + pair);
+
+        String joined = "";
+        for (int n = 0; n < 5; n++) {
+            joined = joined + n + ",";
+        }
+        System.out.println(joined);
+
+        char[] letters = {'a', 'b', 'c'};
+        String word = "";
+        for (char letter : letters) {
+            word += letter;
+        }
+        System.out.println(word);
+
+        try {
+            throw new IllegalStateException("bad state " + i);
+        } catch (IllegalStateException e) {
+            System.out.println("caught " + e.getMessage() + " / " + e);
+        }
+
+        System.out.println(1 + 2 + "3" + 4 + 5);
+        throw new RuntimeException("failed with " + l + " and " + warm);
+    }
+}
@@ -0,0 +1,17 @@
+# switches on patterns are a preview feature of java 17, so javac marks the
+# class files as needing --enable-preview. the bytecode only relies on the
+# SwitchBootstraps api, so the mark is removed to run them like any others
+import glob
+import os
+import subprocess
+import tempfile
+
+with tempfile.TemporaryDirectory() as build:
+    subprocess.check_call(['javac', '--enable-preview', '--release', '17', '-XDsuppressNotes', '-d', build] + glob.glob('src/*.java'))
+    for class_file in glob.glob(os.path.join(build, '*.class')):
+        with open(class_file, 'rb') as f:
+            contents = bytearray(f.read())
+        # the minor version follows the magic number
+        contents[4:6] = b'\x00\x00'
+        with open(os.path.basename(class_file), 'wb') as f:
+            f.write(contents)
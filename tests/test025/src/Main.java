@@ -0,0 +1,110 @@
+sealed interface Shape permits Circle, Square, Rect {}
+
+record Circle(int radius) implements Shape {}
+
+record Square(int side) implements Shape {}
+
+record Rect(int width, int height) implements Shape {}
+
+record Labeled(String label, Shape shape, boolean visible, char mark, double weight, long id) {}
+
+record Empty() {}
+
+enum Color { RED, GREEN, BLUE, BLACK }
+
+public class Main {
+    static String describe(Object o) {
+        return switch (o) {
+            case null -> "null";
+            case Circle c && c.radius() > 10 -> "big circle " + c.radius();
+            case Circle c -> "circle " + c.radius();
+            case Rect r && r.width() == r.height() -> "square rect";
+            case Shape s -> "shape " + s;
+            case String s -> "string " + s;
+            case Color c -> "color " + c;
+            default -> "other " + o;
+        };
+    }
+
+    static int area(Shape shape) {
+        return switch (shape) {
+            case Circle c -> 3 * c.radius() * c.radius();
+            case Square s -> s.side() * s.side();
+            case Rect r -> r.width() * r.height();
+        };
+    }
+
+    static String color(Color color) {
+        return switch (color) {
+            case RED -> "red";
+            case GREEN -> "green";
+            case Color c && c.ordinal() > 2 -> "dark " + c.name();
+            case Color c -> "other " + c;
+        };
+    }
+
+    static String word(String s) {
+        return switch (s) {
+            case "one" -> "1";
+            case "two" -> "2";
+            case String other -> "? " + other;
+        };
+    }
+
+    static boolean checked(int value) {
+        if (value < 0) {
+            throw new IllegalArgumentException("negative " + value);
+        }
+        return value > 5;
+    }
+
+    static String guarded(Object o) {
+        switch (o) {
+            case Square s && checked(s.side()):
+                return "large square";
+            case Square s:
+                return "small square";
+            default:
+                return "not a square";
+        }
+    }
+
+    public static void main(String[] args) {
+        System.out.println(describe(null));
+        System.out.println(describe(new Circle(20)));
+        System.out.println(describe(new Circle(2)));
+        System.out.println(describe(new Rect(4, 4)));
+        System.out.println(describe(new Rect(4, 5)));
+        System.out.println(describe(new Square(3)));
+        System.out.println(describe("text"));
+        System.out.println(describe(Color.GREEN));
+        System.out.println(describe(new Empty()));
+
+        Shape[] shapes = {new Circle(2), new Square(3), new Rect(2, 5)};
+        for (Shape shape : shapes) {
+            System.out.println(shape + " has area " + area(shape));
+        }
+
+        System.out.println(color(Color.RED) + " " + color(Color.GREEN) + " " + color(Color.BLUE) + " " + color(Color.BLACK));
+        System.out.println(word("one") + " " + word("two") + " " + word("three"));
+        System.out.println(guarded(new Square(10)) + ", " + guarded(new Square(1)) + ", " + guarded("x"));
+
+        Labeled labeled = new Labeled("label", new Rect(1, 2), true, 'm', 2.5, 99L);
+        Labeled same = new Labeled("label", new Rect(1, 2), true, 'm', 2.5, 99L);
+        Labeled other = new Labeled("label", new Rect(1, 3), true, 'm', 2.5, 99L);
+        System.out.println("" + labeled);
+        System.out.println(labeled.equals(same) + " " + labeled.equals(other) + " " + labeled.equals(null) + " " + labeled.equals("label"));
+        System.out.println((labeled.hashCode() == same.hashCode()) + " " + new Rect(1, 2).hashCode() + " " + new Square(7).hashCode());
+        System.out.println(new Empty() + " " + new Empty().hashCode() + " " + new Empty().equals(new Empty()));
+        System.out.println("" + new Labeled(null, null, false, 'a', Double.NaN, -1L).equals(new Labeled(null, null, false, 'a', Double.NaN, -1L)));
+        System.out.println("" + new Labeled("a", null, false, 'a', 0.0, 1L).equals(new Labeled("a", null, false, 'a', -0.0, 1L)));
+
+        try {
+            area(null);
+        } catch (NullPointerException e) {
+            System.out.println("null shape");
+        }
+
+        System.out.println(guarded(new Square(-1)));
+    }
+}
@@ -0,0 +1,68 @@
+# dynamically computed constants, which javac does not emit: Constants has a
+# method loading each constant, and Main, compiled against it, calls them
+import glob
+import subprocess
+
+from classgen import ClassFile, Code
+
+ACC_PUBLIC = 0x1
+ACC_STATIC = 0x8
+REF_INVOKE_STATIC = 6
+
+LOOKUP = 'Ljava/lang/invoke/MethodHandles$Lookup;'
+CONSTANT_BOOTSTRAPS = 'java/lang/invoke/ConstantBootstraps'
+
+constants = ClassFile('Constants', major=55)
+
+def bootstrap(owner, name, descriptor, arguments=()):
+    handle = constants.method_handle(REF_INVOKE_STATIC, constants.methodref(owner, name, descriptor))
+    return constants.bootstrap_method(handle, arguments)
+
+def constant_method(name, constant, descriptor, load='ldc_w', ret='areturn', stack=1):
+    code = Code()
+    code.op(load, constant)
+    code.op(ret)
+    constants.add_method(ACC_PUBLIC | ACC_STATIC, name, '()' + descriptor, code, stack, 0)
+
+make = bootstrap('Bootstraps', 'make', '(' + LOOKUP + 'Ljava/lang/String;Ljava/lang/Class;)Ljava/lang/Object;')
+constant_method('greeting', constants.dynamic(make, 'greeting', 'Ljava/lang/String;'), 'Ljava/lang/String;')
+
+# the same bootstrap method for another constant is invoked again
+constant_method('farewell', constants.dynamic(make, 'farewell', 'Ljava/lang/String;'), 'Ljava/lang/String;')
+
+get_static_final = '(' + LOOKUP + 'Ljava/lang/String;Ljava/lang/Class;Ljava/lang/Class;)Ljava/lang/Object;'
+answer = bootstrap(CONSTANT_BOOTSTRAPS, 'getStaticFinal', get_static_final, [constants.klass('Bootstraps')])
+constant_method('answer', constants.dynamic(answer, 'ANSWER', 'I'), 'I', ret='ireturn')
+constant_method('big', constants.dynamic(answer, 'BIG', 'J'), 'J', load='ldc2_w', ret='lreturn', stack=2)
+constant_method('motto', constants.dynamic(answer, 'MOTTO', 'Ljava/lang/String;'), 'Ljava/lang/String;')
+
+null_constant = bootstrap(CONSTANT_BOOTSTRAPS, 'nullConstant', '(' + LOOKUP + 'Ljava/lang/String;Ljava/lang/Class;)Ljava/lang/Object;')
+constant_method('nothing', constants.dynamic(null_constant, '_', 'Ljava/lang/String;'), 'Ljava/lang/String;')
+
+enum_constant = bootstrap(CONSTANT_BOOTSTRAPS, 'enumConstant', '(' + LOOKUP + 'Ljava/lang/String;Ljava/lang/Class;)Ljava/lang/Enum;')
+constant_method('color', constants.dynamic(enum_constant, 'GREEN', 'LColor;'), 'LColor;')
+
+twice = constants.method_handle(REF_INVOKE_STATIC, constants.methodref('Bootstraps', 'twice', '(Ljava/lang/String;)Ljava/lang/String;'))
+invoke = bootstrap(CONSTANT_BOOTSTRAPS, 'invoke', '(' + LOOKUP + 'Ljava/lang/String;Ljava/lang/Class;Ljava/lang/invoke/MethodHandle;[Ljava/lang/Object;)Ljava/lang/Object;',
+                   [twice, constants.string('ab')])
+constant_method('twice', constants.dynamic(invoke, '_', 'Ljava/lang/String;'), 'Ljava/lang/String;')
+
+# a constant that is a static argument of its own bootstrap method
+arguments = '(' + LOOKUP + 'Ljava/lang/String;Ljava/lang/Class;Ljava/lang/Object;)Ljava/lang/Object;'
+cycle = constants.reserve_dynamic('cycle', 'Ljava/lang/Object;')
+constants.set_dynamic(cycle, bootstrap('Bootstraps', 'withArgument', arguments, [cycle]))
+constant_method('cycle', cycle, 'Ljava/lang/Object;')
+
+# a constant using another as a static argument
+inner = constants.dynamic(make, 'inner', 'Ljava/lang/String;')
+constant_method('outer', constants.dynamic(bootstrap('Bootstraps', 'withArgument', arguments, [inner]), 'outer', 'Ljava/lang/Object;'), 'Ljava/lang/Object;')
+
+fail = bootstrap('Bootstraps', 'fail', '(' + LOOKUP + 'Ljava/lang/String;Ljava/lang/Class;)Ljava/lang/Object;')
+constant_method('failing', constants.dynamic(fail, 'failing', 'Ljava/lang/String;'), 'Ljava/lang/String;')
+
+broken = bootstrap('Bootstraps', 'broken', '(' + LOOKUP + 'Ljava/lang/String;Ljava/lang/Class;)Ljava/lang/Object;')
+constant_method('broken', constants.dynamic(broken, 'broken', 'Ljava/lang/String;'), 'Ljava/lang/String;')
+
+constants.write('Constants.class')
+
+subprocess.check_call(['javac', '-cp', '.', '-d', '.'] + glob.glob('src/*.java'))
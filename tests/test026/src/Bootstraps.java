@@ -0,0 +1,40 @@
+import java.lang.invoke.MethodHandles;
+
+enum Color { RED, GREEN, BLUE }
+
+class Broken extends Error {
+    Broken(String message) {
+        super(message);
+    }
+}
+
+public class Bootstraps {
+    static final int ANSWER = 42;
+    static final long BIG = 1L << 40;
+    static final String MOTTO = "constant";
+    static int calls;
+
+    public static Object make(MethodHandles.Lookup lookup, String name, Class<?> type) {
+        calls++;
+        return "made " + name;
+    }
+
+    public static Object withArgument(MethodHandles.Lookup lookup, String name, Class<?> type, Object argument) {
+        calls++;
+        return name + " of " + argument;
+    }
+
+    public static String twice(String s) {
+        return s + s;
+    }
+
+    public static Object fail(MethodHandles.Lookup lookup, String name, Class<?> type) {
+        calls++;
+        throw new IllegalStateException("cannot make " + name);
+    }
+
+    public static Object broken(MethodHandles.Lookup lookup, String name, Class<?> type) {
+        calls++;
+        throw new Broken("broken " + name);
+    }
+}
@@ -0,0 +1,51 @@
+public class Main {
+    public static void main(String[] args) {
+        System.out.println(Constants.greeting());
+        System.out.println(Constants.greeting());
+        System.out.println(Constants.farewell());
+        System.out.println("calls " + Bootstraps.calls);
+        System.out.println("same " + (Constants.greeting() == Constants.greeting()));
+
+        System.out.println(Constants.answer() + 1);
+        System.out.println(Constants.big());
+        System.out.println(Constants.motto());
+        System.out.println(Constants.nothing());
+        System.out.println("" + Constants.color() + " " + Constants.color().ordinal());
+        System.out.println("green " + (Constants.color() == Color.GREEN));
+        System.out.println(Constants.twice());
+        System.out.println("" + Constants.outer());
+        System.out.println("calls " + Bootstraps.calls);
+
+        BootstrapMethodError first = null;
+        for (int i = 0; i < 2; i++) {
+            try {
+                Constants.failing();
+            } catch (BootstrapMethodError e) {
+                System.out.println(e.getMessage() + ": " + e.getCause());
+                if (first == null) {
+                    first = e;
+                } else {
+                    System.out.println("same error " + (first == e));
+                }
+            }
+        }
+
+        System.out.println("calls " + Bootstraps.calls);
+        for (int i = 0; i < 2; i++) {
+            try {
+                Constants.broken();
+            } catch (Broken e) {
+                System.out.println("caught " + e.getMessage());
+            }
+        }
+        System.out.println("calls " + Bootstraps.calls);
+
+        try {
+            Constants.cycle();
+        } catch (Throwable e) {
+            System.out.println(e + ", cause " + e.getCause());
+        }
+        System.out.println("calls " + Bootstraps.calls);
+        Constants.failing();
+    }
+}
@@ -0,0 +1,31 @@
+# method handle and method type constants, which javac only emits as bootstrap
+# arguments: Handles has a method loading each with ldc, and Main, compiled
+# against it, invokes them
+import glob
+import subprocess
+
+from classgen import ClassFile, Code
+
+ACC_PUBLIC = 0x1
+ACC_STATIC = 0x8
+REF_GET_FIELD = 1
+REF_INVOKE_STATIC = 6
+REF_NEW_INVOKE_SPECIAL = 8
+
+handles = ClassFile('Handles', major=55)
+
+def constant_method(name, constant, descriptor):
+    code = Code()
+    code.op('ldc_w', constant)
+    code.op('areturn')
+    handles.add_method(ACC_PUBLIC | ACC_STATIC, name, '()' + descriptor, code, 1, 0)
+
+METHOD_HANDLE = 'Ljava/lang/invoke/MethodHandle;'
+constant_method('adder', handles.method_handle(REF_INVOKE_STATIC, handles.methodref('Main', 'add', '(II)I')), METHOD_HANDLE)
+constant_method('base', handles.method_handle(REF_GET_FIELD, handles.fieldref('Main', 'base', 'I')), METHOD_HANDLE)
+constant_method('constructor', handles.method_handle(REF_NEW_INVOKE_SPECIAL, handles.methodref('Main', '<init>', '(I)V')), METHOD_HANDLE)
+constant_method('adderType', handles.method_type('(II)I'), 'Ljava/lang/invoke/MethodType;')
+
+handles.write('Handles.class')
+
+subprocess.check_call(['javac', '-cp', '.', '-d', '.'] + glob.glob('src/*.java'))
@@ -0,0 +1,160 @@
+import java.lang.invoke.MethodHandle;
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.MethodType;
+import java.lang.invoke.WrongMethodTypeException;
+
+public class Main {
+    static int counter;
+    int base = 5;
+
+    Main() {
+    }
+
+    Main(int base) {
+        this.base = base;
+    }
+
+    static int add(int a, int b) {
+        return a + b;
+    }
+
+    static long widen(long a, double b) {
+        return a + (long) b;
+    }
+
+    static String greet(String greeting, String name) {
+        return greeting + ", " + name;
+    }
+
+    static void count() {
+        counter++;
+    }
+
+    int plus(int a) {
+        return base + a;
+    }
+
+    String describe() {
+        return "Main " + base;
+    }
+
+    public static void main(String[] args) throws Throwable {
+        MethodHandles.Lookup lookup = MethodHandles.lookup();
+        System.out.println("" + lookup);
+
+        MethodHandle add = lookup.findStatic(Main.class, "add", MethodType.methodType(int.class, int.class, int.class));
+        System.out.println("" + add + " " + add.type());
+        System.out.println((int) add.invokeExact(1, 2));
+        System.out.println((long) add.invoke((short) 3, 4));
+        try {
+            long wrong = (long) add.invokeExact(1, 2);
+        } catch (WrongMethodTypeException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            int wrong = (int) add.invoke("a", 2);
+        } catch (WrongMethodTypeException e) {
+            System.out.println(e.getMessage());
+        }
+
+        MethodHandle widen = lookup.findStatic(Main.class, "widen", MethodType.methodType(long.class, long.class, double.class));
+        System.out.println((long) widen.invoke(1, 2));
+
+        MethodType type = MethodType.methodType(String[].class, int[].class, Main.class, String.class);
+        System.out.println("" + type + " " + type.parameterCount() + " " + type.toMethodDescriptorString());
+        System.out.println("" + type.changeReturnType(void.class) + " " + type.dropParameterTypes(0, 2) + " " + type.appendParameterTypes(long.class));
+        System.out.println("" + type.insertParameterTypes(1, char.class, boolean.class) + " " + type.changeParameterType(2, Object.class));
+        System.out.println("" + MethodType.methodType(int.class, type) + " " + (type.parameterType(1) == Main.class) + " " + (type.returnType() == String[].class));
+
+        MethodHandle plus = lookup.findVirtual(Main.class, "plus", MethodType.methodType(int.class, int.class));
+        Main main = new Main(10);
+        System.out.println("" + plus + " " + (int) plus.invokeExact(main, 1));
+        MethodHandle bound = plus.bindTo(main);
+        System.out.println("" + bound + " " + (int) bound.invokeExact(2));
+        MethodHandle describe = lookup.bind(new Main(7), "describe", MethodType.methodType(String.class));
+        System.out.println((String) describe.invokeExact());
+
+        MethodHandle greet = lookup.findStatic(Main.class, "greet", MethodType.methodType(String.class, String.class, String.class));
+        MethodHandle hello = MethodHandles.insertArguments(greet, 0, "hello");
+        System.out.println("" + hello + " " + (String) hello.invokeExact("world"));
+        MethodHandle toBob = MethodHandles.insertArguments(greet, 1, "bob");
+        System.out.println((String) toBob.invokeExact("hi"));
+        System.out.println((String) greet.invokeWithArguments("hey", "you"));
+
+        MethodHandle dropped = MethodHandles.dropArguments(add, 1, String.class, long.class);
+        System.out.println("" + dropped + " " + (int) dropped.invokeExact(1, "ignored", 5L, 2));
+
+        MethodHandle asLong = add.asType(MethodType.methodType(long.class, byte.class, short.class));
+        System.out.println("" + asLong + " " + (long) asLong.invokeExact((byte) 20, (short) 22));
+        MethodHandle discard = add.asType(MethodType.methodType(void.class, int.class, int.class));
+        discard.invokeExact(1, 2);
+        MethodHandle objects = greet.asType(MethodType.methodType(Object.class, Object.class, Object.class));
+        System.out.println("" + objects + " " + objects.invoke("bye", "now"));
+
+        MethodHandle count = lookup.findStatic(Main.class, "count", MethodType.methodType(void.class));
+        count.invokeExact();
+        count.invoke();
+        System.out.println("counter " + counter);
+
+        MethodHandle make = lookup.findConstructor(Main.class, MethodType.methodType(void.class, int.class));
+        Main made = (Main) make.invokeExact(3);
+        System.out.println("" + make + " " + made.base);
+        MethodHandle getter = lookup.findGetter(Main.class, "base", int.class);
+        MethodHandle setter = lookup.findSetter(Main.class, "base", int.class);
+        setter.invokeExact(made, 42);
+        System.out.println("" + getter + " " + setter + " " + (int) getter.invokeExact(made));
+        MethodHandle staticSetter = lookup.findStaticSetter(Main.class, "counter", int.class);
+        staticSetter.invokeExact(100);
+        System.out.println((int) lookup.findStaticGetter(Main.class, "counter", int.class).invokeExact());
+
+        try {
+            lookup.findStatic(Main.class, "missing", MethodType.methodType(int.class));
+        } catch (NoSuchMethodException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            lookup.findVirtual(Main.class, "add", MethodType.methodType(int.class, int.class, int.class));
+        } catch (IllegalAccessException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            lookup.findStatic(Main.class, "plus", MethodType.methodType(int.class, int.class));
+        } catch (IllegalAccessException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            lookup.findGetter(Main.class, "nope", int.class);
+        } catch (NoSuchFieldException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            lookup.findConstructor(Main.class, MethodType.methodType(void.class, String.class));
+        } catch (NoSuchMethodException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            add.asType(MethodType.methodType(int.class, int.class));
+        } catch (WrongMethodTypeException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            add.bindTo(null);
+        } catch (IllegalArgumentException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            add.invokeWithArguments("one");
+        } catch (WrongMethodTypeException e) {
+            System.out.println(e.getMessage());
+        }
+
+        MethodHandle constant = Handles.adder();
+        System.out.println("" + constant + " " + (int) constant.invokeExact(20, 22) + " " + Handles.adderType());
+        System.out.println("" + Handles.adderType().equals(add.type()) + " " + (int) Handles.base().invokeExact(made));
+        Main constructed = (Main) Handles.constructor().invokeExact(9);
+        System.out.println("" + Handles.constructor() + " " + constructed.base);
+
+        MethodHandle missing = null;
+        missing.invokeExact();
+    }
+}
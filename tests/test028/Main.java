@@ -0,0 +1,135 @@
+public class Main {
+    static final String CONSTANT = "constant";
+
+    static String describe(Object value) {
+        if (value instanceof String) {
+            return "string " + value;
+        }
+        return "other";
+    }
+
+    static String join(String[] parts) {
+        String result = "[";
+        for (int i = 0; i < parts.length; i++) {
+            result += (i == 0 ? "" : ", ") + parts[i];
+        }
+        return result + "]";
+    }
+
+    static int kind(String value) {
+        switch (value) {
+            case "one": return 1;
+            case "two": return 2;
+            default: return 0;
+        }
+    }
+
+    public static void main(String[] args) {
+        // identity
+        String a = "hello";
+        String b = "hello";
+        String c = new String("hello");
+        System.out.println(a == b);
+        System.out.println(a == c);
+        System.out.println(a.equals(c));
+        System.out.println(a == c.intern());
+        System.out.println(CONSTANT == "constant");
+        String built = "hel" + args.length;
+        System.out.println(built == "hel0");
+        System.out.println(built.intern() == "hel0");
+
+        // queries
+        System.out.println(a.length());
+        System.out.println("".isEmpty());
+        System.out.println(a.charAt(1));
+        System.out.println(a.hashCode());
+        System.out.println("".hashCode());
+        System.out.println(a.indexOf('l'));
+        System.out.println(a.indexOf('l', 3));
+        System.out.println(a.indexOf("lo"));
+        System.out.println(a.indexOf("x"));
+        System.out.println(a.indexOf("", 99));
+        System.out.println(a.lastIndexOf('l'));
+        System.out.println(a.lastIndexOf("l", 2));
+        System.out.println(a.contains("ell"));
+        System.out.println(a.startsWith("he"));
+        System.out.println(a.startsWith("llo", 2));
+        System.out.println(a.endsWith("lo"));
+        System.out.println(a.compareTo("help"));
+        System.out.println(a.compareTo("hell"));
+        System.out.println("HeLLo".equalsIgnoreCase(a));
+        System.out.println(a.equals(null));
+        System.out.println(a.equals(new Object()));
+
+        // new strings
+        System.out.println(a.substring(1));
+        System.out.println(a.substring(1, 3));
+        System.out.println(a.substring(0) == a);
+        System.out.println(a.subSequence(2, 4));
+        System.out.println(a.concat(" world"));
+        System.out.println(a.concat("") == a);
+        System.out.println(a.replace('l', 'L'));
+        System.out.println(a.replace("ll", "[]"));
+        System.out.println("abc".replace("", "-"));
+        System.out.println(a.replace('z', 'y') == a);
+        System.out.println("[" + "  padded \t".trim() + "]");
+        System.out.println(a.trim() == a);
+        System.out.println(a.toUpperCase());
+        System.out.println("MiXeD".toLowerCase());
+        System.out.println(a.toLowerCase() == a);
+        System.out.println("ab".repeat(3));
+        System.out.println(join("a,b,,c,,".split(",")));
+        System.out.println(join("a,b,,c,,".split(",", -1)));
+        System.out.println(join("a,b,,c,,".split(",", 2)));
+        System.out.println(join("a.b.c".split("\\.")));
+        System.out.println(join("a::b".split("::")));
+        System.out.println("abc".split(",")[0]);
+
+        char[] chars = a.toCharArray();
+        chars[0] = 'j';
+        System.out.println(new String(chars));
+        System.out.println(new String(chars, 1, 3));
+        System.out.println(String.valueOf(chars));
+        System.out.println(String.copyValueOf(chars, 2, 2));
+        System.out.println(new String(new byte[] { 104, 105 }));
+        System.out.println(new String().isEmpty());
+
+        // strings as objects
+        System.out.println(describe(a));
+        System.out.println(describe(new Object()));
+        CharSequence sequence = a;
+        System.out.println(sequence.length() + " " + sequence.charAt(4));
+        Comparable<String> comparable = a;
+        System.out.println(comparable.compareTo("hello"));
+        System.out.println(kind("one") + kind("t" + "wo") + kind(new String("two")) + kind("three"));
+
+        // a supplementary character is two chars
+        String smile = "smile " + "😀";
+        System.out.println(smile.length());
+        System.out.println(smile.indexOf(0x1F600));
+        System.out.println(smile);
+
+        try {
+            a.charAt(10);
+        } catch (StringIndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            a.substring(3, 2);
+        } catch (IndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            new String(chars, 1, 5);
+        } catch (StringIndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            a.repeat(-1);
+        } catch (IllegalArgumentException e) {
+            System.out.println(e.getMessage());
+        }
+        String missing = args.length == 0 ? null : a;
+        missing.length();
+    }
+}
@@ -0,0 +1,65 @@
+public class Main {
+    public static void main(String[] args) {
+        // chars up to 0xff fit in a byte, the others do not
+        String latin = "café";
+        String greek = "αβγ";
+        System.out.println(latin + " " + latin.length() + " " + latin.hashCode());
+        System.out.println(greek + " " + greek.length() + " " + greek.hashCode());
+        System.out.println((int) latin.charAt(3) + " " + (int) greek.charAt(1));
+
+        // a concatenation widens once a char does not fit
+        String mixed = latin + greek + args.length;
+        System.out.println(mixed + " " + mixed.length() + " " + mixed.hashCode());
+        char wide = '€';
+        String euro = "price " + 5 + wide;
+        System.out.println(euro + " " + euro.length());
+        System.out.println(latin.concat(greek).equals(latin + greek));
+
+        // the part of a wide string that fits is equal to the same narrow string
+        String back = mixed.substring(0, 4);
+        System.out.println(back.equals(latin) + " " + (back.hashCode() == latin.hashCode()));
+        System.out.println(back.intern() == latin);
+        System.out.println(mixed.substring(4, 7).intern() == greek);
+        System.out.println(new String(new char[] { 'c', 'a', 'f', 'é' }).equals(latin));
+
+        // searching a narrow string for a wide char
+        System.out.println(latin.indexOf('α') + " " + latin.indexOf(greek) + " " + latin.lastIndexOf("é"));
+        System.out.println(mixed.indexOf('β') + " " + mixed.indexOf("éα") + " " + mixed.lastIndexOf('a'));
+        System.out.println(mixed.contains("fé") + " " + mixed.startsWith(latin) + " " + mixed.endsWith("γ0"));
+
+        System.out.println(latin.compareTo(greek) + " " + greek.compareTo(latin) + " " + latin.compareTo(mixed));
+        System.out.println(mixed.replace('β', 'b') + " " + mixed.replace("αβγ", "abc").equals("caféabc0"));
+        System.out.println(greek.toUpperCase() + " " + latin.toUpperCase() + " " + "ÉTÉ".toLowerCase());
+        System.out.println("é".repeat(3) + " " + greek.repeat(2));
+
+        String[] parts = ("α,b,é").split(",");
+        System.out.println(parts.length + " " + parts[0] + parts[1] + parts[2]);
+
+        switch (mixed.substring(4, 7)) {
+            case "αβγ":
+                System.out.println("greek");
+                break;
+            default:
+                System.out.println("no match");
+        }
+
+        char[] chars = greek.toCharArray();
+        System.out.println(chars.length + " " + (int) chars[2]);
+        try {
+            latin.charAt(4);
+        } catch (StringIndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            greek.charAt(-1);
+        } catch (StringIndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            mixed.substring(9);
+        } catch (StringIndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+        System.out.println(mixed.length() / args.length);
+    }
+}
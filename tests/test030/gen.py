@@ -0,0 +1,6 @@
+# string concatenation the way javac compiled it before invokedynamic, with a
+# StringBuilder appending each value
+import glob
+import subprocess
+
+subprocess.check_call(['javac', '-XDstringConcat=inline', '-d', '.'] + glob.glob('src/*.java'))
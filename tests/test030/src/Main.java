@@ -0,0 +1,103 @@
+public class Main {
+    static String describe(int i, long l, float f, double d, char c, boolean b, String s, Object o) {
+        return "i=" + i + " l=" + l + " f=" + f + " d=" + d + " c=" + c + " b=" + b + " s=" + s + " o=" + o;
+    }
+
+    static String check(StringBuilder builder, int index) {
+        try {
+            return "" + builder.charAt(index);
+        } catch (StringIndexOutOfBoundsException e) {
+            return e.getMessage();
+        }
+    }
+
+    public static void main(String[] args) {
+        System.out.println(describe(-7, 1L << 40, 1.0f / 3, 1e21, 'x', true, null, null));
+        System.out.println(describe(0, -1, 1e-5f, 100.0, 'é', false, "str", "obj"));
+        System.out.println("" + Float.MAX_VALUE + " " + Double.MIN_VALUE + " " + 0.1f + " " + (0.1 + 0.2) + " " + -0.0 + " " + 1.0 / 0);
+        String s = "a";
+        s += 'b';
+        s += 3;
+        s += 4.5;
+        System.out.println(s);
+
+        StringBuilder builder = new StringBuilder();
+        builder.append("abc").append(1).append('-').append(2.5f).append(true).append((Object) null).append(7L).append(0.125);
+        System.out.println(builder.toString() + " " + builder.length());
+        builder.setLength(3);
+        System.out.println(builder.toString());
+        builder.insert(0, 42).insert(2, '|').insert(builder.length(), false).insert(1, "xy").insert(0, new char[] { 'c', 'h' });
+        System.out.println(builder.toString());
+        builder.deleteCharAt(0).deleteCharAt(builder.length() - 1);
+        System.out.println(builder.toString());
+        System.out.println(builder.reverse().toString());
+        System.out.println(new StringBuilder("αβ").append("c").reverse().toString());
+        System.out.println(new StringBuilder().appendCodePoint(0x1F600).append("x").reverse().toString());
+        System.out.println(new StringBuilder().append(new char[] { 'h', 'e', 'y' }).append(new char[] { 'a', 'b', 'c', 'd' }, 1, 2).append("sequence", 2, 5).append((CharSequence) null, 1, 3).toString());
+        System.out.println(new StringBuilder(builder).append(builder).toString());
+        System.out.println(builder.substring(2) + " " + builder.substring(1, 3) + " " + builder.subSequence(0, 2));
+        System.out.println(new StringBuilder("abc").compareTo(new StringBuilder("abd")));
+
+        // setLength pads with null chars, which the string keeps
+        StringBuilder padded = new StringBuilder("ab");
+        padded.setLength(4);
+        String paddedString = padded.toString();
+        System.out.println(paddedString.length() + " " + (int) paddedString.charAt(3));
+
+        // toString makes a new string each time, and changing the builder later leaves it as it was
+        StringBuilder growing = new StringBuilder(2);
+        String before = growing.append("start").toString();
+        for (int i = 0; i < 1000; i++) {
+            growing.append(i % 10);
+        }
+        System.out.println(before + " " + growing.length() + " " + growing.toString().hashCode() + " " + (growing.toString() == growing.toString()));
+        System.out.println(growing.toString().equals(growing.toString()));
+
+        StringBuffer buffer = new StringBuffer("buf");
+        buffer.append(1).append('c').append(false).insert(0, 9.5).reverse();
+        System.out.println(buffer.toString() + " " + buffer.length() + " " + buffer.charAt(0));
+        System.out.println(new String(buffer) + new String(new StringBuilder("sb")));
+        System.out.println("string".contains(new StringBuilder("rin")) + " " + "abc".replace(new StringBuilder("b"), new StringBuffer("B")));
+
+        CharSequence sequence = new StringBuilder("seq");
+        System.out.println(sequence.length() + " " + sequence.charAt(1) + " " + sequence.subSequence(1, 3));
+
+        System.out.println(check(builder, 1) + " " + check(builder, 99) + " " + check(new StringBuilder("αβ"), -1));
+        try {
+            builder.deleteCharAt(99);
+        } catch (StringIndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            builder.insert(99, "x");
+        } catch (StringIndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            builder.setLength(-1);
+        } catch (StringIndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            builder.substring(3, 1);
+        } catch (StringIndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            builder.append(new char[] { 'a' }, 0, 2);
+        } catch (IndexOutOfBoundsException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            builder.appendCodePoint(-1);
+        } catch (IllegalArgumentException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            new StringBuilder(-1);
+        } catch (NegativeArraySizeException e) {
+            System.out.println(e.getMessage());
+        }
+        System.out.println(builder.length() / args.length);
+    }
+}
@@ -0,0 +1,143 @@
+public class Main {
+    static class Plain {
+        int value;
+
+        Plain(int value) {
+            this.value = value;
+        }
+
+        Object copy() throws CloneNotSupportedException {
+            return clone();
+        }
+    }
+
+    static class Point implements Cloneable {
+        int x;
+        int[] history = new int[] { 1, 2 };
+        String label = "p";
+
+        Point(int x) {
+            this.x = x;
+        }
+
+        @Override
+        public Point clone() {
+            try {
+                return (Point) super.clone();
+            } catch (CloneNotSupportedException e) {
+                throw new AssertionError(e);
+            }
+        }
+    }
+
+    static class Named {
+        String name;
+
+        Named(String name) {
+            this.name = name;
+        }
+
+        @Override
+        public boolean equals(Object other) {
+            return other instanceof Named && ((Named) other).name.equals(name);
+        }
+
+        @Override
+        public int hashCode() {
+            return 255;
+        }
+    }
+
+    static class Shown {
+        @Override
+        public String toString() {
+            return "shown";
+        }
+    }
+
+    interface Shape {
+    }
+
+    static class Square implements Shape {
+    }
+
+    public static void main(String[] args) throws Exception {
+        Object a = new Object();
+        Object b = new Object();
+        System.out.println(a.equals(a) + " " + a.equals(b) + " " + a.equals(null));
+        System.out.println(a.hashCode() == a.hashCode());
+        System.out.println(a.hashCode() == System.identityHashCode(a));
+        System.out.println(a.hashCode() >= 0);
+        System.out.println(System.identityHashCode(null));
+        System.out.println(a.toString().startsWith("java.lang.Object@"));
+        System.out.println(a.toString().equals(a.toString()));
+        System.out.println(String.valueOf(a).equals(a.toString()) + " " + ("" + a).equals(a.toString()));
+
+        // toString uses the hash code of the class
+        Named named = new Named("n");
+        System.out.println(named.toString());
+        System.out.println(named.equals(new Named("n")) + " " + named.equals(new Named("m")) + " " + (System.identityHashCode(named) == 255));
+        System.out.println(new Shown());
+        System.out.println((Object) "a string");
+        Shape shape = new Square();
+        System.out.println(shape.toString().startsWith("Main$Square@") + " " + shape.equals(shape) + " " + (shape.hashCode() == System.identityHashCode(shape)));
+
+        // every class has a single Class object
+        System.out.println(a.getClass() == b.getClass());
+        System.out.println(a.getClass().getName() + " " + named.getClass().getName() + " " + "s".getClass().getName());
+        System.out.println(new int[0].getClass().getName() + " " + new String[0][0].getClass().getName());
+        System.out.println(new int[1].getClass() == new int[2].getClass());
+        System.out.println(named.getClass() == a.getClass());
+
+        // clone makes a shallow copy
+        Point point = new Point(3);
+        Point copy = point.clone();
+        copy.x = 4;
+        copy.history[0] = 9;
+        System.out.println(point.x + " " + copy.x + " " + (copy != point) + " " + (copy.history == point.history) + " " + point.history[0] + " " + (copy.label == point.label));
+        System.out.println(copy.getClass() == Point.class);
+        try {
+            new Plain(1).copy();
+        } catch (CloneNotSupportedException e) {
+            System.out.println("CloneNotSupportedException " + e.getMessage());
+        }
+
+        int[] numbers = { 1, 2, 3 };
+        int[] numbersCopy = numbers.clone();
+        numbersCopy[0] = 10;
+        System.out.println(numbers[0] + " " + numbersCopy[0] + " " + numbersCopy.length);
+        String[][] grid = { { "a" }, { "b" } };
+        String[][] gridCopy = grid.clone();
+        gridCopy[1] = new String[] { "c" };
+        gridCopy[0][0] = "z";
+        System.out.println(grid[0][0] + grid[1][0] + gridCopy[0][0] + gridCopy[1][0]);
+        Object array = numbers;
+        System.out.println(array.equals(numbers) + " " + array.equals(numbersCopy) + " " + (array.hashCode() == System.identityHashCode(numbers)));
+        System.out.println(array.toString().startsWith("[I@") + " " + numbers.toString().equals(array.toString()));
+
+        // there is no other thread, so waiting for a short while ends by itself
+        synchronized (a) {
+            a.notify();
+            a.notifyAll();
+            a.wait(1);
+            a.wait(0, 5);
+        }
+        try {
+            synchronized (a) {
+                a.wait(-1);
+            }
+        } catch (IllegalArgumentException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            synchronized (a) {
+                a.wait(0, 1000000);
+            }
+        } catch (IllegalArgumentException e) {
+            System.out.println(e.getMessage());
+        }
+
+        Object missing = args.length == 0 ? null : a;
+        missing.hashCode();
+    }
+}
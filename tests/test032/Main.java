@@ -0,0 +1,53 @@
+public class Main {
+    interface IntOp {
+        int apply(int n);
+    }
+
+    interface Walk {
+        Object step(Object node, int depth);
+    }
+
+    static IntOp count;
+    static Walk walk;
+    static int deepest;
+
+    static int viaMethod(int n) {
+        return n == 0 ? 0 : 1 + count.apply(n - 1);
+    }
+
+    public static void main(String[] args) {
+        count = n -> n == 0 ? 0 : 1 + count.apply(n - 1);
+        System.out.println(count.apply(2000));
+
+        // recursion that never ends has to run out of java stack, not native stack
+        IntOp forever = new IntOp() {
+            public int apply(int n) {
+                return count.apply(n + 1);
+            }
+        };
+        count = n -> {
+            deepest = n;
+            return forever.apply(n);
+        };
+        try {
+            count.apply(0);
+            System.out.println("no overflow");
+        } catch (StackOverflowError e) {
+            System.out.println("overflow " + (deepest > 1000));
+        }
+
+        // a method reference recursing through a static method
+        count = Main::viaMethod;
+        System.out.println(count.apply(1500));
+
+        walk = (node, depth) -> depth == 0 ? node : walk.step(depth < 4 ? "<" + node + ">" : node, depth - 1);
+        System.out.println(walk.step("x", 3));
+        try {
+            walk.step("", Integer.MAX_VALUE);
+            System.out.println("no overflow");
+        } catch (StackOverflowError e) {
+            System.out.println("overflow");
+        }
+        System.out.println(count.apply(10));
+    }
+}
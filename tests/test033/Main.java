@@ -0,0 +1,61 @@
+import java.lang.invoke.MethodHandle;
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.MethodType;
+
+public class Main {
+    interface ToInt {
+        int apply(String s);
+    }
+
+    interface Join {
+        String apply(String a, String b);
+    }
+
+    interface Copy {
+        int[] apply(int[] array);
+    }
+
+    interface Show {
+        String apply(Object o);
+    }
+
+    static String join(int[] values) {
+        String result = "";
+        for (int i = 0; i < values.length; i++) {
+            result += (i == 0 ? "" : ",") + values[i];
+        }
+        return result;
+    }
+
+    public static void main(String[] args) throws Throwable {
+        ToInt length = String::length;
+        System.out.println(length.apply("hello"));
+        System.out.println(length.apply(""));
+
+        Join concat = String::concat;
+        System.out.println(concat.apply("foo", "bar"));
+
+        int[] original = new int[] { 1, 2, 3 };
+        Copy copy = int[]::clone;
+        int[] copied = copy.apply(original);
+        copied[0] = 9;
+        System.out.println(join(original) + " " + join(copied));
+
+        Show show = Object::toString;
+        System.out.println(show.apply("text"));
+
+        MethodHandle handle = MethodHandles.lookup().findVirtual(String.class, "length", MethodType.methodType(int.class));
+        int n = (int) handle.invokeExact("four");
+        System.out.println(n);
+
+        MethodHandle charAt = MethodHandles.lookup().findVirtual(String.class, "charAt", MethodType.methodType(char.class, int.class));
+        char c = (char) charAt.invokeExact("abc", 1);
+        System.out.println(c);
+
+        try {
+            length.apply(null);
+        } catch (NullPointerException e) {
+            System.out.println("null receiver");
+        }
+    }
+}
@@ -0,0 +1,26 @@
+public class Main {
+    static final String HIGH = "x\uD83D";
+
+    public static void main(String[] args) {
+        String high = "a\uD83D";
+        System.out.println(high.length());
+        System.out.println((int) high.charAt(1));
+        System.out.println((int) "\uDE00".charAt(0));
+
+        String joined = high + "\uDE00";
+        System.out.println(joined.equals("a😀"));
+        System.out.println(joined.length());
+        System.out.println(joined == "a😀");
+        System.out.println(joined.intern() == "a😀");
+
+        // a literal split in two with a variable between the halves goes through the concatenation recipe
+        String middle = "-";
+        String recipe = "\uD83D" + middle + "\uDE00";
+        System.out.println((int) recipe.charAt(0) + " " + (int) recipe.charAt(2));
+
+        System.out.println((int) HIGH.charAt(1));
+        System.out.println(high.concat("\uDE00").equals("a😀"));
+        System.out.println(("\uD83D".hashCode()));
+        System.out.println(high.indexOf("\uD83D"));
+    }
+}
@@ -0,0 +1,52 @@
+import java.lang.invoke.MethodHandle;
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.MethodType;
+import java.lang.invoke.WrongMethodTypeException;
+
+public class Main {
+    static int twice(int x) {
+        return 2 * x;
+    }
+
+    static String greet(String greeting, String name) {
+        return greeting + ", " + name;
+    }
+
+    public static void main(String[] args) throws Throwable {
+        MethodHandles.Lookup lookup = MethodHandles.lookup();
+        MethodHandle twice = lookup.findStatic(Main.class, "twice", MethodType.methodType(int.class, int.class));
+
+        // widening needs no boxing
+        MethodHandle wide = twice.asType(MethodType.methodType(long.class, short.class));
+        long result = (long) wide.invokeExact((short) 21);
+        System.out.println(result);
+
+        try {
+            twice.asType(MethodType.methodType(long.class, String.class));
+            System.out.println("converted");
+        } catch (WrongMethodTypeException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            MethodHandles.insertArguments(twice, 0, "text");
+            System.out.println("inserted");
+        } catch (ClassCastException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            MethodHandles.insertArguments(twice, 0, new int[] { 1 });
+            System.out.println("inserted");
+        } catch (ClassCastException e) {
+            System.out.println("array rejected");
+        }
+
+        MethodHandle greet = lookup.findStatic(Main.class, "greet", MethodType.methodType(String.class, String.class, String.class));
+        MethodHandle hello = MethodHandles.insertArguments(greet, 0, "hello");
+        System.out.println((String) hello.invokeExact("world"));
+
+        MethodHandle general = greet.asType(MethodType.methodType(Object.class, Object.class, Object.class));
+        System.out.println((Object) general.invokeExact((Object) "hi", (Object) "there"));
+    }
+}
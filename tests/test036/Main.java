@@ -0,0 +1,51 @@
+public class Main {
+    enum Color {
+        RED, GREEN, BLUE
+    }
+
+    enum Op {
+        ADD {
+            int apply(int a, int b) {
+                return a + b;
+            }
+        },
+        MUL {
+            int apply(int a, int b) {
+                return a * b;
+            }
+        };
+
+        abstract int apply(int a, int b);
+    }
+
+    public static void main(String[] args) {
+        System.out.println(Color.valueOf("GREEN").ordinal());
+        System.out.println(Color.valueOf("BLUE") == Color.BLUE);
+        System.out.println(Enum.valueOf(Color.class, "RED"));
+        System.out.println(Op.valueOf("MUL").apply(6, 7));
+
+        String[] names = { "PURPLE", "red", "" };
+        for (String name : names) {
+            try {
+                Color.valueOf(name);
+                System.out.println("found " + name);
+            } catch (IllegalArgumentException e) {
+                System.out.println(e.getMessage());
+            }
+        }
+
+        try {
+            Color.valueOf(null);
+        } catch (NullPointerException e) {
+            System.out.println(e.getMessage());
+        }
+
+        switch (Color.valueOf("RED")) {
+            case RED:
+                System.out.println("red");
+                break;
+            default:
+                System.out.println("other");
+        }
+    }
+}
@@ -0,0 +1,89 @@
+import java.util.function.BiConsumer;
+import java.util.function.BiFunction;
+import java.util.function.BinaryOperator;
+import java.util.function.BooleanSupplier;
+import java.util.function.Consumer;
+import java.util.function.DoubleBinaryOperator;
+import java.util.function.DoubleUnaryOperator;
+import java.util.function.Function;
+import java.util.function.IntBinaryOperator;
+import java.util.function.IntConsumer;
+import java.util.function.IntFunction;
+import java.util.function.IntPredicate;
+import java.util.function.IntSupplier;
+import java.util.function.IntUnaryOperator;
+import java.util.function.LongBinaryOperator;
+import java.util.function.ObjIntConsumer;
+import java.util.function.Predicate;
+import java.util.function.Supplier;
+import java.util.function.ToIntFunction;
+import java.util.function.UnaryOperator;
+
+public class Main {
+    static IntUnaryOperator factorial;
+
+    static int max(int a, int b) {
+        return a > b ? a : b;
+    }
+
+    static String apply(Function<String, String> f, String value) {
+        return f.apply(value);
+    }
+
+    public static void main(String[] args) {
+        Function<String, String> upper = String::toUpperCase;
+        System.out.println(upper.apply("shout"));
+        System.out.println(apply(s -> s + s, "ab"));
+
+        UnaryOperator<String> wrap = s -> "[" + s + "]";
+        System.out.println(apply(wrap, "x"));
+
+        BiFunction<String, String, String> join = (a, b) -> a + "-" + b;
+        BinaryOperator<String> longer = (a, b) -> a.length() >= b.length() ? a : b;
+        System.out.println(join.apply("left", "right") + " " + longer.apply("ab", "abc"));
+
+        String prefix = "value: ";
+        Supplier<String> supplier = () -> prefix + 42;
+        System.out.println(supplier.get());
+
+        Consumer<String> printer = System.out::println;
+        printer.accept("consumed");
+        BiConsumer<String, String> pair = (a, b) -> System.out.println(a + "=" + b);
+        pair.accept("key", "value");
+
+        Predicate<String> empty = String::isEmpty;
+        System.out.println(empty.test("") + " " + empty.test("no"));
+
+        IntBinaryOperator add = (a, b) -> a + b;
+        IntBinaryOperator max = Main::max;
+        System.out.println(add.applyAsInt(3, 4) + " " + max.applyAsInt(3, 4));
+
+        factorial = n -> n <= 1 ? 1 : n * factorial.applyAsInt(n - 1);
+        System.out.println(factorial.applyAsInt(10));
+
+        IntPredicate even = n -> n % 2 == 0;
+        IntSupplier seven = () -> 7;
+        IntFunction<String> stars = n -> "*".repeat(n);
+        System.out.println(even.test(seven.getAsInt()) + " " + stars.apply(3));
+
+        int[] total = new int[1];
+        IntConsumer accumulate = n -> total[0] += n;
+        accumulate.accept(5);
+        accumulate.accept(6);
+        System.out.println(total[0]);
+
+        ToIntFunction<String> length = String::length;
+        ObjIntConsumer<String> repeat = (s, n) -> System.out.println(s.repeat(n));
+        repeat.accept("ab", length.applyAsInt("xyz"));
+
+        LongBinaryOperator multiply = (a, b) -> a * b;
+        System.out.println(multiply.applyAsLong(1L << 20, 1L << 20));
+
+        DoubleUnaryOperator half = d -> d / 2;
+        DoubleBinaryOperator square = (a, b) -> a * a + b * b;
+        System.out.println(half.applyAsDouble(5) + " " + square.applyAsDouble(3, 4));
+
+        BooleanSupplier yes = () -> true;
+        System.out.println(yes.getAsBoolean());
+    }
+}
@@ -0,0 +1,47 @@
+# a method with more locals than a byte can index, which javac reaches with wide,
+# and a loop too long for goto, which javac closes with goto_w
+import os
+import subprocess
+import tempfile
+
+LOCALS = 300
+STATEMENTS = 5000
+
+lines = []
+lines.append('public class Main {')
+lines.append('    static int manyLocals(int seed) {')
+for i in range(LOCALS):
+    lines.append('        int v%d = seed + %d;' % (i, i))
+lines.append('        v299 += 1000;')
+lines.append('        v298 -= 3000;')
+lines.append('        long wide = v297;')
+lines.append('        double half = v296 / 2.0;')
+lines.append('        String name = "v" + v295;')
+lines.append('        return v0 + v150 + v299 + v298 + (int) wide + (int) half + name.length();')
+lines.append('    }')
+lines.append('')
+lines.append('    static int longLoop(int n) {')
+lines.append('        int total = 0;')
+lines.append('        for (int i = 0; i < n; i++) {')
+for i in range(STATEMENTS):
+    lines.append('            total += i ^ %d;' % (i % 100))
+lines.append('        }')
+lines.append('        return total;')
+lines.append('    }')
+lines.append('')
+lines.append('    public static void main(String[] args) {')
+lines.append('        int x = 5;')
+lines.append('        x += 1000;')
+lines.append('        System.out.println(x);')
+lines.append('        x -= 70000;')
+lines.append('        System.out.println(x);')
+lines.append('        System.out.println(manyLocals(1));')
+lines.append('        System.out.println(longLoop(3));')
+lines.append('    }')
+lines.append('}')
+
+with tempfile.TemporaryDirectory() as directory:
+    source = os.path.join(directory, 'Main.java')
+    with open(source, 'w') as f:
+        f.write('\n'.join(lines) + '\n')
+    subprocess.check_call(['javac', '-d', '.', source])
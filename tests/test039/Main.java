@@ -0,0 +1,33 @@
+public class Main {
+    static int depth;
+
+    static class Mirror {
+        public String toString() {
+            depth++;
+            return "M" + this;
+        }
+    }
+
+    static class Countdown {
+        int n;
+
+        Countdown(int n) {
+            this.n = n;
+        }
+
+        public String toString() {
+            return n == 0 ? "" : n + " " + new Countdown(n - 1);
+        }
+    }
+
+    public static void main(String[] args) {
+        try {
+            System.out.println(new Mirror());
+        } catch (StackOverflowError e) {
+            System.out.println("toString overflowed " + (depth > 100));
+        }
+        System.out.println(new Countdown(5));
+        // nesting that ends well before the stack does
+        System.out.println(new Countdown(400).toString().length());
+    }
+}
@@ -0,0 +1,57 @@
+public class Main {
+    static int depth;
+
+    static class Appender {
+        void append(StringBuilder sb) {
+            sb.append(this);
+        }
+
+        public String toString() {
+            depth++;
+            StringBuilder sb = new StringBuilder();
+            append(sb);
+            return sb.toString();
+        }
+    }
+
+    static class Inserter {
+        public String toString() {
+            depth++;
+            return new StringBuffer("!").insert(0, this).toString();
+        }
+    }
+
+    static class Chain {
+        int n;
+
+        Chain(int n) {
+            this.n = n;
+        }
+
+        public String toString() {
+            StringBuilder sb = new StringBuilder().append(n);
+            if (n > 0) {
+                sb.append(',').append(new Chain(n - 1));
+            }
+            return sb.toString();
+        }
+    }
+
+    public static void main(String[] args) {
+        try {
+            System.out.println(new Appender());
+        } catch (StackOverflowError e) {
+            System.out.println("append overflowed " + (depth > 100));
+        }
+
+        depth = 0;
+        try {
+            System.out.println(new Inserter());
+        } catch (StackOverflowError e) {
+            System.out.println("insert overflowed " + (depth > 100));
+        }
+
+        System.out.println(new Chain(5));
+        System.out.println(new Chain(300).toString().length());
+    }
+}
@@ -0,0 +1,97 @@
+import java.lang.invoke.MethodHandle;
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.MethodType;
+import java.lang.invoke.WrongMethodTypeException;
+import java.util.function.BinaryOperator;
+import java.util.function.Function;
+import java.util.function.Supplier;
+
+public class Main {
+    static String describe(Object value) {
+        return "object " + value;
+    }
+
+    static int twice(int x) {
+        return 2 * x;
+    }
+
+    static long widen(long x) {
+        return x + 1;
+    }
+
+    static boolean negate(boolean b) {
+        return !b;
+    }
+
+    public static void main(String[] args) throws Throwable {
+        MethodHandles.Lookup lookup = MethodHandles.lookup();
+
+        // an int passed to a handle taking an Object is boxed
+        MethodHandle describe = lookup.findStatic(Main.class, "describe", MethodType.methodType(String.class, Object.class));
+        System.out.println((String) describe.invoke(42));
+        System.out.println((String) describe.invoke(2.5));
+        System.out.println((String) describe.invoke('c'));
+        System.out.println((String) describe.invoke(true));
+
+        // and an Integer or an Object holding one is unboxed for a handle taking an int
+        MethodHandle twice = lookup.findStatic(Main.class, "twice", MethodType.methodType(int.class, int.class));
+        Integer boxed = 21;
+        System.out.println((int) twice.invoke(boxed));
+        Object general = twice.invoke((Object) Integer.valueOf(5));
+        System.out.println(general);
+        MethodHandle generic = twice.asType(MethodType.methodType(Object.class, Object.class));
+        System.out.println(generic.invoke((Object) 7));
+        System.out.println(twice.invokeWithArguments(8));
+
+        // unboxing then widening
+        MethodHandle widen = lookup.findStatic(Main.class, "widen", MethodType.methodType(long.class, long.class));
+        System.out.println((long) widen.invoke(Integer.valueOf(9)));
+
+        MethodHandle negate = lookup.findStatic(Main.class, "negate", MethodType.methodType(boolean.class, boolean.class));
+        System.out.println((Boolean) negate.invoke(Boolean.TRUE.booleanValue()));
+
+        try {
+            twice.invoke((Object) "nine");
+        } catch (ClassCastException e) {
+            System.out.println("not an int");
+        }
+        try {
+            twice.invoke((Integer) null);
+        } catch (NullPointerException e) {
+            System.out.println("null unboxed");
+        }
+        try {
+            twice.asType(MethodType.methodType(int.class, Long.class));
+        } catch (WrongMethodTypeException e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            describe.asType(MethodType.methodType(String.class, Long.class)).invoke(3L);
+            negate.asType(MethodType.methodType(Object.class, int.class));
+        } catch (WrongMethodTypeException e) {
+            System.out.println(e.getMessage());
+        }
+
+        // lambdas over boxed types call methods on primitives
+        Function<Integer, Integer> doubler = Main::twice;
+        System.out.println(doubler.apply(12));
+        BinaryOperator<Integer> add = (a, b) -> a + b;
+        System.out.println(add.apply(40, 2));
+        Supplier<Long> seven = () -> 7L;
+        System.out.println(seven.get() * 6);
+
+        // valueOf caches small values
+        Integer a = 100;
+        Integer b = 100;
+        Integer c = 1000;
+        Integer d = 1000;
+        System.out.println((a == b) + " " + (c == d) + " " + c.equals(d) + " " + c.hashCode());
+        Long big = 1L << 40;
+        Double half = 0.5;
+        Character letter = 'x';
+        System.out.println(big.hashCode() + " " + half + " " + half.intValue() + " " + letter + " " + big.intValue());
+        Object o = 3;
+        System.out.println(o.equals(3) + " " + o.equals(3L));
+        System.out.println((Boolean.valueOf(true) == Boolean.TRUE) + " " + Boolean.FALSE);
+    }
+}